@@ -19,6 +19,12 @@ export interface FindAffectedOptions {
   enableProfiling?: boolean
   /** Lockfile change detection strategy: "none", "direct", "full" (default: "direct") */
   lockfileStrategy?: string
+  /** Trace changes to individual class, object literal and enum members (default: false) */
+  memberPrecision?: boolean
+  /** Which dependency edges propagate changes: "all", "runtime" (default: "all") */
+  propagation?: string
+  /** Whether implementation-only changes cross project boundaries: "full", "signature" (default: "full") */
+  boundaryMode?: string
 }
 
 export interface NapiProject {
@@ -29,4 +35,6 @@ export interface NapiProject {
   tsConfig?: string
   implicitDependencies: Array<string>
   targets: Array<string>
+  /** Project tags (project.json `tags` or package.json `domino.tags`) */
+  tags?: Array<string>
 }
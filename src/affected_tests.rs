@@ -0,0 +1,133 @@
+//! Test files affected by a change (`domino affected-tests`).
+//!
+//! The same symbol-level tracing that finds affected projects records every
+//! file it reaches; the test files among them (plus changed test files) are
+//! the only tests that can observe the change. Test files are those matching
+//! the test globs of [`TestFiles`]: the defaults plus `domino.testPatterns`.
+//! When a global input changed, every test file in the workspace is returned
+//! instead.
+
+use crate::core;
+use crate::error::Result;
+use crate::profiler::Profiler;
+use crate::semantic::WorkspaceAnalyzer;
+use crate::test_files::TestFiles;
+use crate::types::{AffectedTests, TestsFormat, TrueAffectedConfig};
+use crate::workspace;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Find the test files that are changed or transitively import a changed
+/// symbol. `extra_patterns` are globs relative to the workspace root matching
+/// test files in addition to the configured ones.
+pub fn find_affected_tests(
+  config: TrueAffectedConfig,
+  extra_patterns: &[String],
+  profiler: Arc<Profiler>,
+) -> Result<AffectedTests> {
+  let test_files = TestFiles::new(&config.cwd, &config.projects, extra_patterns)?;
+  find_affected_matching(
+    config,
+    None,
+    |file| test_files.matches_pattern(file),
+    profiler,
+  )
+}
+
+/// Find the files selected by `is_match` that are changed or transitively
+/// import a changed symbol, or all of them when a global input changed.
+/// `analyzer`, when given, is an already built analyzer of `config.projects`.
+pub(crate) fn find_affected_matching(
+  config: TrueAffectedConfig,
+  analyzer: Option<&WorkspaceAnalyzer>,
+  is_match: impl Fn(&Path) -> bool,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedTests> {
+  let cwd = config.cwd.clone();
+  let affected = match analyzer {
+    Some(analyzer) => core::find_affected_files_using(config, analyzer, profiler)?,
+    None => core::find_affected_files(config, profiler)?,
+  };
+  if !affected.global_triggers.is_empty() {
+    debug!(
+      "{} global inputs changed; every matching file is affected",
+      affected.global_triggers.len()
+    );
+    let mut files = workspace::workspace_files(&cwd)?;
+    files.retain(|file| is_match(file));
+    files.sort();
+    return Ok(AffectedTests {
+      files,
+      global: true,
+    });
+  }
+
+  let files: Vec<PathBuf> = affected
+    .files
+    .into_iter()
+    .filter(|file| is_match(file) && cwd.join(file).exists())
+    .collect();
+  debug!("{} affected matching files", files.len());
+  Ok(AffectedTests {
+    files,
+    global: false,
+  })
+}
+
+/// Arguments selecting `tests` for a test runner, or the JSON / text listing.
+/// Empty when there is nothing to run.
+pub fn format_tests(tests: &AffectedTests, format: TestsFormat) -> String {
+  let paths: Vec<String> = tests
+    .files
+    .iter()
+    .map(|file| file.to_string_lossy().into_owned())
+    .collect();
+  match format {
+    TestsFormat::Json => serde_json::to_string(tests).unwrap(),
+    TestsFormat::Text => paths.join("\n"),
+    _ if paths.is_empty() => String::new(),
+    // Jest matches the pattern as a regex against absolute paths
+    TestsFormat::Jest => format!(
+      "--testPathPattern=({})$",
+      paths
+        .iter()
+        .map(|path| regex::escape(path))
+        .collect::<Vec<_>>()
+        .join("|")
+    ),
+    TestsFormat::Vitest | TestsFormat::Playwright => paths.join(" "),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_format_tests() {
+    let tests = AffectedTests {
+      files: vec![
+        PathBuf::from("libs/ui/src/button.spec.tsx"),
+        PathBuf::from("apps/web/src/page.test.ts"),
+      ],
+      global: false,
+    };
+    assert_eq!(
+      format_tests(&tests, TestsFormat::Jest),
+      r"--testPathPattern=(libs/ui/src/button\.spec\.tsx|apps/web/src/page\.test\.ts)$"
+    );
+    assert_eq!(
+      format_tests(&tests, TestsFormat::Vitest),
+      "libs/ui/src/button.spec.tsx apps/web/src/page.test.ts"
+    );
+    assert_eq!(
+      format_tests(&tests, TestsFormat::Json),
+      r#"{"files":["libs/ui/src/button.spec.tsx","apps/web/src/page.test.ts"],"global":false}"#
+    );
+    assert_eq!(
+      format_tests(&AffectedTests::default(), TestsFormat::Playwright),
+      ""
+    );
+  }
+}
@@ -0,0 +1,1174 @@
+//! Public API surface diff (`domino api-diff`).
+//!
+//! For every project with a resolvable entry point (package.json `exports` /
+//! `module` / `main`, falling back to an Nx-style `index.ts` in the source
+//! root), the exported API is read at the base revision and at head and the
+//! two are compared by name. Each export is described by its kind and a
+//! normalized signature — the declaration text with bodies, initializers and
+//! comments stripped and whitespace canonicalized — so formatting-only edits
+//! and implementation changes never show up, while a changed parameter,
+//! return type or public member does.
+//!
+//! Head is the working tree, read through the [`WorkspaceAnalyzer`] (its parsed
+//! files and `exports`). The base side re-parses `git show` snapshots with the
+//! same parser. Re-exports are followed through workspace modules on both
+//! sides; they are resolved against the working tree's file layout, so a
+//! barrel that re-exports a module deleted since base reports that export as
+//! an opaque re-export rather than its base signature.
+
+use crate::error::Result;
+use crate::git;
+use crate::profiler::Profiler;
+use crate::semantic::{create_resolve_options, WorkspaceAnalyzer};
+use crate::types::{ApiChange, ApiEntry, ApiKind, Export, Project, ProjectApiDiff};
+use oxc_ast::ast::{
+  BindingPatternKind, Class, ClassElement, Declaration, ExportDefaultDeclarationKind, Expression,
+  Function, ImportDeclarationSpecifier, Program, Statement, TSAccessibility,
+  TSModuleDeclarationName, VariableDeclaration,
+};
+use oxc_resolver::Resolver;
+use oxc_span::{GetSpan, Span};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Entry point file names probed in a project's source root when its
+/// package.json does not name a workspace source file
+const INDEX_FILES: &[&str] = &[
+  "index.ts",
+  "index.tsx",
+  "index.mts",
+  "index.js",
+  "index.jsx",
+  "index.mjs",
+];
+
+/// Compare every project's public API between `base` and the working tree.
+///
+/// `base` is resolved exactly like `domino affected` does: against the working
+/// tree it is `merge-base(base, HEAD)`, with an explicit `head` it is used
+/// directly. Only projects whose API changed are returned, sorted by name.
+pub fn find_api_changes(
+  cwd: &Path,
+  base: &str,
+  head: Option<&str>,
+  projects: Vec<Project>,
+  profiler: Arc<Profiler>,
+) -> Result<Vec<ProjectApiDiff>> {
+  let analyzer = WorkspaceAnalyzer::new(projects, cwd, profiler)?;
+  find_api_changes_using(&analyzer, cwd, base, head)
+}
+
+/// [`find_api_changes`] on an already built `analyzer` of the workspace
+pub fn find_api_changes_using(
+  analyzer: &WorkspaceAnalyzer,
+  cwd: &Path,
+  base: &str,
+  head: Option<&str>,
+) -> Result<Vec<ProjectApiDiff>> {
+  let revision = git::get_base_revision(cwd, base, head)?;
+  debug!("Comparing public API against {}", revision);
+
+  let resolver = Resolver::new(create_resolve_options(cwd, &analyzer.projects));
+
+  let mut head_api = ApiReader::new(analyzer, &resolver, cwd, None);
+  let mut base_api = ApiReader::new(analyzer, &resolver, cwd, Some(&revision));
+
+  let mut diffs = Vec::new();
+  for project in &analyzer.projects {
+    let Some(entry_point) = find_entry_point(analyzer, cwd, project) else {
+      debug!("No entry point found for project {}", project.name);
+      continue;
+    };
+
+    let head_entries = head_api.module_api(&entry_point);
+    let base_entries = base_api.module_api(&entry_point);
+    let diff = compare_api(&project.name, entry_point, base_entries, head_entries);
+    if !diff.is_empty() {
+      diffs.push(diff);
+    }
+  }
+
+  diffs.sort_by(|a, b| a.project.cmp(&b.project));
+  Ok(diffs)
+}
+
+/// Match base and head exports by name into additions, removals and changes
+fn compare_api(
+  project: &str,
+  entry_point: PathBuf,
+  base: Vec<ApiEntry>,
+  head: Vec<ApiEntry>,
+) -> ProjectApiDiff {
+  let base_by_name: FxHashMap<&str, &ApiEntry> =
+    base.iter().map(|e| (e.name.as_str(), e)).collect();
+  let head_names: FxHashSet<&str> = head.iter().map(|e| e.name.as_str()).collect();
+
+  let mut added = Vec::new();
+  let mut changed = Vec::new();
+  for entry in &head {
+    match base_by_name.get(entry.name.as_str()) {
+      None => added.push(entry.clone()),
+      Some(old) if old.kind != entry.kind || old.signature != entry.signature => {
+        changed.push(ApiChange {
+          name: entry.name.clone(),
+          kind: entry.kind,
+          base: old.signature.clone(),
+          head: entry.signature.clone(),
+        });
+      }
+      Some(_) => {}
+    }
+  }
+
+  let mut removed: Vec<ApiEntry> = base
+    .iter()
+    .filter(|e| !head_names.contains(e.name.as_str()))
+    .cloned()
+    .collect();
+
+  added.sort_by(|a, b| a.name.cmp(&b.name));
+  removed.sort_by(|a, b| a.name.cmp(&b.name));
+  changed.sort_by(|a, b| a.name.cmp(&b.name));
+
+  ProjectApiDiff {
+    project: project.to_string(),
+    entry_point,
+    added,
+    removed,
+    changed,
+  }
+}
+
+/// Locate the source file a project exposes as its public entry point.
+///
+/// package.json `exports` (the `"."` entry), `module` and `main` are tried in
+/// that order; a target only counts when it is a parsed workspace source file,
+/// so build outputs like `dist/index.js` fall through to the `index.*` probe
+/// in the source root.
+pub(crate) fn find_entry_point(
+  analyzer: &WorkspaceAnalyzer,
+  cwd: &Path,
+  project: &Project,
+) -> Option<PathBuf> {
+  let is_source = |path: &Path| analyzer.files.contains_key(path);
+
+  if let Ok(content) = fs::read_to_string(cwd.join(&project.root).join("package.json")) {
+    if let Ok(package) = serde_json::from_str::<serde_json::Value>(&content) {
+      let candidates = [
+        package.get("exports").and_then(root_export_target),
+        package.get("module").and_then(|v| v.as_str()),
+        package.get("main").and_then(|v| v.as_str()),
+      ];
+      for candidate in candidates.into_iter().flatten() {
+        let path = clean_path(&project.root.join(candidate));
+        if is_source(&path) {
+          return Some(path);
+        }
+      }
+    }
+  }
+
+  INDEX_FILES
+    .iter()
+    .map(|name| project.source_root.join(name))
+    .find(|path| is_source(path))
+}
+
+/// The `"."` target of a package.json `exports` field: a bare string, a
+/// conditions object, or a subpath map whose `"."` is either of those
+fn root_export_target(exports: &serde_json::Value) -> Option<&str> {
+  match exports {
+    serde_json::Value::String(target) => Some(target),
+    serde_json::Value::Object(map) => match map.get(".") {
+      Some(root) => root_export_target(root),
+      None => ["types", "import", "module", "default", "require"]
+        .iter()
+        .find_map(|condition| map.get(*condition).and_then(root_export_target)),
+    },
+    _ => None,
+  }
+}
+
+/// Drop `.` components so `libs/a/./src/index.ts` matches the analyzer's keys
+fn clean_path(path: &Path) -> PathBuf {
+  path
+    .components()
+    .filter(|c| !matches!(c, Component::CurDir))
+    .collect()
+}
+
+/// How a module binds a top-level name that it may export
+#[derive(Debug, Clone)]
+enum Binding {
+  /// A declaration in the module itself
+  Declared(ApiEntry),
+  /// `import { imported as local } from specifier` (`*` for namespace imports)
+  Imported { specifier: String, imported: String },
+  /// `export default local`
+  Alias(String),
+}
+
+/// Everything about one module that its public API depends on, copied out of
+/// the AST so it can be read without holding on to the parsed file
+#[derive(Debug, Default)]
+struct ModuleShape {
+  bindings: FxHashMap<String, Binding>,
+  /// The default export, when it is not covered by `bindings`
+  default: Option<Binding>,
+  exports: Vec<Export>,
+}
+
+impl ModuleShape {
+  /// Collect declarations, imports and exports from `program`.
+  ///
+  /// `exports` is the module's export list as [`WorkspaceAnalyzer`] records
+  /// it; declarations the analyzer does not list (inline `export interface`,
+  /// `export type`, `export enum`, `export namespace`) are appended here.
+  fn from_program(program: &Program, mut exports: Vec<Export>) -> Self {
+    let writer = SignatureWriter::new(program);
+    let mut shape = Self::default();
+
+    for statement in &program.body {
+      match statement {
+        Statement::ImportDeclaration(import) => {
+          let specifier = import.source.value.to_string();
+          for spec in import.specifiers.iter().flatten() {
+            let (local, imported) = match spec {
+              ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                (&s.local, s.imported.name().to_string())
+              }
+              ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                (&s.local, "default".to_string())
+              }
+              ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
+                (&s.local, "*".to_string())
+              }
+            };
+            shape.bindings.insert(
+              local.name.to_string(),
+              Binding::Imported {
+                specifier: specifier.clone(),
+                imported,
+              },
+            );
+          }
+        }
+        Statement::ExportNamedDeclaration(export) => {
+          if let Some(declaration) = &export.declaration {
+            for entry in writer.declaration(declaration) {
+              if !exports.iter().any(|e| e.exported_name == entry.name) {
+                exports.push(Export {
+                  exported_name: entry.name.clone(),
+                  local_name: None,
+                  re_export_from: None,
+                  is_namespace: false,
+                });
+              }
+              shape.declare(entry);
+            }
+          }
+        }
+        Statement::ExportDefaultDeclaration(export) => {
+          shape.default = writer.default_export(&export.declaration);
+        }
+        _ => {
+          if let Some(declaration) = statement.as_declaration() {
+            for entry in writer.declaration(declaration) {
+              shape.declare(entry);
+            }
+          }
+        }
+      }
+    }
+
+    shape.exports = exports;
+    shape
+  }
+
+  /// Record a declaration, joining function overloads into one signature
+  fn declare(&mut self, entry: ApiEntry) {
+    match self.bindings.get_mut(&entry.name) {
+      Some(Binding::Declared(existing))
+        if existing.kind == ApiKind::Function && entry.kind == ApiKind::Function =>
+      {
+        existing.signature = format!("{}; {}", existing.signature, entry.signature);
+      }
+      _ => {
+        self
+          .bindings
+          .insert(entry.name.clone(), Binding::Declared(entry));
+      }
+    }
+  }
+}
+
+/// Reads the public API of modules at one revision: the working tree
+/// (`revision: None`, straight from the analyzer) or a git revision.
+struct ApiReader<'w> {
+  analyzer: &'w WorkspaceAnalyzer,
+  resolver: &'w Resolver,
+  cwd: &'w Path,
+  revision: Option<&'w str>,
+  /// Memoized module APIs, including `default`
+  modules: FxHashMap<PathBuf, Vec<ApiEntry>>,
+  /// Modules currently being read, to cut `export *` cycles
+  in_progress: FxHashSet<PathBuf>,
+}
+
+impl<'w> ApiReader<'w> {
+  fn new(
+    analyzer: &'w WorkspaceAnalyzer,
+    resolver: &'w Resolver,
+    cwd: &'w Path,
+    revision: Option<&'w str>,
+  ) -> Self {
+    Self {
+      analyzer,
+      resolver,
+      cwd,
+      revision,
+      modules: FxHashMap::default(),
+      in_progress: FxHashSet::default(),
+    }
+  }
+
+  /// Shape of `path` at this reader's revision; `None` when the file does
+  /// not exist there
+  fn shape(&self, path: &Path) -> Option<ModuleShape> {
+    match self.revision {
+      None => {
+        let file_data = self.analyzer.files.get(path)?;
+        let exports = self.analyzer.exports.get(path).cloned().unwrap_or_default();
+        Some(ModuleShape::from_program(
+          file_data.semantic().nodes().program(),
+          exports,
+        ))
+      }
+      Some(revision) => {
+        let source = match git::get_file_at_revision(self.cwd, revision, path) {
+          Ok(Some(source)) => source,
+          Ok(None) => return None,
+          Err(e) => {
+            debug!("Failed to read {:?} at {}: {}", path, revision, e);
+            return None;
+          }
+        };
+        let file_data = match WorkspaceAnalyzer::parse_source(path, source) {
+          Ok(data) => data,
+          Err(e) => {
+            debug!("Failed to parse {:?} at {}: {}", path, revision, e);
+            return None;
+          }
+        };
+        let program = file_data.semantic().nodes().program();
+        Some(ModuleShape::from_program(
+          program,
+          WorkspaceAnalyzer::extract_exports(program),
+        ))
+      }
+    }
+  }
+
+  /// Every export of `path`, sorted by name
+  fn module_api(&mut self, path: &Path) -> Vec<ApiEntry> {
+    if let Some(entries) = self.modules.get(path) {
+      return entries.clone();
+    }
+    if !self.in_progress.insert(path.to_path_buf()) {
+      debug!("Re-export cycle through {:?}", path);
+      return Vec::new();
+    }
+
+    let mut entries: Vec<ApiEntry> = Vec::new();
+    if let Some(shape) = self.shape(path) {
+      for export in &shape.exports {
+        for entry in self.resolve_export(path, &shape, export) {
+          if !entries.iter().any(|e| e.name == entry.name) {
+            entries.push(entry);
+          }
+        }
+      }
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    self.in_progress.remove(path);
+    self.modules.insert(path.to_path_buf(), entries.clone());
+    entries
+  }
+
+  /// The API entries one export statement contributes
+  fn resolve_export(&mut self, path: &Path, shape: &ModuleShape, export: &Export) -> Vec<ApiEntry> {
+    let exported = &export.exported_name;
+
+    if let Some(specifier) = &export.re_export_from {
+      let target = self.resolve(path, specifier);
+      if export.is_namespace {
+        return vec![self.namespace(exported, specifier, target.as_deref())];
+      }
+      if exported == "*" {
+        return match target {
+          Some(target) => self
+            .module_api(&target)
+            .into_iter()
+            .filter(|e| e.name != "default")
+            .collect(),
+          None => vec![opaque(&format!("* from '{}'", specifier), specifier)],
+        };
+      }
+      let imported = export.local_name.as_deref().unwrap_or(exported);
+      return self
+        .imported(imported, specifier, target.as_deref())
+        .map(|entry| renamed(entry, exported))
+        .into_iter()
+        .collect();
+    }
+
+    let binding = match (&export.local_name, exported.as_str()) {
+      (Some(local), _) => shape.bindings.get(local).cloned(),
+      (None, "default") => shape.default.clone(),
+      (None, _) => shape.bindings.get(exported).cloned(),
+    };
+    binding
+      .and_then(|binding| self.binding_entry(path, shape, binding))
+      .map(|entry| renamed(entry, exported))
+      .into_iter()
+      .collect()
+  }
+
+  /// Describe a local binding, following imports into their source module
+  fn binding_entry(
+    &mut self,
+    path: &Path,
+    shape: &ModuleShape,
+    binding: Binding,
+  ) -> Option<ApiEntry> {
+    match binding {
+      Binding::Declared(entry) => Some(entry),
+      Binding::Imported {
+        specifier,
+        imported,
+      } => {
+        let target = self.resolve(path, &specifier);
+        if imported == "*" {
+          return Some(self.namespace(&imported, &specifier, target.as_deref()));
+        }
+        self.imported(&imported, &specifier, target.as_deref())
+      }
+      // `bindings` never holds an alias, so this recurses at most once
+      Binding::Alias(local) => {
+        let binding = shape.bindings.get(&local).cloned()?;
+        self.binding_entry(path, shape, binding)
+      }
+    }
+  }
+
+  /// Export `name` of the module at `target`, or an opaque entry when the
+  /// specifier does not resolve to a workspace module
+  fn imported(&mut self, name: &str, specifier: &str, target: Option<&Path>) -> Option<ApiEntry> {
+    match target {
+      Some(target) => self.module_api(target).into_iter().find(|e| e.name == name),
+      None => Some(opaque(name, specifier)),
+    }
+  }
+
+  /// A namespace export (`export * as ns`, or a re-exported namespace import),
+  /// whose signature lists the members of the target module
+  fn namespace(&mut self, name: &str, specifier: &str, target: Option<&Path>) -> ApiEntry {
+    let Some(target) = target else {
+      return opaque(name, specifier);
+    };
+    let members: Vec<String> = self
+      .module_api(target)
+      .into_iter()
+      .map(|e| format!("{}: {}", e.name, e.signature))
+      .collect();
+    ApiEntry {
+      name: name.to_string(),
+      kind: ApiKind::Namespace,
+      signature: format!("{{ {} }}", members.join("; ")),
+    }
+  }
+
+  fn resolve(&self, from_file: &Path, specifier: &str) -> Option<PathBuf> {
+    self
+      .analyzer
+      .resolve_workspace_specifier(self.resolver, self.cwd, from_file, specifier)
+  }
+}
+
+/// An export forwarded from a module that is not analyzed
+fn opaque(name: &str, specifier: &str) -> ApiEntry {
+  ApiEntry {
+    name: name.to_string(),
+    kind: ApiKind::ReExport,
+    signature: format!("from '{}'", specifier),
+  }
+}
+
+fn renamed(mut entry: ApiEntry, name: &str) -> ApiEntry {
+  entry.name = name.to_string();
+  entry
+}
+
+/// Compares the declared signatures of top-level symbols between a base
+/// revision and the working tree, for `--boundary-mode signature`.
+///
+/// A symbol counts as changed when its normalized signature differs, when it
+/// is missing on either side (added, deleted, or not a plain top-level
+/// declaration such as an anonymous default export), or when it has been
+/// [marked](Self::mark_changed) because its signature names a type that
+/// changed. Member paths (`Class#method`, `ROUTES.key`) compare their owner.
+pub(crate) struct SignatureComparison {
+  cwd: PathBuf,
+  revision: String,
+  head: FxHashMap<PathBuf, FxHashMap<String, String>>,
+  base: FxHashMap<PathBuf, FxHashMap<String, String>>,
+  marked: FxHashSet<(PathBuf, String)>,
+}
+
+impl SignatureComparison {
+  pub(crate) fn new(cwd: &Path, revision: &str) -> Self {
+    Self {
+      cwd: cwd.to_path_buf(),
+      revision: revision.to_string(),
+      head: FxHashMap::default(),
+      base: FxHashMap::default(),
+      marked: FxHashSet::default(),
+    }
+  }
+
+  /// Whether the signature of `symbol` in `file` changed since base
+  pub(crate) fn changed(
+    &mut self,
+    analyzer: &WorkspaceAnalyzer,
+    file: &Path,
+    symbol: &str,
+  ) -> bool {
+    let owner = symbol
+      .split_once(['#', '.'])
+      .map_or(symbol, |(owner, _)| owner);
+    if self
+      .marked
+      .contains(&(file.to_path_buf(), owner.to_string()))
+    {
+      return true;
+    }
+
+    if !self.head.contains_key(file) {
+      let signatures = analyzer
+        .files
+        .get(file)
+        .map(|data| declared_signatures(data.semantic().nodes().program()))
+        .unwrap_or_default();
+      self.head.insert(file.to_path_buf(), signatures);
+    }
+    if !self.base.contains_key(file) {
+      let signatures = git::get_file_at_revision(&self.cwd, &self.revision, file)
+        .ok()
+        .flatten()
+        .and_then(|source| WorkspaceAnalyzer::parse_source(file, source).ok())
+        .map(|data| declared_signatures(data.semantic().nodes().program()))
+        .unwrap_or_default();
+      self.base.insert(file.to_path_buf(), signatures);
+    }
+
+    match (self.base[file].get(owner), self.head[file].get(owner)) {
+      (Some(base), Some(head)) => base != head,
+      _ => true,
+    }
+  }
+
+  /// Treat `symbol` in `file` as changed regardless of its own text
+  pub(crate) fn mark_changed(&mut self, file: &Path, symbol: &str) {
+    let owner = symbol
+      .split_once(['#', '.'])
+      .map_or(symbol, |(owner, _)| owner);
+    self.marked.insert((file.to_path_buf(), owner.to_string()));
+  }
+}
+
+/// Signature of every named top-level declaration in `program`
+fn declared_signatures(program: &Program) -> FxHashMap<String, String> {
+  ModuleShape::from_program(program, Vec::new())
+    .bindings
+    .into_iter()
+    .filter_map(|(name, binding)| match binding {
+      Binding::Declared(entry) => Some((name, entry.signature)),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Builds normalized signatures from declaration source text
+struct SignatureWriter<'s> {
+  source: &'s str,
+  comments: Vec<Span>,
+}
+
+impl<'s> SignatureWriter<'s> {
+  fn new(program: &Program<'s>) -> Self {
+    Self {
+      source: program.source_text,
+      comments: program.comments.iter().map(|c| c.span).collect(),
+    }
+  }
+
+  /// Normalized text of `start..end`, with comments removed
+  fn text(&self, start: u32, end: u32) -> String {
+    let mut raw = String::new();
+    let mut cursor = start;
+    for comment in &self.comments {
+      if comment.end <= cursor || comment.start >= end {
+        continue;
+      }
+      raw.push_str(&self.source[cursor as usize..comment.start.max(cursor) as usize]);
+      raw.push(' ');
+      cursor = comment.end;
+    }
+    if cursor < end {
+      raw.push_str(&self.source[cursor as usize..end as usize]);
+    }
+    normalize_signature(&raw)
+  }
+
+  fn span_text(&self, span: Span) -> String {
+    self.text(span.start, span.end)
+  }
+
+  /// Entries for a top-level declaration (several for `const a = 1, b = 2`)
+  fn declaration(&self, declaration: &Declaration) -> Vec<ApiEntry> {
+    let entry = |name: &str, kind, signature| ApiEntry {
+      name: name.to_string(),
+      kind,
+      signature,
+    };
+    match declaration {
+      Declaration::FunctionDeclaration(func) => func
+        .id
+        .as_ref()
+        .map(|id| entry(&id.name, ApiKind::Function, self.function(func)))
+        .into_iter()
+        .collect(),
+      Declaration::ClassDeclaration(class) => class
+        .id
+        .as_ref()
+        .map(|id| entry(&id.name, ApiKind::Class, self.class(class)))
+        .into_iter()
+        .collect(),
+      Declaration::VariableDeclaration(var_decl) => self.variables(var_decl),
+      Declaration::TSInterfaceDeclaration(decl) => {
+        vec![entry(
+          &decl.id.name,
+          ApiKind::Interface,
+          self.span_text(decl.span),
+        )]
+      }
+      Declaration::TSTypeAliasDeclaration(decl) => {
+        vec![entry(
+          &decl.id.name,
+          ApiKind::Type,
+          self.span_text(decl.span),
+        )]
+      }
+      Declaration::TSEnumDeclaration(decl) => {
+        vec![entry(
+          &decl.id.name,
+          ApiKind::Enum,
+          self.span_text(decl.span),
+        )]
+      }
+      Declaration::TSModuleDeclaration(decl) => match &decl.id {
+        TSModuleDeclarationName::Identifier(id) => {
+          vec![entry(
+            &id.name,
+            ApiKind::Namespace,
+            self.span_text(decl.span),
+          )]
+        }
+        TSModuleDeclarationName::StringLiteral(_) => Vec::new(),
+      },
+      Declaration::TSImportEqualsDeclaration(_) => Vec::new(),
+    }
+  }
+
+  /// The default export: a declaration, an expression, or a local alias
+  fn default_export(&self, declaration: &ExportDefaultDeclarationKind) -> Option<Binding> {
+    let entry = |kind, signature| {
+      Some(Binding::Declared(ApiEntry {
+        name: "default".to_string(),
+        kind,
+        signature,
+      }))
+    };
+    match declaration {
+      ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+        entry(ApiKind::Function, self.function(func))
+      }
+      ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+        entry(ApiKind::Class, self.class(class))
+      }
+      ExportDefaultDeclarationKind::TSInterfaceDeclaration(decl) => {
+        entry(ApiKind::Interface, self.span_text(decl.span))
+      }
+      _ => {
+        let expression = declaration.as_expression()?.get_inner_expression();
+        match expression {
+          Expression::Identifier(id) => Some(Binding::Alias(id.name.to_string())),
+          Expression::FunctionExpression(func) => entry(ApiKind::Function, self.function(func)),
+          Expression::ClassExpression(class) => entry(ApiKind::Class, self.class(class)),
+          _ => entry(
+            ApiKind::Variable,
+            self.initializer(expression, true).unwrap_or_default(),
+          ),
+        }
+      }
+    }
+  }
+
+  /// Function header up to (not including) the body
+  fn function(&self, func: &Function) -> String {
+    let end = func
+      .body
+      .as_ref()
+      .map_or(func.span.end, |body| body.span.start);
+    self.text(func.span.start, end)
+  }
+
+  /// Class header plus its public members without bodies or initializers
+  fn class(&self, class: &Class) -> String {
+    let start = class
+      .decorators
+      .last()
+      .map_or(class.span.start, |d| d.span.end);
+    let header = self.text(start, class.body.span.start);
+
+    let members: Vec<String> = class
+      .body
+      .body
+      .iter()
+      .filter_map(|element| self.class_member(element))
+      .collect();
+
+    if members.is_empty() {
+      format!("{} {{}}", header)
+    } else {
+      format!("{} {{ {} }}", header, members.join("; "))
+    }
+  }
+
+  fn class_member(&self, element: &ClassElement) -> Option<String> {
+    let is_private =
+      |accessibility: Option<TSAccessibility>| accessibility == Some(TSAccessibility::Private);
+    match element {
+      ClassElement::MethodDefinition(method) => {
+        if method.key.is_private_identifier() || is_private(method.accessibility) {
+          return None;
+        }
+        let start = method
+          .decorators
+          .last()
+          .map_or(method.span.start, |d| d.span.end);
+        let end = method
+          .value
+          .body
+          .as_ref()
+          .map_or(method.span.end, |body| body.span.start);
+        Some(self.text(start, end))
+      }
+      ClassElement::PropertyDefinition(property) => {
+        if property.key.is_private_identifier() || is_private(property.accessibility) {
+          return None;
+        }
+        let start = property
+          .decorators
+          .last()
+          .map_or(property.span.start, |d| d.span.end);
+        let end = property
+          .type_annotation
+          .as_ref()
+          .map_or(property.key.span().end, |annotation| annotation.span.end);
+        Some(self.text(start, end))
+      }
+      ClassElement::AccessorProperty(property) => {
+        if property.key.is_private_identifier() || is_private(property.accessibility) {
+          return None;
+        }
+        let start = property
+          .decorators
+          .last()
+          .map_or(property.span.start, |d| d.span.end);
+        let end = property
+          .type_annotation
+          .as_ref()
+          .map_or(property.key.span().end, |annotation| annotation.span.end);
+        Some(self.text(start, end))
+      }
+      ClassElement::TSIndexSignature(signature) => Some(self.span_text(signature.span)),
+      ClassElement::StaticBlock(_) => None,
+    }
+  }
+
+  /// One entry per identifier declarator. Type annotations and function
+  /// initializers make up the signature; a `const` literal's value is part of
+  /// its API, any other initializer is not.
+  fn variables(&self, var_decl: &VariableDeclaration) -> Vec<ApiEntry> {
+    let keyword = var_decl.kind.as_str();
+    let is_const = keyword == "const";
+
+    var_decl
+      .declarations
+      .iter()
+      .filter_map(|declarator| {
+        let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else {
+          return None;
+        };
+        let mut signature = format!("{} {}", keyword, id.name);
+        if let Some(annotation) = &declarator.id.type_annotation {
+          signature.push_str(&self.span_text(annotation.span));
+        } else if let Some(value) = declarator
+          .init
+          .as_ref()
+          .and_then(|init| self.initializer(init.get_inner_expression(), is_const))
+        {
+          signature = format!("{} = {}", signature, value);
+        }
+        Some(ApiEntry {
+          name: id.name.to_string(),
+          kind: ApiKind::Variable,
+          signature,
+        })
+      })
+      .collect()
+  }
+
+  /// The part of an initializer that belongs to the API, if any
+  fn initializer(&self, expression: &Expression, is_const: bool) -> Option<String> {
+    match expression {
+      Expression::ArrowFunctionExpression(arrow) => {
+        Some(self.text(arrow.span.start, arrow.body.span.start))
+      }
+      Expression::FunctionExpression(func) => Some(self.function(func)),
+      Expression::ClassExpression(class) => Some(self.class(class)),
+      Expression::ObjectExpression(object) => {
+        let keys: Vec<String> = object
+          .properties
+          .iter()
+          .map(|property| match property {
+            oxc_ast::ast::ObjectPropertyKind::ObjectProperty(p) => self.span_text(p.key.span()),
+            oxc_ast::ast::ObjectPropertyKind::SpreadProperty(s) => self.span_text(s.span),
+          })
+          .collect();
+        Some(format!("{{ {} }}", keys.join(", ")))
+      }
+      _ if is_const && (expression.is_literal() || is_static_template(expression)) => {
+        Some(self.span_text(expression.span()))
+      }
+      _ => None,
+    }
+  }
+}
+
+fn is_static_template(expression: &Expression) -> bool {
+  matches!(expression, Expression::TemplateLiteral(t) if t.expressions.is_empty())
+}
+
+/// Characters that keep a separating space between them: identifier
+/// characters and string quotes (`declare module 'x'`)
+fn is_word_char(c: char) -> bool {
+  c.is_alphanumeric() || c == '_' || c == '$' || c == '\'' || c == '"' || c == '`'
+}
+
+/// Canonicalize declaration text so that only meaningful edits change it.
+///
+/// Whitespace runs are dropped, except a single space between two words;
+/// `,` `;` `:` are followed by one space, `=` `=>` `|` `&` and the inside of
+/// braces get one space on each side, and trailing `,`/`;` before a closing
+/// bracket are removed. String and template literals are copied verbatim.
+fn normalize_signature(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  let mut pending_space = false;
+  let mut chars = text.chars().peekable();
+
+  fn trim_end(out: &mut String) {
+    while out.ends_with(' ') {
+      out.pop();
+    }
+  }
+  fn space(out: &mut String) {
+    if !out.is_empty() && !out.ends_with(' ') {
+      out.push(' ');
+    }
+  }
+  fn spaced(out: &mut String, op: &str) {
+    trim_end(out);
+    space(out);
+    out.push_str(op);
+    out.push(' ');
+  }
+
+  while let Some(c) = chars.next() {
+    if c.is_whitespace() {
+      pending_space = true;
+      continue;
+    }
+    if pending_space
+      && out.ends_with(|p: char| is_word_char(p) || matches!(p, ')' | ']' | '}'))
+      && is_word_char(c)
+    {
+      out.push(' ');
+    }
+    pending_space = false;
+
+    match c {
+      '\'' | '"' | '`' => {
+        out.push(c);
+        let mut escaped = false;
+        for next in chars.by_ref() {
+          out.push(next);
+          if escaped {
+            escaped = false;
+          } else if next == '\\' {
+            escaped = true;
+          } else if next == c {
+            break;
+          }
+        }
+      }
+      ',' | ';' | ':' => {
+        trim_end(&mut out);
+        out.push(c);
+        out.push(' ');
+      }
+      '=' if chars.peek() == Some(&'>') => {
+        chars.next();
+        spaced(&mut out, "=>");
+      }
+      '=' | '|' | '&' => {
+        let mut op = c.to_string();
+        while let Some(&next) = chars.peek() {
+          if next == c || (c != '=' && next == '=') {
+            op.push(next);
+            chars.next();
+          } else {
+            break;
+          }
+        }
+        if c == '=' && out.ends_with('!') {
+          out.push_str(&op);
+        } else {
+          spaced(&mut out, &op);
+        }
+      }
+      '{' => {
+        if out.ends_with(|p: char| is_word_char(p) || matches!(p, ')' | ']' | '>')) {
+          out.push(' ');
+        }
+        out.push_str("{ ");
+      }
+      '}' | ')' | ']' | '>' => {
+        trim_end(&mut out);
+        while out.ends_with([',', ';']) {
+          out.pop();
+          trim_end(&mut out);
+        }
+        if c == '}' && !out.ends_with('{') {
+          out.push(' ');
+        }
+        out.push(c);
+      }
+      _ => out.push(c),
+    }
+  }
+
+  trim_end(&mut out);
+  while out.ends_with([',', ';']) {
+    out.pop();
+    trim_end(&mut out);
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn shape_of(source: &str) -> (Vec<(String, ApiKind, String)>, Vec<String>) {
+    let data = WorkspaceAnalyzer::parse_source(Path::new("index.ts"), source.to_string()).unwrap();
+    let program = data.semantic().nodes().program();
+    let shape = ModuleShape::from_program(program, WorkspaceAnalyzer::extract_exports(program));
+    let declared: Vec<(String, ApiKind, String)> = shape
+      .bindings
+      .into_iter()
+      .filter_map(|(name, binding)| match binding {
+        Binding::Declared(entry) => Some((name, entry.kind, entry.signature)),
+        _ => None,
+      })
+      .collect();
+    let exported = shape.exports.into_iter().map(|e| e.exported_name).collect();
+    (declared, exported)
+  }
+
+  #[test]
+  fn test_normalize_signature_ignores_formatting() {
+    assert_eq!(
+      normalize_signature("function  format(\n  value:number,\n  opts ?: Options,\n) : string"),
+      "function format(value: number, opts?: Options): string"
+    );
+    assert_eq!(
+      normalize_signature("interface Props {a:string;b?:Array<number>;}"),
+      "interface Props { a: string; b?: Array<number> }"
+    );
+    assert_eq!(
+      normalize_signature("type Mode='a'|'b  c'"),
+      "type Mode = 'a' | 'b  c'"
+    );
+    assert_eq!(
+      normalize_signature("const f=(a:number)=>"),
+      "const f = (a: number) =>"
+    );
+    assert_eq!(normalize_signature("type Empty = {  }"), "type Empty = {}");
+  }
+
+  #[test]
+  fn test_signatures_strip_bodies_comments_and_private_members() {
+    let (declared, exported) = shape_of(
+      r#"
+/** Formats a value */
+export function format(value: number /* raw */): string {
+  return String(value);
+}
+
+export class Store<T> {
+  private cache = new Map();
+  #secret = 1;
+  readonly name: string = 'store';
+  constructor(public id: string) {}
+  get(key: string): T | undefined { return undefined; }
+  static create(): Store<unknown> { return new Store('x'); }
+}
+
+export interface Options { verbose?: boolean }
+export type Mode = 'fast' | 'safe';
+export const VERSION = '1.0';
+export const handler = async (req: Request): Promise<void> => { await req; };
+export let counter = computeStart();
+const internal = { a: 1, b: 2 };
+export { internal as config };
+"#,
+    );
+
+    let signature = |name: &str| {
+      declared
+        .iter()
+        .find(|(n, _, _)| n == name)
+        .map(|(_, kind, sig)| (*kind, sig.as_str()))
+        .unwrap()
+    };
+
+    assert_eq!(
+      signature("format"),
+      (ApiKind::Function, "function format(value: number): string")
+    );
+    assert_eq!(
+      signature("Store"),
+      (
+        ApiKind::Class,
+        "class Store<T> { readonly name: string; constructor(public id: string); get(key: string): T | undefined; static create(): Store<unknown> }"
+      )
+    );
+    assert_eq!(
+      signature("Options"),
+      (
+        ApiKind::Interface,
+        "interface Options { verbose?: boolean }"
+      )
+    );
+    assert_eq!(signature("Mode").0, ApiKind::Type);
+    assert_eq!(
+      signature("VERSION"),
+      (ApiKind::Variable, "const VERSION = '1.0'")
+    );
+    assert_eq!(
+      signature("handler"),
+      (
+        ApiKind::Variable,
+        "const handler = async(req: Request): Promise<void> =>"
+      )
+    );
+    assert_eq!(signature("counter"), (ApiKind::Variable, "let counter"));
+    assert_eq!(
+      signature("internal"),
+      (ApiKind::Variable, "const internal = { a, b }")
+    );
+
+    for name in ["format", "Store", "Options", "Mode", "VERSION", "config"] {
+      assert!(
+        exported.iter().any(|e| e == name),
+        "{} should be exported",
+        name
+      );
+    }
+    assert!(!exported.iter().any(|e| e == "internal"));
+  }
+
+  #[test]
+  fn test_compare_api_classifies_changes() {
+    let entry = |name: &str, signature: &str| ApiEntry {
+      name: name.to_string(),
+      kind: ApiKind::Function,
+      signature: signature.to_string(),
+    };
+    let base = vec![
+      entry("a", "function a()"),
+      entry("b", "function b()"),
+      entry("c", "function c()"),
+    ];
+    let head = vec![
+      entry("a", "function a()"),
+      entry("c", "function c(x: number)"),
+      entry("d", "function d()"),
+    ];
+
+    let diff = compare_api("lib", PathBuf::from("libs/lib/src/index.ts"), base, head);
+
+    assert_eq!(
+      diff
+        .added
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>(),
+      ["d"]
+    );
+    assert_eq!(
+      diff
+        .removed
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>(),
+      ["b"]
+    );
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].base, "function c()");
+    assert_eq!(diff.changed[0].head, "function c(x: number)");
+  }
+
+  #[test]
+  fn test_root_export_target() {
+    let exports = serde_json::json!({
+      ".": { "types": "./src/index.ts", "default": "./dist/index.js" },
+      "./package.json": "./package.json"
+    });
+    assert_eq!(root_export_target(&exports), Some("./src/index.ts"));
+    assert_eq!(
+      root_export_target(&serde_json::json!("./index.ts")),
+      Some("./index.ts")
+    );
+    assert_eq!(
+      clean_path(Path::new("libs/a/./src/index.ts")),
+      PathBuf::from("libs/a/src/index.ts")
+    );
+  }
+}
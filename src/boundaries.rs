@@ -0,0 +1,323 @@
+//! Module boundary enforcement from project tags (`domino boundaries`).
+//!
+//! The native counterpart of Nx's `@nx/enforce-module-boundaries` lint rule:
+//! every import, re-export and `import()` between projects is checked against
+//! the `depConstraints` of the workspace. Constraints come from the root
+//! package.json's `domino.depConstraints`, or else from the rule's options in
+//! `.eslintrc.json`; project tags from [`Project::tags`].
+//!
+//! Given the changed files, only their imports are parsed and resolved — no
+//! workspace-wide analysis — which keeps the check fast enough for a
+//! pre-commit hook.
+
+use crate::error::{DominoError, Result};
+use crate::profiler::Profiler;
+use crate::semantic::analyzer::FileSemanticData;
+use crate::semantic::{self, create_resolve_options, WorkspaceAnalyzer};
+use crate::types::{BoundaryViolation, DepConstraint, Project};
+use crate::utils::{is_source_file, offset_to_line_col, tag_matches, ProjectIndex};
+use oxc_ast::ast::Expression;
+use oxc_ast::AstKind;
+use oxc_resolver::Resolver;
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// ESLint rule names that carry Nx `depConstraints`
+const ESLINT_RULES: &[&str] = &[
+  "@nx/enforce-module-boundaries",
+  "@nrwl/nx/enforce-module-boundaries",
+];
+
+/// Check the imports of `changed` files — or of every workspace file when
+/// `None` — against the workspace's dependency constraints, sorted by file and
+/// line
+pub fn find_boundary_violations(
+  cwd: &Path,
+  projects: Vec<Project>,
+  changed: Option<&[PathBuf]>,
+  profiler: Arc<Profiler>,
+) -> Result<Vec<BoundaryViolation>> {
+  let constraints = load_dep_constraints(cwd)?;
+  if constraints.is_empty() {
+    debug!("No depConstraints configured; nothing to check");
+    return Ok(Vec::new());
+  }
+
+  let checker = BoundaryChecker {
+    cwd,
+    resolver: Resolver::new(create_resolve_options(cwd, &projects)),
+    tsconfig_path_prefixes: semantic::parse_tsconfig_path_prefixes(cwd),
+    index: ProjectIndex::new(&projects, cwd),
+    tags: projects
+      .iter()
+      .map(|project| (project.name.clone(), project.tags.clone()))
+      .collect(),
+    constraints,
+    projects: &projects,
+  };
+
+  let mut violations = Vec::new();
+  match changed {
+    Some(files) => {
+      for file in files {
+        if !is_source_file(file) || checker.index.get_owning_packages_by_path(file).is_empty() {
+          continue;
+        }
+        // Deleted files have no imports left to check
+        let Ok(source) = fs::read_to_string(cwd.join(file)) else {
+          continue;
+        };
+        match WorkspaceAnalyzer::parse_source(file, source) {
+          Ok(data) => violations.extend(checker.check_file(file, &data)),
+          Err(e) => debug!("Skipping {:?}: {}", file, e),
+        }
+      }
+    }
+    None => {
+      let analyzer = WorkspaceAnalyzer::new(projects.clone(), cwd, profiler)?;
+      for (file, data) in &analyzer.files {
+        violations.extend(checker.check_file(file, data));
+      }
+    }
+  }
+
+  violations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+  Ok(violations)
+}
+
+struct BoundaryChecker<'a> {
+  cwd: &'a Path,
+  resolver: Resolver,
+  tsconfig_path_prefixes: Vec<String>,
+  index: ProjectIndex,
+  tags: FxHashMap<String, Vec<String>>,
+  constraints: Vec<DepConstraint>,
+  projects: &'a [Project],
+}
+
+impl BoundaryChecker<'_> {
+  fn check_file(&self, file: &Path, data: &FileSemanticData) -> Vec<BoundaryViolation> {
+    let sources = self.index.get_owning_packages_by_path(file);
+    let mut violations = Vec::new();
+
+    for (specifier, offset) in module_requests(data) {
+      let Some(resolved) = semantic::resolve_workspace_specifier(
+        &self.resolver,
+        self.cwd,
+        self.projects,
+        &self.tsconfig_path_prefixes,
+        file,
+        &specifier,
+      ) else {
+        continue;
+      };
+
+      for target in self.index.get_owning_packages_by_path(&resolved) {
+        for source in &sources {
+          if *source == target {
+            continue;
+          }
+          for message in
+            broken_constraints(&self.constraints, &self.tags[source], &self.tags[&target])
+          {
+            violations.push(BoundaryViolation {
+              file: file.to_path_buf(),
+              line: offset_to_line_col(data.source(), offset).0,
+              specifier: specifier.clone(),
+              project: source.clone(),
+              dependency: target.clone(),
+              message,
+            });
+          }
+        }
+      }
+    }
+
+    violations
+  }
+}
+
+/// Every module specifier a file loads — imports, re-exports and `import()`
+/// with a string literal — with the offset of the loading statement
+fn module_requests(data: &FileSemanticData) -> Vec<(String, usize)> {
+  data
+    .semantic()
+    .nodes()
+    .iter()
+    .filter_map(|node| match node.kind() {
+      AstKind::ImportDeclaration(import) => {
+        Some((import.source.value.to_string(), import.span.start))
+      }
+      AstKind::ExportNamedDeclaration(export) => export
+        .source
+        .as_ref()
+        .map(|source| (source.value.to_string(), export.span.start)),
+      AstKind::ExportAllDeclaration(export) => {
+        Some((export.source.value.to_string(), export.span.start))
+      }
+      AstKind::ImportExpression(import) => match &import.source {
+        Expression::StringLiteral(literal) => Some((literal.value.to_string(), import.span.start)),
+        _ => None,
+      },
+      _ => None,
+    })
+    .map(|(specifier, offset)| (specifier, offset as usize))
+    .collect()
+}
+
+/// Messages for every constraint a dependency between projects with these
+/// tags breaks
+fn broken_constraints(
+  constraints: &[DepConstraint],
+  source_tags: &[String],
+  target_tags: &[String],
+) -> Vec<String> {
+  let has_tag = |tags: &[String], pattern: &str| tags.iter().any(|tag| tag_matches(pattern, tag));
+  let quoted = |tags: &[String]| {
+    tags
+      .iter()
+      .map(|tag| format!("\"{}\"", tag))
+      .collect::<Vec<_>>()
+      .join(", ")
+  };
+
+  let mut messages = Vec::new();
+  for constraint in constraints {
+    // `*` constrains every project, tagged or not
+    if constraint.source_tag != "*" && !has_tag(source_tags, &constraint.source_tag) {
+      continue;
+    }
+    let allowed = &constraint.only_depend_on_libs_with_tags;
+    if !allowed.is_empty() && !allowed.iter().any(|pattern| has_tag(target_tags, pattern)) {
+      messages.push(format!(
+        "A project tagged with \"{}\" can only depend on projects tagged with {}",
+        constraint.source_tag,
+        quoted(allowed)
+      ));
+    }
+    for pattern in &constraint.not_depend_on_libs_with_tags {
+      if has_tag(target_tags, pattern) {
+        messages.push(format!(
+          "A project tagged with \"{}\" cannot depend on projects tagged with \"{}\"",
+          constraint.source_tag, pattern
+        ));
+      }
+    }
+  }
+  messages
+}
+
+/// `depConstraints` from the root package.json's `domino` field, falling back
+/// to the options of Nx's module boundary rule in `.eslintrc.json`
+fn load_dep_constraints(cwd: &Path) -> Result<Vec<DepConstraint>> {
+  let read_json = |name: &str| -> Option<serde_json::Value> {
+    let content = fs::read_to_string(cwd.join(name)).ok()?;
+    match serde_json::from_str(&content) {
+      Ok(json) => Some(json),
+      Err(e) => {
+        debug!("Ignoring unparsable {}: {}", name, e);
+        None
+      }
+    }
+  };
+  let parse = |value: &serde_json::Value, origin: &str| {
+    serde_json::from_value::<Vec<DepConstraint>>(value.clone())
+      .map_err(|e| DominoError::Parse(format!("Invalid depConstraints in {}: {}", origin, e)))
+  };
+
+  if let Some(constraints) = read_json("package.json")
+    .as_ref()
+    .and_then(|json| json.get("domino")?.get("depConstraints"))
+  {
+    return parse(constraints, "package.json");
+  }
+
+  let Some(eslintrc) = read_json(".eslintrc.json") else {
+    return Ok(Vec::new());
+  };
+  let rule_sets = std::iter::once(eslintrc.get("rules")).chain(
+    eslintrc
+      .get("overrides")
+      .and_then(|overrides| overrides.as_array())
+      .into_iter()
+      .flatten()
+      .map(|override_| override_.get("rules")),
+  );
+  for rules in rule_sets.flatten() {
+    for rule in ESLINT_RULES {
+      // `"rule": ["error", { "depConstraints": [...] }]`
+      if let Some(constraints) = rules
+        .get(*rule)
+        .and_then(|entry| entry.get(1))
+        .and_then(|options| options.get("depConstraints"))
+      {
+        return parse(constraints, ".eslintrc.json");
+      }
+    }
+  }
+  Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn constraint(source_tag: &str, only: &[&str], not: &[&str]) -> DepConstraint {
+    DepConstraint {
+      source_tag: source_tag.to_string(),
+      only_depend_on_libs_with_tags: only.iter().map(|t| t.to_string()).collect(),
+      not_depend_on_libs_with_tags: not.iter().map(|t| t.to_string()).collect(),
+    }
+  }
+
+  fn tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|t| t.to_string()).collect()
+  }
+
+  #[test]
+  fn test_broken_constraints() {
+    let constraints = [
+      constraint("scope:billing", &["scope:billing", "scope:shared"], &[]),
+      constraint("type:lib", &[], &["type:app"]),
+    ];
+    let billing = tags(&["scope:billing", "type:lib"]);
+
+    assert!(broken_constraints(&constraints, &billing, &tags(&["scope:shared"])).is_empty());
+    assert_eq!(
+      broken_constraints(&constraints, &billing, &tags(&["scope:web", "type:app"])),
+      [
+        "A project tagged with \"scope:billing\" can only depend on projects tagged with \"scope:billing\", \"scope:shared\"",
+        "A project tagged with \"type:lib\" cannot depend on projects tagged with \"type:app\"",
+      ]
+    );
+    // Untagged projects are only constrained by `*`
+    assert!(broken_constraints(&constraints, &[], &tags(&["type:app"])).is_empty());
+    let everyone = [constraint("*", &[], &["type:app"])];
+    assert_eq!(
+      broken_constraints(&everyone, &[], &tags(&["type:app"])).len(),
+      1
+    );
+  }
+
+  #[test]
+  fn test_module_requests() {
+    let source = "import { a } from './a';\nexport * from './b';\nexport { c } from './c';\nconst d = () => import('./d');\n";
+    let data = WorkspaceAnalyzer::parse_source(Path::new("x.ts"), source.to_string()).unwrap();
+    let lines: Vec<(String, usize)> = module_requests(&data)
+      .into_iter()
+      .map(|(specifier, offset)| (specifier, offset_to_line_col(source, offset).0))
+      .collect();
+    assert_eq!(
+      lines,
+      [
+        ("./a".to_string(), 1),
+        ("./b".to_string(), 2),
+        ("./c".to_string(), 3),
+        ("./d".to_string(), 4),
+      ]
+    );
+  }
+}
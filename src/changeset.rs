@@ -0,0 +1,276 @@
+//! Version bump suggestions for publishable packages (`domino changeset`).
+//!
+//! Every affected workspace package that is not `private` gets a suggested
+//! semver bump from what changed in its public API (see [`crate::api_diff`]):
+//! a removed export or changed signature is a major bump, an added export a
+//! minor one, and anything else — internal changes, changes reaching the
+//! package through its imports, dependency updates from the lockfile — a
+//! patch. The result can be written out as a `.changeset/*.md` file for the
+//! changesets release tooling.
+
+use crate::api_diff;
+use crate::core;
+use crate::error::Result;
+use crate::profiler::Profiler;
+use crate::semantic::WorkspaceAnalyzer;
+use crate::types::{AffectCause, BumpType, PackageBump, ProjectApiDiff, TrueAffectedConfig};
+use crate::workspace::workspaces::publishable_package_name;
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Suggest a version bump for every publishable package affected by the diff
+/// described by `config`, sorted by package name.
+pub fn find_package_bumps(
+  config: TrueAffectedConfig,
+  profiler: Arc<Profiler>,
+) -> Result<Vec<PackageBump>> {
+  let cwd = config.cwd.clone();
+  let base = config.base.clone();
+  let head = config.head.clone();
+  let analyzer = WorkspaceAnalyzer::new(config.projects.clone(), &cwd, profiler.clone())?;
+  let result = core::find_affected_with_report_using(config, &analyzer, profiler)?;
+  let causes: FxHashMap<String, Vec<AffectCause>> = result
+    .report
+    .map(|report| {
+      report
+        .projects
+        .into_iter()
+        .map(|project| (project.name, project.causes))
+        .collect()
+    })
+    .unwrap_or_default();
+
+  let publishable: Vec<(String, String)> = analyzer
+    .projects
+    .iter()
+    .filter(|project| result.affected_projects.contains(&project.name))
+    .filter_map(|project| {
+      publishable_package_name(&cwd.join(&project.root)).map(|name| (project.name.clone(), name))
+    })
+    .collect();
+  debug!(
+    "{} of {} affected projects are publishable",
+    publishable.len(),
+    result.affected_projects.len()
+  );
+  if publishable.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let api_diffs: FxHashMap<String, ProjectApiDiff> =
+    api_diff::find_api_changes_using(&analyzer, &cwd, &base, head.as_deref())?
+      .into_iter()
+      .map(|diff| (diff.project.clone(), diff))
+      .collect();
+
+  let mut bumps: Vec<PackageBump> = publishable
+    .into_iter()
+    .map(|(project, package)| {
+      let project_causes = causes.get(&project).map(Vec::as_slice).unwrap_or_default();
+      classify(
+        package,
+        project.clone(),
+        api_diffs.get(&project),
+        project_causes,
+      )
+    })
+    .collect();
+  bumps.sort_by(|a, b| a.package.cmp(&b.package));
+  Ok(bumps)
+}
+
+/// Pick the bump for one package from its API diff and why it was affected
+fn classify(
+  package: String,
+  project: String,
+  api: Option<&ProjectApiDiff>,
+  causes: &[AffectCause],
+) -> PackageBump {
+  let mut bump = BumpType::Patch;
+  let mut reasons = Vec::new();
+
+  if let Some(api) = api {
+    for entry in &api.removed {
+      reasons.push(format!("removed export `{}`", entry.name));
+    }
+    for change in &api.changed {
+      reasons.push(format!("changed signature of `{}`", change.name));
+    }
+    if !reasons.is_empty() {
+      bump = BumpType::Major;
+    }
+    for entry in &api.added {
+      reasons.push(format!("added export `{}`", entry.name));
+      bump = bump.max(BumpType::Minor);
+    }
+  }
+
+  let mut dependencies: Vec<&str> = Vec::new();
+  let mut upstream: Vec<String> = Vec::new();
+  let mut internal = false;
+  for cause in causes {
+    match cause {
+      AffectCause::LockfileChange { dependency, .. } => dependencies.push(dependency),
+      AffectCause::ImportedSymbol {
+        source_project,
+        symbol,
+        ..
+      } if *source_project != project => {
+        upstream.push(format!("uses changed `{}` from {}", symbol, source_project));
+      }
+      AffectCause::ImplicitDependency { depends_on } => {
+        upstream.push(format!("implicit dependency {} changed", depends_on));
+      }
+      _ => internal = true,
+    }
+  }
+  dependencies.sort_unstable();
+  dependencies.dedup();
+  upstream.sort();
+  upstream.dedup();
+
+  if reasons.is_empty() && internal {
+    reasons.push("internal changes".to_string());
+  }
+  reasons.extend(upstream);
+  reasons.extend(
+    dependencies
+      .into_iter()
+      .map(|dependency| format!("updated dependency `{}`", dependency)),
+  );
+
+  PackageBump {
+    package,
+    project,
+    bump,
+    reasons,
+  }
+}
+
+/// Write `bumps` as a single changeset at `.changeset/domino-<revision>.md`.
+///
+/// The file is named after the base revision, so re-running on the same
+/// branch replaces the previous suggestion instead of piling up new files.
+pub fn write_changeset(cwd: &Path, base_revision: &str, bumps: &[PackageBump]) -> Result<PathBuf> {
+  let dir = cwd.join(".changeset");
+  fs::create_dir_all(&dir)?;
+
+  let short = &base_revision[..base_revision.len().min(8)];
+  let path = dir.join(format!("domino-{}.md", short));
+  fs::write(&path, render_changeset(bumps))?;
+  Ok(path)
+}
+
+/// Changeset markdown: a frontmatter of package bumps and one summary line
+/// per package
+fn render_changeset(bumps: &[PackageBump]) -> String {
+  let mut out = String::from("---\n");
+  for bump in bumps {
+    out.push_str(&format!("\"{}\": {}\n", bump.package, bump.bump));
+  }
+  out.push_str("---\n\n");
+  for bump in bumps {
+    out.push_str(&format!(
+      "- {}: {}\n",
+      bump.package,
+      bump.reasons.join("; ")
+    ));
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::{ApiChange, ApiEntry, ApiKind};
+
+  fn entry(name: &str) -> ApiEntry {
+    ApiEntry {
+      name: name.to_string(),
+      kind: ApiKind::Function,
+      signature: format!("function {}()", name),
+    }
+  }
+
+  fn api(added: &[&str], removed: &[&str], changed: &[&str]) -> ProjectApiDiff {
+    ProjectApiDiff {
+      project: "ui".to_string(),
+      entry_point: PathBuf::from("libs/ui/src/index.ts"),
+      added: added.iter().map(|n| entry(n)).collect(),
+      removed: removed.iter().map(|n| entry(n)).collect(),
+      changed: changed
+        .iter()
+        .map(|n| ApiChange {
+          name: n.to_string(),
+          kind: ApiKind::Function,
+          base: format!("function {}()", n),
+          head: format!("function {}(x: number)", n),
+        })
+        .collect(),
+    }
+  }
+
+  fn direct_change() -> AffectCause {
+    AffectCause::DirectChange {
+      file: PathBuf::from("libs/ui/src/button.ts"),
+      symbol: Some("Button".to_string()),
+      line: 3,
+    }
+  }
+
+  #[test]
+  fn test_classify_by_api_change() {
+    let bump = |api: Option<&ProjectApiDiff>| {
+      classify("@org/ui".into(), "ui".into(), api, &[direct_change()])
+    };
+
+    let major = bump(Some(&api(&["Size"], &[], &["format"])));
+    assert_eq!(major.bump, BumpType::Major);
+    assert_eq!(
+      major.reasons,
+      ["changed signature of `format`", "added export `Size`"]
+    );
+
+    assert_eq!(bump(Some(&api(&["Size"], &[], &[]))).bump, BumpType::Minor);
+
+    let patch = bump(None);
+    assert_eq!(patch.bump, BumpType::Patch);
+    assert_eq!(patch.reasons, ["internal changes"]);
+  }
+
+  #[test]
+  fn test_classify_dependency_bump_is_patch() {
+    let lockfile = |dependency: &str| AffectCause::LockfileChange {
+      dependency: dependency.to_string(),
+      importing_file: PathBuf::from("libs/ui/src/date.ts"),
+    };
+    let bump = classify(
+      "@org/ui".into(),
+      "ui".into(),
+      None,
+      &[lockfile("date-fns"), lockfile("date-fns")],
+    );
+    assert_eq!(bump.bump, BumpType::Patch);
+    assert_eq!(bump.reasons, ["updated dependency `date-fns`"]);
+  }
+
+  #[test]
+  fn test_render_changeset() {
+    let bumps = vec![
+      classify("@org/core".into(), "core".into(), None, &[direct_change()]),
+      classify(
+        "@org/ui".into(),
+        "ui".into(),
+        Some(&api(&[], &["legacy"], &[])),
+        &[],
+      ),
+    ];
+    assert_eq!(
+      render_changeset(&bumps),
+      "---\n\"@org/core\": patch\n\"@org/ui\": major\n---\n\n- @org/core: internal changes\n- @org/ui: removed export `legacy`\n"
+    );
+  }
+}
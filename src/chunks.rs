@@ -0,0 +1,276 @@
+//! Bundle entry points and lazy chunks affected by a change
+//! (`domino affected-chunks`).
+//!
+//! Entry points are each project's Nx `build` target `main` / `browser`
+//! option, its package.json entry (see [`api_diff::find_entry_point`]), a
+//! `main.*` in its source root, and any file matching the configured entry
+//! globs. Every dynamic `import()` and `new URL(…, import.meta.url)` target
+//! starts a chunk of its own. A chunk holds the files reachable from its root
+//! through runtime static imports and re-exports, the way a bundler splits
+//! them.
+//!
+//! A chunk is affected when one of its files is reached by the change. The
+//! file that loads a lazy chunk is reached whenever the chunk's exports
+//! change, but its own chunk only changes if something else reached it too,
+//! so a file reached only through its `import()` does not count.
+
+use crate::api_diff;
+use crate::core;
+use crate::error::{DominoError, Result};
+use crate::named_inputs::NxInputs;
+use crate::profiler::Profiler;
+use crate::semantic::WorkspaceAnalyzer;
+use crate::types::{AffectedChunk, AffectedChunks, ChunkKind, Project, TrueAffectedConfig};
+use crate::utils::{domino_setting, ProjectIndex, MATCH_OPTIONS};
+use glob::Pattern;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Entry file names probed in a project's source root
+const MAIN_FILES: &[&str] = &["main.ts", "main.tsx", "main.js", "main.jsx"];
+
+/// Runtime import edges between workspace files
+#[derive(Default)]
+struct ModuleGraph {
+  /// importing file -> files it imports or re-exports statically
+  static_imports: FxHashMap<PathBuf, FxHashSet<PathBuf>>,
+  /// importing file -> files it loads lazily, with the chunk kind
+  lazy_imports: FxHashMap<PathBuf, Vec<(PathBuf, ChunkKind)>>,
+}
+
+/// Find the entry point and lazy chunks containing files reached by the
+/// change. `entry_patterns` are globs relative to the workspace root naming
+/// additional entry points.
+pub fn find_affected_chunks(
+  config: TrueAffectedConfig,
+  entry_patterns: &[String],
+  profiler: Arc<Profiler>,
+) -> Result<AffectedChunks> {
+  let cwd = config.cwd.clone();
+  let projects = config.projects.clone();
+  let patterns = domino_setting::<Vec<String>>(&cwd, "entryPoints")
+    .unwrap_or_default()
+    .into_iter()
+    .chain(entry_patterns.iter().cloned())
+    .map(|pattern| {
+      Pattern::new(&pattern)
+        .map_err(|e| DominoError::Other(format!("Invalid entry pattern '{}': {}", pattern, e)))
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let analyzer = WorkspaceAnalyzer::new(projects.clone(), &cwd, profiler.clone())?;
+  let graph = module_graph(&analyzer);
+  let nx_inputs = NxInputs::load(&cwd, &projects);
+  let mut roots = entry_points(&analyzer, &cwd, &projects, nx_inputs.as_ref(), &patterns);
+  let mut lazy_roots: Vec<(PathBuf, ChunkKind)> = graph
+    .lazy_imports
+    .values()
+    .flatten()
+    .filter(|(root, _)| !roots.iter().any(|(entry, _)| entry == root))
+    .cloned()
+    .collect();
+  lazy_roots.sort();
+  lazy_roots.dedup_by(|a, b| a.0 == b.0);
+  roots.extend(lazy_roots);
+  debug!("Found {} chunk roots", roots.len());
+
+  let affected = core::find_affected_files_using(config, &analyzer, profiler)?;
+  let changed: FxHashSet<PathBuf> = affected.changed.into_iter().collect();
+  let global = !affected.global_triggers.is_empty();
+  let reached: FxHashSet<PathBuf> = affected.files.into_iter().collect();
+  let counts = |file: &PathBuf| {
+    if !reached.contains(file) {
+      return false;
+    }
+    if changed.contains(file) {
+      return true;
+    }
+    let loads_reached_chunk = graph
+      .lazy_imports
+      .get(file)
+      .is_some_and(|targets| targets.iter().any(|(target, _)| reached.contains(target)));
+    let imports_reached = graph
+      .static_imports
+      .get(file)
+      .is_some_and(|targets| targets.iter().any(|target| reached.contains(target)));
+    !loads_reached_chunk || imports_reached
+  };
+
+  let index = ProjectIndex::new(&projects, &cwd);
+  let mut chunks = Vec::new();
+  for (root, kind) in roots {
+    let mut files: Vec<PathBuf> = if global {
+      vec![]
+    } else {
+      chunk_files(&graph, &root)
+        .into_iter()
+        .filter(|file| counts(file))
+        .collect()
+    };
+    if !global && files.is_empty() {
+      continue;
+    }
+    files.sort();
+    chunks.push(AffectedChunk {
+      project: index
+        .get_owning_packages_by_path(&root)
+        .into_iter()
+        .next()
+        .unwrap_or_default(),
+      kind,
+      root,
+      files,
+    });
+  }
+  chunks.sort_by(|a, b| (&a.project, a.kind, &a.root).cmp(&(&b.project, b.kind, &b.root)));
+
+  Ok(AffectedChunks {
+    initial: chunks.iter().any(|chunk| chunk.kind == ChunkKind::Entry),
+    chunks,
+    global,
+  })
+}
+
+/// Runtime edges from the import and re-export indexes. Type-only imports are
+/// erased by bundlers and not followed.
+fn module_graph(analyzer: &WorkspaceAnalyzer) -> ModuleGraph {
+  let mut graph = ModuleGraph::default();
+  for ((source_file, _), values) in &analyzer.import_index {
+    for value in values {
+      if value.is_type_only {
+        continue;
+      }
+      let importing_file = value.importing_file.clone();
+      if value.is_dynamic || value.is_module_url {
+        let kind = if value.is_module_url {
+          ChunkKind::Worker
+        } else {
+          ChunkKind::Lazy
+        };
+        let targets = graph.lazy_imports.entry(importing_file).or_default();
+        if !targets.iter().any(|(target, _)| target == source_file) {
+          targets.push((source_file.clone(), kind));
+        }
+      } else {
+        graph
+          .static_imports
+          .entry(importing_file)
+          .or_default()
+          .insert(source_file.clone());
+      }
+    }
+  }
+  for (source_file, entries) in &analyzer.reexport_index {
+    for (reexporting_file, _) in entries {
+      graph
+        .static_imports
+        .entry(reexporting_file.clone())
+        .or_default()
+        .insert(source_file.clone());
+    }
+  }
+  graph
+}
+
+/// Files reachable from `root` through static edges, `root` included
+fn chunk_files(graph: &ModuleGraph, root: &Path) -> FxHashSet<PathBuf> {
+  let mut files = FxHashSet::default();
+  let mut queue = VecDeque::from([root.to_path_buf()]);
+  while let Some(file) = queue.pop_front() {
+    if !files.insert(file.clone()) {
+      continue;
+    }
+    if let Some(targets) = graph.static_imports.get(&file) {
+      queue.extend(targets.iter().cloned());
+    }
+  }
+  files
+}
+
+/// Every project's entry points plus the workspace files matching `patterns`
+fn entry_points(
+  analyzer: &WorkspaceAnalyzer,
+  cwd: &Path,
+  projects: &[Project],
+  nx_inputs: Option<&NxInputs>,
+  patterns: &[Pattern],
+) -> Vec<(PathBuf, ChunkKind)> {
+  let is_source = |path: &Path| analyzer.files.contains_key(path);
+  let mut entries: Vec<PathBuf> = Vec::new();
+  for project in projects {
+    let candidates = nx_inputs
+      .map_or(&[][..], |nx_inputs| nx_inputs.build_entries(&project.name))
+      .iter()
+      .cloned()
+      .chain(api_diff::find_entry_point(analyzer, cwd, project))
+      .chain(MAIN_FILES.iter().map(|name| project.source_root.join(name)));
+    for candidate in candidates {
+      if is_source(&candidate) && !entries.contains(&candidate) {
+        entries.push(candidate);
+      }
+    }
+  }
+
+  let mut matched: Vec<&PathBuf> = analyzer
+    .files
+    .keys()
+    .filter(|file| {
+      patterns
+        .iter()
+        .any(|pattern| pattern.matches_path_with(file, MATCH_OPTIONS))
+    })
+    .collect();
+  matched.sort();
+  for file in matched {
+    if !entries.contains(file) {
+      entries.push(file.clone());
+    }
+  }
+  entries
+    .into_iter()
+    .map(|entry| (entry, ChunkKind::Entry))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_chunk_files_stop_at_lazy_imports() {
+    let mut graph = ModuleGraph::default();
+    let edge = |graph: &mut ModuleGraph, from: &str, to: &str| {
+      graph
+        .static_imports
+        .entry(PathBuf::from(from))
+        .or_default()
+        .insert(PathBuf::from(to));
+    };
+    edge(&mut graph, "main.ts", "app.ts");
+    edge(&mut graph, "app.ts", "shared.ts");
+    edge(&mut graph, "billing.ts", "shared.ts");
+    edge(&mut graph, "billing.ts", "chart.ts");
+    graph.lazy_imports.insert(
+      PathBuf::from("app.ts"),
+      vec![(PathBuf::from("billing.ts"), ChunkKind::Lazy)],
+    );
+
+    let mut main: Vec<PathBuf> = chunk_files(&graph, Path::new("main.ts"))
+      .into_iter()
+      .collect();
+    main.sort();
+    assert_eq!(main, ["app.ts", "main.ts", "shared.ts"].map(PathBuf::from));
+
+    let mut billing: Vec<PathBuf> = chunk_files(&graph, Path::new("billing.ts"))
+      .into_iter()
+      .collect();
+    billing.sort();
+    assert_eq!(
+      billing,
+      ["billing.ts", "chart.ts", "shared.ts"].map(PathBuf::from)
+    );
+  }
+}
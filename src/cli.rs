@@ -1,10 +1,28 @@
+use crate::affected_tests;
+use crate::api_diff;
+use crate::boundaries;
+use crate::changeset;
+use crate::chunks;
 use crate::core;
+use crate::cycles;
 use crate::error::Result;
+use crate::named_inputs;
 use crate::profiler::Profiler;
-use crate::types::{LockfileStrategy, TrueAffectedConfig};
+use crate::project_graph;
+use crate::routes;
+use crate::semantic::WorkspaceAnalyzer;
+use crate::stories;
+use crate::types::{
+  AffectedScope, BoundaryMode, BoundaryViolation, BumpType, CycleReport, GraphFormat, ImportCycle,
+  LockfileStrategy, PackageBump, ProjectApiDiff, ProjectGraphReport, ProjectTarget, Propagation,
+  StoriesFormat, TestsFormat, TrueAffectedConfig, UnusedExport,
+};
+use crate::unused_exports;
+use crate::utils::{filter_by_tags, select_targets, ProjectIndex};
 use crate::workspace;
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use rustc_hash::FxHashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::debug;
@@ -61,6 +79,250 @@ enum Commands {
     /// Lockfile change detection strategy: none, direct, full
     #[arg(long, default_value = "direct")]
     lockfile_strategy: LockfileStrategy,
+
+    /// Trace changes to individual class, object literal and enum members
+    #[arg(long)]
+    member_precision: bool,
+
+    /// Which dependency edges propagate changes: all, runtime (skip type-only edges)
+    #[arg(long, default_value = "all")]
+    propagation: Propagation,
+
+    /// Whether implementation-only changes cross project boundaries: full, signature
+    #[arg(long, default_value = "full")]
+    boundary_mode: BoundaryMode,
+
+    /// Only list projects with at least one of these tags (comma-separated, `*` globs allowed)
+    #[arg(long, value_delimiter = ',')]
+    tags: Vec<String>,
+
+    /// Leave out projects with any of these tags (comma-separated, `*` globs allowed)
+    #[arg(long, value_delimiter = ',')]
+    exclude_tags: Vec<String>,
+
+    /// Include each project's tags in the output (JSON entries become `{ name, tags }`, or
+    /// `{ name, project, target, tags }` with --target)
+    #[arg(long)]
+    with_tags: bool,
+
+    /// Only list projects affected in this scope: production (through non-test files) or test-only
+    #[arg(long, value_name = "SCOPE")]
+    only: Option<AffectedScope>,
+
+    /// Only list affected projects defining these targets, as `project:target` pairs (comma-separated).
+    /// In Nx workspaces, targets none of whose inputs changed are left out
+    #[arg(long = "target", value_delimiter = ',', value_name = "TARGET")]
+    targets: Vec<String>,
+  },
+  /// Report public API changes of each project's entry point
+  ApiDiff {
+    /// Base branch to compare against
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+  },
+  /// Suggest version bumps for affected publishable packages
+  Changeset {
+    /// Base branch to compare against
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Write the suggestion to a .changeset/*.md file
+    #[arg(long)]
+    write: bool,
+  },
+  /// Report import cycles between files and between projects
+  Cycles {
+    /// Base branch to compare against (with --fail-on-new)
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Only report cycles not present at the base revision, and exit with an
+    /// error if there are any
+    #[arg(long)]
+    fail_on_new: bool,
+  },
+  /// Report exports that nothing in the workspace imports
+  UnusedExports {
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Glob of files allowed to have unused exports, in addition to package
+    /// entry points, pages, stories and config files (repeatable)
+    #[arg(long = "entry", value_name = "GLOB")]
+    entries: Vec<String>,
+  },
+  /// Show project dependencies derived from source imports
+  ProjectGraph {
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Output format: text, json (Nx project graph), dot (Graphviz)
+    #[arg(long, default_value = "text")]
+    format: GraphFormat,
+  },
+  /// Check imports between projects against tag-based dependency constraints
+  Boundaries {
+    /// Base branch to compare against (with --changed)
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Only check the imports of files changed since the base
+    #[arg(long)]
+    changed: bool,
+  },
+  /// List the test files that can observe the changes
+  AffectedTests {
+    /// Base branch to compare against
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Output format: text, json, jest (--testPathPattern), vitest, playwright (file filters)
+    #[arg(long, default_value = "text")]
+    format: TestsFormat,
+
+    /// Glob of test files, in addition to the defaults and `domino.testPatterns` (repeatable)
+    #[arg(long = "pattern", value_name = "GLOB")]
+    patterns: Vec<String>,
+
+    /// Which dependency edges propagate changes: all, runtime
+    #[arg(long, default_value = "all")]
+    propagation: Propagation,
+  },
+  /// List the bundle entry points and lazy chunks affected by the changes
+  AffectedChunks {
+    /// Base branch to compare against
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Glob of entry point files, in addition to the inferred ones (repeatable)
+    #[arg(long = "entry", value_name = "GLOB")]
+    entries: Vec<String>,
+
+    /// Which dependency edges propagate changes: all, runtime
+    #[arg(long, default_value = "all")]
+    propagation: Propagation,
+  },
+  /// List the file-system routes (Next.js, Remix) affected by the changes
+  AffectedRoutes {
+    /// Base branch to compare against
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Which dependency edges propagate changes: all, runtime
+    #[arg(long, default_value = "all")]
+    propagation: Propagation,
+  },
+  /// List the Storybook stories that can render differently after the changes
+  AffectedStories {
+    /// Base branch to compare against
+    #[arg(short, long, default_value = "origin/main")]
+    base: String,
+
+    /// Current working directory
+    #[arg(long)]
+    cwd: Option<PathBuf>,
+
+    /// Head commit to compare (defaults to working tree)
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Output format: text, json, ids, chromatic (--only-story-names), loki (--storiesFilter)
+    #[arg(long, default_value = "text")]
+    format: StoriesFormat,
+
+    /// Glob of story files, in addition to *.stories.* (repeatable)
+    #[arg(long = "pattern", value_name = "GLOB")]
+    patterns: Vec<String>,
+
+    /// Which dependency edges propagate changes: all, runtime
+    #[arg(long, default_value = "all")]
+    propagation: Propagation,
   },
 }
 
@@ -68,7 +330,35 @@ pub fn run() -> Result<()> {
   let cli = Cli::parse();
 
   // Check if json mode is enabled (need to extract from command)
-  let json_mode = matches!(&cli.command, Commands::Affected { json: true, .. });
+  let json_mode = matches!(
+    &cli.command,
+    Commands::Affected { json: true, .. }
+      | Commands::ApiDiff { json: true, .. }
+      | Commands::Changeset { json: true, .. }
+      | Commands::Cycles { json: true, .. }
+      | Commands::UnusedExports { json: true, .. }
+      | Commands::Boundaries { json: true, .. }
+      | Commands::AffectedChunks { json: true, .. }
+      | Commands::AffectedRoutes { json: true, .. }
+      | Commands::ProjectGraph {
+        format: GraphFormat::Json | GraphFormat::Dot,
+        ..
+      }
+      | Commands::AffectedTests {
+        format: TestsFormat::Json
+          | TestsFormat::Jest
+          | TestsFormat::Vitest
+          | TestsFormat::Playwright,
+        ..
+      }
+      | Commands::AffectedStories {
+        format: StoriesFormat::Json
+          | StoriesFormat::Ids
+          | StoriesFormat::Chromatic
+          | StoriesFormat::Loki,
+        ..
+      }
+  );
 
   // Setup logging with cleaner formatting
   // In JSON mode, suppress warnings to ensure clean JSON output
@@ -98,6 +388,14 @@ pub fn run() -> Result<()> {
       profile,
       report,
       lockfile_strategy,
+      member_precision,
+      propagation,
+      boundary_mode,
+      tags,
+      exclude_tags,
+      with_tags,
+      targets,
+      only,
     } => {
       let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
 
@@ -129,16 +427,45 @@ pub fn run() -> Result<()> {
 
       debug!("Found {} projects", projects.len());
 
+      let project_tags: FxHashMap<String, Vec<String>> = projects
+        .iter()
+        .map(|p| (p.name.clone(), p.tags.clone()))
+        .collect();
+      let tagged = |name: &String| {
+        let tags = project_tags.get(name).cloned().unwrap_or_default();
+        serde_json::json!({ "name": name, "tags": tags })
+      };
+      let label = |name: &String| match project_tags.get(name) {
+        Some(tags) if with_tags && !tags.is_empty() => {
+          format!("{} {}", name, format!("[{}]", tags.join(", ")).dimmed())
+        }
+        _ => name.clone(),
+      };
+
       if all {
         // Show all projects
-        let all_projects: Vec<String> = projects.iter().map(|p| p.name.clone()).collect();
+        let all_projects = filter_by_tags(
+          projects.iter().map(|p| p.name.clone()).collect(),
+          &projects,
+          &tags,
+          &exclude_tags,
+        );
 
-        if json {
+        if !targets.is_empty() {
+          print_targets(
+            &select_targets(&all_projects, &projects, &targets),
+            json,
+            with_tags.then_some(&project_tags),
+          );
+        } else if json && with_tags {
+          let entries: Vec<_> = all_projects.iter().map(tagged).collect();
+          println!("{}", serde_json::to_string(&entries).unwrap());
+        } else if json {
           println!("{}", serde_json::to_string(&all_projects).unwrap());
         } else {
           println!("{}", "All projects:".bold().blue());
           for project in &all_projects {
-            println!("  {} {}", "•".blue(), project);
+            println!("  {} {}", "•".blue(), label(project));
           }
           println!("\n{} {} projects", "Total:".bold(), all_projects.len());
         }
@@ -152,18 +479,59 @@ pub fn run() -> Result<()> {
       // Run true-affected analysis
       let config = TrueAffectedConfig {
         cwd: cwd.clone(),
-        base,
-        head,
-        projects,
+        base: base.clone(),
+        head: head.clone(),
+        projects: projects.clone(),
         lockfile_strategy,
+        member_precision,
+        propagation,
+        boundary_mode,
       };
 
-      // Use the report-generating version if --report is specified
-      let result = if report.is_some() {
-        core::find_affected_with_report(config, profiler)?
+      // In Nx workspaces, --target also needs the project graph for `^`
+      // inputs, which shares the analyzer with the affected analysis
+      let nx_inputs = if targets.is_empty() {
+        None
       } else {
-        core::find_affected(config, profiler)?
+        named_inputs::NxInputs::load(&cwd, &projects)
+      };
+      let analyzer = match nx_inputs {
+        Some(_) => Some(WorkspaceAnalyzer::new(
+          projects.clone(),
+          &cwd,
+          profiler.clone(),
+        )?),
+        None => None,
+      };
+
+      // Use the report-generating version if --report is specified
+      // Scopes are derived from the causes, which only the report records
+      let with_report = report.is_some() || only.is_some();
+      let mut result = match &analyzer {
+        Some(analyzer) if with_report => {
+          core::find_affected_with_report_using(config, analyzer, profiler)?
+        }
+        Some(analyzer) => core::find_affected_using(config, analyzer, profiler)?,
+        None if with_report => core::find_affected_with_report(config, profiler)?,
+        None => core::find_affected(config, profiler)?,
       };
+      if let (Some(only), Some(report_data)) = (only, &result.report) {
+        let scopes: FxHashMap<&str, AffectedScope> = report_data
+          .projects
+          .iter()
+          .map(|project| (project.name.as_str(), project.scope))
+          .collect();
+        // Projects without recorded causes are kept as production
+        result.affected_projects.retain(|name| {
+          scopes
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(AffectedScope::Production)
+            == only
+        });
+      }
+      result.affected_projects =
+        filter_by_tags(result.affected_projects, &projects, &tags, &exclude_tags);
 
       // Generate HTML report if requested
       if let Some(report_path) = report {
@@ -181,7 +549,52 @@ pub fn run() -> Result<()> {
         }
       }
 
-      if json {
+      if !targets.is_empty() {
+        let mut affected_targets = select_targets(&result.affected_projects, &projects, &targets);
+        // In Nx workspaces, drop targets none of whose inputs changed, and add
+        // those reading a changed workspace file of their own inputs
+        if let (Some(inputs), Some(analyzer)) = (&nx_inputs, &analyzer) {
+          let (changed_files, _) = crate::git::get_changed_files(&cwd, &base, head.as_deref())?;
+          let lockfile_changed = crate::lockfile::detect_package_manager(&cwd)
+            .is_some_and(|pm| crate::lockfile::has_lockfile_changed(&changed_files, &pm));
+          let index = ProjectIndex::new(&projects, &cwd);
+          let graph = project_graph::build_project_graph_using(&cwd, analyzer).graph;
+          affected_targets = named_inputs::filter_targets_by_inputs(
+            inputs,
+            affected_targets,
+            &changed_files,
+            &index,
+            &graph,
+            lockfile_changed,
+          );
+          let unaffected: Vec<String> = filter_by_tags(
+            projects
+              .iter()
+              .map(|project| project.name.clone())
+              .collect(),
+            &projects,
+            &tags,
+            &exclude_tags,
+          )
+          .into_iter()
+          .filter(|name| !result.affected_projects.contains(name))
+          .collect();
+          affected_targets.extend(named_inputs::targets_reading_workspace_files(
+            inputs,
+            select_targets(&unaffected, &projects, &targets),
+            &changed_files,
+            &index,
+          ));
+          affected_targets.sort_by_key(|pair| {
+            let position = targets.iter().position(|target| *target == pair.target);
+            (pair.project.clone(), position)
+          });
+        }
+        print_targets(&affected_targets, json, with_tags.then_some(&project_tags));
+      } else if json && with_tags {
+        let entries: Vec<_> = result.affected_projects.iter().map(tagged).collect();
+        println!("{}", serde_json::to_string(&entries).unwrap());
+      } else if json {
         println!(
           "{}",
           serde_json::to_string(&result.affected_projects).unwrap()
@@ -191,7 +604,7 @@ pub fn run() -> Result<()> {
       } else {
         println!("{}", "Affected projects:".bold().green());
         for project in &result.affected_projects {
-          println!("  {} {}", "•".green(), project);
+          println!("  {} {}", "•".green(), label(project));
         }
         println!(
           "\n{} {} affected project{}",
@@ -207,5 +620,666 @@ pub fn run() -> Result<()> {
 
       Ok(())
     }
+    Commands::ApiDiff {
+      base,
+      head,
+      cwd,
+      json,
+    } => {
+      let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
+
+      let base = if base == "origin/main" {
+        crate::git::detect_default_branch(&cwd)
+      } else {
+        base
+      };
+
+      let projects = workspace::discover_projects(&cwd)?;
+      if projects.is_empty() {
+        if !json {
+          eprintln!("{}", "No projects found in workspace".red());
+        }
+        return Ok(());
+      }
+
+      let profiler = Arc::new(Profiler::new(false));
+      let diffs = api_diff::find_api_changes(&cwd, &base, head.as_deref(), projects, profiler)?;
+
+      if json {
+        println!("{}", serde_json::to_string(&diffs).unwrap());
+      } else if diffs.is_empty() {
+        println!("{}", "No public API changes".yellow());
+      } else {
+        for diff in &diffs {
+          print_api_diff(diff);
+        }
+      }
+
+      Ok(())
+    }
+    Commands::Changeset {
+      base,
+      head,
+      cwd,
+      json,
+      write,
+    } => {
+      let Some(config) = resolve_config(cwd, base, head, Propagation::All, json)? else {
+        return Ok(());
+      };
+      let (cwd, base, head) = (config.cwd.clone(), config.base.clone(), config.head.clone());
+      let bumps = changeset::find_package_bumps(config, Arc::new(Profiler::new(false)))?;
+
+      if json {
+        println!("{}", serde_json::to_string(&bumps).unwrap());
+      } else if bumps.is_empty() {
+        println!("{}", "No publishable packages affected".yellow());
+      } else {
+        print_package_bumps(&bumps);
+      }
+
+      if write && !bumps.is_empty() {
+        let revision = crate::git::get_base_revision(&cwd, &base, head.as_deref())?;
+        let path = changeset::write_changeset(&cwd, &revision, &bumps)?;
+        if !json {
+          eprintln!(
+            "{} {}",
+            "✓".green(),
+            format!("Changeset written: {}", path.display()).bold()
+          );
+        }
+      }
+
+      Ok(())
+    }
+    Commands::AffectedTests {
+      base,
+      head,
+      cwd,
+      format,
+      patterns,
+      propagation,
+    } => {
+      let Some(config) = resolve_config(cwd, base, head, propagation, format != TestsFormat::Text)?
+      else {
+        return Ok(());
+      };
+      let tests =
+        affected_tests::find_affected_tests(config, &patterns, Arc::new(Profiler::new(false)))?;
+
+      if format != TestsFormat::Text {
+        let output = affected_tests::format_tests(&tests, format);
+        if !output.is_empty() {
+          println!("{}", output);
+        }
+      } else if tests.files.is_empty() {
+        println!("{}", "No affected test files".yellow());
+      } else {
+        if tests.global {
+          println!(
+            "{}",
+            "A global input changed; every test file is affected".yellow()
+          );
+        }
+        println!(
+          "{}",
+          format!("Affected test files ({}):", tests.files.len())
+            .bold()
+            .green()
+        );
+        for file in &tests.files {
+          println!("  {} {}", "•".green(), file.display());
+        }
+      }
+
+      Ok(())
+    }
+    Commands::AffectedChunks {
+      base,
+      head,
+      cwd,
+      json,
+      entries,
+      propagation,
+    } => {
+      let Some(config) = resolve_config(cwd, base, head, propagation, json)? else {
+        return Ok(());
+      };
+      let affected =
+        chunks::find_affected_chunks(config, &entries, Arc::new(Profiler::new(false)))?;
+
+      if json {
+        println!("{}", serde_json::to_string(&affected).unwrap());
+      } else if affected.chunks.is_empty() {
+        println!("{}", "No affected chunks".yellow());
+      } else {
+        if affected.global {
+          println!(
+            "{}",
+            "A global input changed; every chunk is affected".yellow()
+          );
+        }
+        if affected.initial {
+          println!("{}", "Initial bundle is affected".bold().red());
+        } else {
+          println!(
+            "{}",
+            "Only lazily loaded chunks are affected".bold().green()
+          );
+        }
+        println!(
+          "{}",
+          format!("Affected chunks ({}):", affected.chunks.len())
+            .bold()
+            .green()
+        );
+        for chunk in &affected.chunks {
+          println!(
+            "  {} {} {} {}",
+            "•".green(),
+            chunk.kind,
+            chunk.root.display(),
+            format!("({})", chunk.project).dimmed()
+          );
+          for file in &chunk.files {
+            println!("      {}", file.display().to_string().dimmed());
+          }
+        }
+      }
+
+      Ok(())
+    }
+    Commands::AffectedRoutes {
+      base,
+      head,
+      cwd,
+      json,
+      propagation,
+    } => {
+      let Some(config) = resolve_config(cwd, base, head, propagation, json)? else {
+        return Ok(());
+      };
+      let affected = routes::find_affected_routes(config, Arc::new(Profiler::new(false)))?;
+
+      if json {
+        println!("{}", serde_json::to_string(&affected).unwrap());
+      } else if affected.routes.is_empty() {
+        println!("{}", "No affected routes".yellow());
+      } else {
+        if affected.global {
+          println!(
+            "{}",
+            "A global input changed; every route is affected".yellow()
+          );
+        }
+        println!(
+          "{}",
+          format!("Affected routes ({}):", affected.routes.len())
+            .bold()
+            .green()
+        );
+        for route in &affected.routes {
+          println!(
+            "  {} {} {}",
+            "•".green(),
+            route.route,
+            format!("({})", route.project).dimmed()
+          );
+        }
+      }
+
+      Ok(())
+    }
+    Commands::AffectedStories {
+      base,
+      head,
+      cwd,
+      format,
+      patterns,
+      propagation,
+    } => {
+      let Some(config) =
+        resolve_config(cwd, base, head, propagation, format != StoriesFormat::Text)?
+      else {
+        return Ok(());
+      };
+      let patterns: Vec<String> = stories::STORY_FILE_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .chain(patterns)
+        .collect();
+      let affected =
+        stories::find_affected_stories(config, &patterns, Arc::new(Profiler::new(false)))?;
+      if !affected.untitled.is_empty() {
+        eprintln!(
+          "{} {}",
+          "⚠".yellow(),
+          "Affected story files without a static title or a covering Storybook `stories` entry:"
+            .yellow()
+        );
+        for file in &affected.untitled {
+          eprintln!("  {} {}", "•".yellow(), file.display());
+        }
+      }
+
+      if format != StoriesFormat::Text {
+        let output = stories::format_stories(&affected, format);
+        if !output.is_empty() {
+          println!("{}", output);
+        }
+      } else if affected.stories.is_empty() {
+        println!("{}", "No affected stories".yellow());
+      } else {
+        if affected.global {
+          println!(
+            "{}",
+            "A global input changed; every story is affected".yellow()
+          );
+        }
+        println!(
+          "{}",
+          format!("Affected stories ({}):", affected.stories.len())
+            .bold()
+            .green()
+        );
+        for story in &affected.stories {
+          println!(
+            "  {} {} {}",
+            "•".green(),
+            story.id,
+            format!("({})", story.file.display()).dimmed()
+          );
+        }
+      }
+
+      Ok(())
+    }
+    Commands::Cycles {
+      base,
+      head,
+      cwd,
+      json,
+      fail_on_new,
+    } => {
+      let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
+
+      let projects = workspace::discover_projects(&cwd)?;
+      if projects.is_empty() {
+        if !json {
+          eprintln!("{}", "No projects found in workspace".red());
+        }
+        return Ok(());
+      }
+
+      let profiler = Arc::new(Profiler::new(false));
+      let report = if fail_on_new {
+        let base = if base == "origin/main" {
+          crate::git::detect_default_branch(&cwd)
+        } else {
+          base
+        };
+        cycles::find_new_cycles(&cwd, &base, head.as_deref(), projects, profiler)?
+      } else {
+        cycles::find_cycles(&cwd, projects, profiler)?
+      };
+
+      if json {
+        println!("{}", serde_json::to_string(&report).unwrap());
+      } else if report.is_empty() {
+        let message = if fail_on_new {
+          "No new import cycles"
+        } else {
+          "No import cycles"
+        };
+        println!("{}", message.green());
+      } else {
+        print_cycles(&report);
+      }
+
+      if fail_on_new && !report.is_empty() {
+        return Err(crate::error::DominoError::Other(format!(
+          "{} new file cycle(s) and {} new project cycle(s) introduced",
+          report.files.len(),
+          report.projects.len()
+        )));
+      }
+
+      Ok(())
+    }
+    Commands::UnusedExports { cwd, json, entries } => {
+      let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
+
+      let projects = workspace::discover_projects(&cwd)?;
+      if projects.is_empty() {
+        if !json {
+          eprintln!("{}", "No projects found in workspace".red());
+        }
+        return Ok(());
+      }
+
+      let patterns: Vec<String> = unused_exports::DEFAULT_ENTRY_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .chain(entries)
+        .collect();
+      let unused = unused_exports::find_unused_exports(
+        &cwd,
+        projects,
+        &patterns,
+        Arc::new(Profiler::new(false)),
+      )?;
+
+      if json {
+        println!("{}", serde_json::to_string(&unused).unwrap());
+      } else if unused.is_empty() {
+        println!("{}", "No unused exports".green());
+      } else {
+        print_unused_exports(&unused);
+      }
+
+      Ok(())
+    }
+    Commands::ProjectGraph { cwd, format } => {
+      let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
+
+      let projects = workspace::discover_projects(&cwd)?;
+      if projects.is_empty() {
+        if format == GraphFormat::Text {
+          eprintln!("{}", "No projects found in workspace".red());
+        }
+        return Ok(());
+      }
+
+      let report =
+        project_graph::build_project_graph(&cwd, projects, Arc::new(Profiler::new(false)))?;
+      match format {
+        GraphFormat::Json => println!("{}", serde_json::to_string(&report).unwrap()),
+        GraphFormat::Dot => print!("{}", project_graph::to_dot(&report.graph)),
+        GraphFormat::Text => print_project_graph(&report),
+      }
+
+      Ok(())
+    }
+    Commands::Boundaries {
+      base,
+      head,
+      cwd,
+      json,
+      changed,
+    } => {
+      let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
+
+      let projects = workspace::discover_projects(&cwd)?;
+      if projects.is_empty() {
+        if !json {
+          eprintln!("{}", "No projects found in workspace".red());
+        }
+        return Ok(());
+      }
+
+      let changed_files = if changed {
+        let base = if base == "origin/main" {
+          crate::git::detect_default_branch(&cwd)
+        } else {
+          base
+        };
+        let (files, _) = crate::git::get_changed_files(&cwd, &base, head.as_deref())?;
+        Some(files.into_iter().map(|f| f.file_path).collect::<Vec<_>>())
+      } else {
+        None
+      };
+      let violations = boundaries::find_boundary_violations(
+        &cwd,
+        projects,
+        changed_files.as_deref(),
+        Arc::new(Profiler::new(false)),
+      )?;
+
+      if json {
+        println!("{}", serde_json::to_string(&violations).unwrap());
+      } else if violations.is_empty() {
+        println!("{}", "No module boundary violations".green());
+      } else {
+        print_boundary_violations(&violations);
+      }
+
+      if !violations.is_empty() {
+        return Err(crate::error::DominoError::Other(format!(
+          "{} module boundary violation(s)",
+          violations.len()
+        )));
+      }
+
+      Ok(())
+    }
+  }
+}
+
+/// The analysis config shared by the subcommands tracing a change: `cwd`
+/// defaults to the current directory and the default `origin/main` base to
+/// the detected default branch. `None` when the workspace has no projects,
+/// which is reported on stderr unless `quiet`.
+fn resolve_config(
+  cwd: Option<PathBuf>,
+  base: String,
+  head: Option<String>,
+  propagation: Propagation,
+  quiet: bool,
+) -> Result<Option<TrueAffectedConfig>> {
+  let cwd = cwd.unwrap_or_else(|| std::env::current_dir().unwrap());
+  let base = if base == "origin/main" {
+    crate::git::detect_default_branch(&cwd)
+  } else {
+    base
+  };
+
+  let projects = workspace::discover_projects(&cwd)?;
+  if projects.is_empty() {
+    if !quiet {
+      eprintln!("{}", "No projects found in workspace".red());
+    }
+    return Ok(None);
+  }
+
+  Ok(Some(TrueAffectedConfig {
+    cwd,
+    base,
+    head,
+    projects,
+    lockfile_strategy: LockfileStrategy::default(),
+    member_precision: false,
+    propagation,
+    boundary_mode: BoundaryMode::Full,
+  }))
+}
+
+/// Print `project:target` pairs. With `tags` (`--with-tags`), JSON entries
+/// become `{ name, project, target, tags }` and text lines show the tags.
+fn print_targets(
+  targets: &[ProjectTarget],
+  json: bool,
+  tags: Option<&FxHashMap<String, Vec<String>>>,
+) {
+  let project_tags = |pair: &ProjectTarget| {
+    tags
+      .and_then(|tags| tags.get(&pair.project))
+      .cloned()
+      .unwrap_or_default()
+  };
+  if json && tags.is_some() {
+    let entries: Vec<_> = targets
+      .iter()
+      .map(|pair| {
+        serde_json::json!({
+          "name": pair.to_string(),
+          "project": pair.project,
+          "target": pair.target,
+          "tags": project_tags(pair),
+        })
+      })
+      .collect();
+    println!("{}", serde_json::to_string(&entries).unwrap());
+  } else if json {
+    let pairs: Vec<String> = targets.iter().map(ToString::to_string).collect();
+    println!("{}", serde_json::to_string(&pairs).unwrap());
+  } else if targets.is_empty() {
+    println!("{}", "No affected targets".yellow());
+  } else {
+    println!("{}", "Affected targets:".bold().green());
+    for pair in targets {
+      let pair_tags = project_tags(pair);
+      if pair_tags.is_empty() {
+        println!("  {} {}", "•".green(), pair);
+      } else {
+        let tag_list = format!("[{}]", pair_tags.join(", "));
+        println!("  {} {} {}", "•".green(), pair, tag_list.dimmed());
+      }
+    }
+    println!(
+      "\n{} {} target{}",
+      "Total:".bold(),
+      targets.len(),
+      if targets.len() == 1 { "" } else { "s" }
+    );
+  }
+}
+
+fn print_boundary_violations(violations: &[BoundaryViolation]) {
+  println!(
+    "{}",
+    format!("Module boundary violations ({}):", violations.len())
+      .bold()
+      .red()
+  );
+  for violation in violations {
+    println!(
+      "  {} {} → {} {}",
+      format!("{}:{}", violation.file.display(), violation.line).dimmed(),
+      violation.project,
+      violation.dependency,
+      format!("('{}')", violation.specifier).dimmed()
+    );
+    println!("      {}", violation.message);
+  }
+}
+
+fn print_project_graph(report: &ProjectGraphReport) {
+  println!("{}", "Project dependencies:".bold().green());
+  for (project, dependencies) in &report.graph.dependencies {
+    println!("  {}", project.bold());
+    for dependency in dependencies {
+      println!(
+        "    → {} {}",
+        dependency.target,
+        format!("({})", dependency.dependency_type).dimmed()
+      );
+    }
+  }
+
+  for (title, drift) in [
+    (
+      "Imported but missing from package.json:",
+      &report.undeclared_dependencies,
+    ),
+    (
+      "Declared in package.json but never imported:",
+      &report.unused_dependencies,
+    ),
+  ] {
+    if drift.is_empty() {
+      continue;
+    }
+    println!();
+    println!("{}", title.bold().yellow());
+    for entry in drift {
+      println!("  {} → {}", entry.project, entry.dependency);
+    }
+  }
+}
+
+fn print_unused_exports(unused: &[UnusedExport]) {
+  println!(
+    "{}",
+    format!("Unused exports ({}):", unused.len())
+      .bold()
+      .yellow()
+  );
+  for export in unused {
+    println!(
+      "  {} {}",
+      format!("{}:{}", export.file.display(), export.line).dimmed(),
+      export.name
+    );
+  }
+}
+
+fn print_cycles(report: &CycleReport) {
+  let print = |title: &str, cycles: &[ImportCycle]| {
+    if cycles.is_empty() {
+      return;
+    }
+    println!(
+      "{}",
+      format!("{} ({}):", title, cycles.len()).bold().yellow()
+    );
+    for cycle in cycles {
+      println!("  {}", cycle.path.join(" → "));
+      for (from, to) in &cycle.via {
+        println!("      {}", format!("{} → {}", from, to).dimmed());
+      }
+    }
+  };
+  print("File cycles", &report.files);
+  print("Project cycles", &report.projects);
+}
+
+fn print_package_bumps(bumps: &[PackageBump]) {
+  println!("{}", "Suggested version bumps:".bold().green());
+  for bump in bumps {
+    let label = bump.bump.to_string();
+    let label = match bump.bump {
+      BumpType::Major => label.red().bold(),
+      BumpType::Minor => label.yellow().bold(),
+      BumpType::Patch => label.green().bold(),
+    };
+    println!("  {} {}", label, bump.package);
+    for reason in &bump.reasons {
+      println!("      {}", reason.dimmed());
+    }
+  }
+}
+
+fn print_api_diff(diff: &ProjectApiDiff) {
+  println!(
+    "{} {}",
+    diff.project.bold(),
+    format!("({})", diff.entry_point.display()).dimmed()
+  );
+  for entry in &diff.added {
+    println!(
+      "  {} {} {}",
+      "+".green(),
+      entry.kind.to_string().dimmed(),
+      entry.signature.green()
+    );
+  }
+  for entry in &diff.removed {
+    println!(
+      "  {} {} {}",
+      "-".red(),
+      entry.kind.to_string().dimmed(),
+      entry.signature.red()
+    );
+  }
+  for change in &diff.changed {
+    println!(
+      "  {} {} {}",
+      "~".yellow(),
+      change.kind.to_string().dimmed(),
+      change.name.bold()
+    );
+    println!("      {} {}", "-".red(), change.base.red());
+    println!("      {} {}", "+".green(), change.head.green());
   }
+  println!();
 }
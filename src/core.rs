@@ -1,25 +1,101 @@
+use crate::api_diff::SignatureComparison;
 use crate::error::Result;
 use crate::git;
 use crate::lockfile;
 use crate::named_inputs;
 use crate::profiler::Profiler;
 use crate::semantic::{AssetReferenceFinder, ReferenceFinder, WorkspaceAnalyzer};
+use crate::test_files::TestFiles;
 use crate::types::{
-  AffectCause, AffectedProjectInfo, AffectedReport, AffectedResult, ChangedFile, GlobalTrigger,
-  LockfileStrategy, Project, ReportTotals, TrueAffectedConfig,
+  AffectCause, AffectedFiles, AffectedProjectInfo, AffectedReport, AffectedResult, AffectedScope,
+  BoundaryMode, ChangedFile, GlobalTrigger, LockfileStrategy, Project, Propagation, Reference,
+  ReportTotals, TrueAffectedConfig,
 };
 use crate::utils::{self, ProjectIndex};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tracing::debug;
+use tracing::{debug, warn};
+
+/// Pseudo-symbol traced when a change cannot be pinned to a declaration but the
+/// file is loaded as a whole through a `new URL('…', import.meta.url)` module
+/// reference (a worker entry point). It matches the namespace (`*`) entries of
+/// the import index, which is where module URL importers live.
+const WHOLE_MODULE_SYMBOL: &str = "*";
+
+/// Separator between a class and one of its members in a member-precision
+/// symbol path (`ApiClient#getUser`). Binding names can't contain `#`, so a
+/// path never collides with a top-level symbol.
+const MEMBER_SEPARATOR: char = '#';
+
+/// Separator between a top-level object literal or enum and one of its
+/// properties in a member-precision symbol path (`ROUTES.billing`,
+/// `Permission.Read`).
+const PROPERTY_SEPARATOR: char = '.';
 
 /// Mutable state for tracking affected symbols during analysis
 struct AffectedState<'a> {
   affected_packages: &'a mut FxHashSet<String>,
   project_causes: Option<&'a mut FxHashMap<String, Vec<AffectCause>>>,
   visited: &'a mut FxHashSet<(PathBuf, String)>,
+  /// Trace class members (`ApiClient#getUser`) instead of whole classes
+  member_precision: bool,
+  /// Whether type-only references propagate
+  propagation: Propagation,
+  /// Base/head signature comparison when cross-project propagation is limited
+  /// to signature changes (`BoundaryMode::Signature`)
+  signatures: Option<&'a mut SignatureComparison>,
+  /// Every file a change reaches, when the caller wants files rather than
+  /// projects (see [`find_affected_files`])
+  reached_files: Option<&'a mut FxHashSet<PathBuf>>,
+}
+
+impl AffectedState<'_> {
+  /// Whether a change propagates through `reference` under the configured
+  /// propagation mode.
+  fn follows(&self, reference: &Reference) -> bool {
+    self.propagation == Propagation::All || !reference.is_type_only
+  }
+
+  /// Whether a change to `symbol_name` stops before reaching `ref_packages`:
+  /// in signature boundary mode, projects other than the symbol's own are
+  /// only affected when its signature changed.
+  fn stops_at_boundary(
+    &mut self,
+    analyzer: &WorkspaceAnalyzer,
+    file_path: &Path,
+    symbol_name: &str,
+    source_projects: &[String],
+    ref_packages: &[String],
+  ) -> bool {
+    let Some(signatures) = self.signatures.as_deref_mut() else {
+      return false;
+    };
+    let crosses_boundary = !ref_packages.iter().any(|pkg| source_projects.contains(pkg));
+    crosses_boundary && !signatures.changed(analyzer, file_path, symbol_name)
+  }
+
+  /// A type-position use of a symbol whose signature changed changes the
+  /// signature of the declarations containing it (`function f(o: Options)`
+  /// when `Options` changed), even though their own text did not.
+  fn carry_signature_change(
+    &mut self,
+    analyzer: &WorkspaceAnalyzer,
+    file_path: &Path,
+    symbol_name: &str,
+    reference: &Reference,
+    containers: &[String],
+  ) {
+    let Some(signatures) = self.signatures.as_deref_mut() else {
+      return;
+    };
+    if reference.is_type_only && signatures.changed(analyzer, file_path, symbol_name) {
+      for container in containers {
+        signatures.mark_changed(&reference.file_path, container);
+      }
+    }
+  }
 }
 
 /// Record a `DirectChange` cause for `pkg` for each changed line (or a
@@ -60,7 +136,7 @@ pub fn find_affected(
   config: TrueAffectedConfig,
   profiler: Arc<Profiler>,
 ) -> Result<AffectedResult> {
-  find_affected_internal(config, profiler, false)
+  find_affected_internal(config, profiler, false, None, None, None)
 }
 
 /// Main true-affected algorithm implementation with optional report generation
@@ -68,13 +144,86 @@ pub fn find_affected_with_report(
   config: TrueAffectedConfig,
   profiler: Arc<Profiler>,
 ) -> Result<AffectedResult> {
-  find_affected_internal(config, profiler, true)
+  find_affected_internal(config, profiler, true, None, None, None)
+}
+
+/// [`find_affected`] on an already built `analyzer` of `config.projects`, for
+/// callers that analyze the workspace further
+pub fn find_affected_using(
+  config: TrueAffectedConfig,
+  analyzer: &WorkspaceAnalyzer,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedResult> {
+  find_affected_internal(config, profiler, false, Some(analyzer), None, None)
+}
+
+/// [`find_affected_with_report`] on an already built `analyzer` of
+/// `config.projects`, for callers that analyze the workspace further
+pub fn find_affected_with_report_using(
+  config: TrueAffectedConfig,
+  analyzer: &WorkspaceAnalyzer,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedResult> {
+  find_affected_internal(config, profiler, true, Some(analyzer), None, None)
+}
+
+/// Files reached by the changes: the changed files themselves plus every file
+/// referencing a changed symbol, transitively, sorted. `global_triggers` is
+/// non-empty when a global input changed, in which case every file is affected.
+pub fn find_affected_files(
+  config: TrueAffectedConfig,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedFiles> {
+  affected_files(config, None, profiler)
+}
+
+/// [`find_affected_files`] on an already built `analyzer` of
+/// `config.projects`, for callers that analyze the workspace further
+pub fn find_affected_files_using(
+  config: TrueAffectedConfig,
+  analyzer: &WorkspaceAnalyzer,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedFiles> {
+  affected_files(config, Some(analyzer), profiler)
+}
+
+fn affected_files(
+  config: TrueAffectedConfig,
+  analyzer: Option<&WorkspaceAnalyzer>,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedFiles> {
+  let mut reached = FxHashSet::default();
+  let mut changed = Vec::new();
+  // The report keeps global runs going through the semantic analysis and
+  // carries the triggers
+  let result = find_affected_internal(
+    config,
+    profiler,
+    true,
+    analyzer,
+    Some(&mut reached),
+    Some(&mut changed),
+  )?;
+  let mut files: Vec<PathBuf> = reached.into_iter().collect();
+  files.sort();
+  changed.sort();
+  Ok(AffectedFiles {
+    files,
+    changed,
+    global_triggers: result
+      .report
+      .map(|report| report.global_triggers)
+      .unwrap_or_default(),
+  })
 }
 
 fn find_affected_internal(
   config: TrueAffectedConfig,
   profiler: Arc<Profiler>,
   generate_report: bool,
+  analyzer: Option<&WorkspaceAnalyzer>,
+  mut reached_files: Option<&mut FxHashSet<PathBuf>>,
+  changed_out: Option<&mut Vec<PathBuf>>,
 ) -> Result<AffectedResult> {
   debug!("Starting true-affected analysis");
   debug!("Base: {}", config.base);
@@ -99,6 +248,9 @@ fn find_affected_internal(
     });
   }
 
+  let mut signatures = (config.boundary_mode == BoundaryMode::Signature)
+    .then(|| SignatureComparison::new(&config.cwd, &merge_base));
+
   // Step 1b: Apply the workspace's global-invalidation config — Nx
   // `namedInputs` or Turborepo `globalDependencies`, whichever the workspace
   // uses (see `resolve_global_inputs` for precedence) — collecting global
@@ -159,6 +311,12 @@ fn find_affected_internal(
   } else {
     changed_files
   };
+  if let Some(files) = reached_files.as_deref_mut() {
+    files.extend(changed_files.iter().map(|f| f.file_path.clone()));
+  }
+  if let Some(files) = changed_out {
+    files.extend(changed_files.iter().map(|f| f.file_path.clone()));
+  }
 
   // Step 2: Build project index for O(unique_roots) lookups instead of O(n_projects)
   // Also parses each project's tsconfig to extract exclude patterns, so that
@@ -166,12 +324,19 @@ fn find_affected_internal(
   let project_index = ProjectIndex::new(&config.projects, &config.cwd);
 
   // Step 3: Build workspace analyzer (includes building import index)
-  debug!("Building workspace semantic analysis...");
-  let analyzer = WorkspaceAnalyzer::new(config.projects.clone(), &config.cwd, profiler.clone())?;
+  let built;
+  let analyzer = match analyzer {
+    Some(analyzer) => analyzer,
+    None => {
+      debug!("Building workspace semantic analysis...");
+      built = WorkspaceAnalyzer::new(config.projects.clone(), &config.cwd, profiler.clone())?;
+      &built
+    }
+  };
   debug!("Analyzed {} files", analyzer.files.len());
 
   // Step 4: Initialize reference finder
-  let reference_finder = ReferenceFinder::new(&analyzer, &config.cwd, profiler.clone());
+  let reference_finder = ReferenceFinder::new(analyzer, &config.cwd, profiler.clone());
 
   // Step 5: Track affected packages and their causes
   let mut affected_packages = FxHashSet::default();
@@ -187,7 +352,7 @@ fn find_affected_internal(
         .as_ref()
         .is_none_or(|name| f.file_path.to_str() != Some(*name))
     })
-    .partition(|f| utils::is_source_file(&f.file_path));
+    .partition(|f| utils::is_analyzable_file(&f.file_path));
 
   debug!(
     "Partitioned files: {} source, {} assets",
@@ -236,7 +401,7 @@ fn find_affected_internal(
       .changed_lines
       .iter()
       .map(
-        |&line| match analyzer.find_node_at_line(file_path, line, 0) {
+        |&line| match symbols_at(analyzer, file_path, line, 0, config.member_precision) {
           Ok(symbols) => (line, symbols),
           Err(e) => {
             debug!(
@@ -341,6 +506,17 @@ fn find_affected_internal(
       .collect();
     unique_symbols.extend(deleted_symbols.iter());
 
+    // Top-level statements of a worker entry (`self.onmessage = ...`) have no
+    // enclosing symbol, but they still change what the spawning file runs.
+    let whole_module = WHOLE_MODULE_SYMBOL.to_string();
+    if unique_symbols.is_empty() && analyzer.is_module_url_entry(file_path) {
+      debug!(
+        "Untraceable change in module URL entry {:?}, tracing the whole module",
+        file_path
+      );
+      unique_symbols.insert(&whole_module);
+    }
+
     if unique_symbols.is_empty() {
       debug!(
         "No traceable symbols found in {:?}, skipping reference traversal",
@@ -369,12 +545,16 @@ fn find_affected_internal(
           None
         },
         visited: &mut visited,
+        member_precision: config.member_precision,
+        propagation: config.propagation,
+        signatures: signatures.as_mut(),
+        reached_files: reached_files.as_deref_mut(),
       };
 
       for symbol_name in &unique_symbols {
         debug!("Processing symbol '{}' in {:?}", symbol_name, file_path);
         if let Err(e) = process_changed_symbol(
-          &analyzer,
+          analyzer,
           &reference_finder,
           file_path,
           symbol_name,
@@ -443,6 +623,10 @@ fn find_affected_internal(
         for reference in references {
           let source_file_rel = &reference.source_file;
 
+          if let Some(files) = reached_files.as_deref_mut() {
+            files.insert(source_file_rel.clone());
+          }
+
           // Mark all referencing projects as affected
           let ref_packages = project_index.get_package_names_by_path(source_file_rel);
           for pkg in &ref_packages {
@@ -500,7 +684,11 @@ fn find_affected_internal(
             // Find exported symbols that use this import
             // E.g., if "diamondLottie" is imported and used by "Diamond" export,
             // we need to trace "Diamond" to find affected projects
-            match analyzer.find_exported_symbols_using(source_file_rel, &local_name) {
+            match analyzer.find_exported_symbols_using(
+              source_file_rel,
+              &local_name,
+              config.propagation == Propagation::All,
+            ) {
               Ok(exported_symbols) if !exported_symbols.is_empty() => {
                 debug!(
                   "Found {} exported symbols using '{}': {:?}",
@@ -520,6 +708,10 @@ fn find_affected_internal(
                       None
                     },
                     visited: &mut visited,
+                    member_precision: config.member_precision,
+                    propagation: config.propagation,
+                    signatures: signatures.as_mut(),
+                    reached_files: reached_files.as_deref_mut(),
                   };
 
                   debug!(
@@ -528,7 +720,7 @@ fn find_affected_internal(
                   );
 
                   if let Err(e) = process_changed_symbol(
-                    &analyzer,
+                    analyzer,
                     &reference_finder,
                     source_file_rel,
                     &export_symbol,
@@ -559,10 +751,14 @@ fn find_affected_internal(
                     None
                   },
                   visited: &mut visited,
+                  member_precision: config.member_precision,
+                  propagation: config.propagation,
+                  signatures: signatures.as_mut(),
+                  reached_files: reached_files.as_deref_mut(),
                 };
 
                 if let Err(e) = process_changed_symbol(
-                  &analyzer,
+                  analyzer,
                   &reference_finder,
                   source_file_rel,
                   &local_name,
@@ -623,6 +819,9 @@ fn find_affected_internal(
                 continue;
               }
 
+              if let Some(files) = reached_files.as_deref_mut() {
+                files.insert(file_path.clone());
+              }
               let owning_packages = project_index.get_package_names_by_path(file_path);
               for pkg in &owning_packages {
                 affected_packages.insert(pkg.clone());
@@ -640,15 +839,18 @@ fn find_affected_internal(
 
               if matches!(config.lockfile_strategy, LockfileStrategy::Full) {
                 for &(imp, _) in &matching_imports {
-                  let symbols_to_trace =
-                    match analyzer.find_exported_symbols_using(file_path, &imp.local_name) {
-                      Ok(exports) if !exports.is_empty() => exports,
-                      Ok(_) => vec![imp.local_name.clone()],
-                      Err(e) => {
-                        debug!("Error finding exports using '{}': {}", imp.local_name, e);
-                        continue;
-                      }
-                    };
+                  let symbols_to_trace = match analyzer.find_exported_symbols_using(
+                    file_path,
+                    &imp.local_name,
+                    config.propagation == Propagation::All,
+                  ) {
+                    Ok(exports) if !exports.is_empty() => exports,
+                    Ok(_) => vec![imp.local_name.clone()],
+                    Err(e) => {
+                      debug!("Error finding exports using '{}': {}", imp.local_name, e);
+                      continue;
+                    }
+                  };
 
                   for sym in symbols_to_trace {
                     let mut state = AffectedState {
@@ -659,9 +861,15 @@ fn find_affected_internal(
                         None
                       },
                       visited: &mut lockfile_visited,
+                      member_precision: config.member_precision,
+                      propagation: config.propagation,
+                      // A dependency bump can change the types behind an
+                      // unchanged declaration, so it always crosses boundaries
+                      signatures: None,
+                      reached_files: reached_files.as_deref_mut(),
                     };
                     if let Err(e) = process_changed_symbol(
-                      &analyzer,
+                      analyzer,
                       &reference_finder,
                       file_path,
                       &sym,
@@ -730,13 +938,30 @@ fn find_affected_internal(
       }
     }
 
+    // A bad `domino.testPatterns` glob only degrades the scopes of the report
+    let test_files = TestFiles::new(&config.cwd, &config.projects, &[]).unwrap_or_else(|e| {
+      warn!(
+        "{}; labelling test-only projects with the default test globs",
+        e
+      );
+      TestFiles::with_default_patterns(&config.cwd, &config.projects)
+    });
+    let scopes = test_files.classify(&project_causes);
     let mut projects_info: Vec<AffectedProjectInfo> = project_causes
       .into_iter()
       .map(|(name, mut causes)| {
         // Deduplicate causes - sort and remove duplicates
         causes.sort();
         causes.dedup();
-        AffectedProjectInfo { name, causes }
+        let scope = scopes
+          .get(&name)
+          .copied()
+          .unwrap_or(AffectedScope::Production);
+        AffectedProjectInfo {
+          name,
+          causes,
+          scope,
+        }
       })
       .collect();
     projects_info.sort_by(|a, b| a.name.cmp(&b.name));
@@ -792,6 +1017,20 @@ fn process_changed_symbol(
   }
   state.visited.insert(key);
 
+  if let Some((owner, member)) = symbol_name.split_once([MEMBER_SEPARATOR, PROPERTY_SEPARATOR]) {
+    let separator = symbol_name.as_bytes()[owner.len()] as char;
+    return process_changed_member(
+      analyzer,
+      reference_finder,
+      file_path,
+      owner,
+      separator,
+      member,
+      project_index,
+      state,
+    );
+  }
+
   debug!("Processing symbol '{}' in {:?}", symbol_name, file_path);
 
   // Get the source projects for causality tracking (may be multiple with shared sourceRoot)
@@ -805,11 +1044,33 @@ fn process_changed_symbol(
     symbol_name
   );
 
+  // Owners of the member paths step 1 descends into, which step 3 must not
+  // widen back to whole exports
+  let mut member_owners: FxHashSet<String> = FxHashSet::default();
   for local_ref in local_refs {
+    if !state.follows(&local_ref) {
+      continue;
+    }
     // Find the root symbol containing this reference
-    let container_symbols =
-      analyzer.find_node_at_line(file_path, local_ref.line, local_ref.column)?;
+    let container_symbols = symbols_at(
+      analyzer,
+      file_path,
+      local_ref.line,
+      local_ref.column,
+      state.member_precision,
+    )?;
+    state.carry_signature_change(
+      analyzer,
+      file_path,
+      symbol_name,
+      &local_ref,
+      &container_symbols,
+    );
     for container_symbol in container_symbols {
+      if let Some((owner, _)) = container_symbol.split_once([MEMBER_SEPARATOR, PROPERTY_SEPARATOR])
+      {
+        member_owners.insert(owner.to_string());
+      }
       // Skip if it's the same symbol (self-reference)
       if container_symbol != symbol_name {
         debug!(
@@ -850,14 +1111,26 @@ fn process_changed_symbol(
   // where findAllReferences tracks symbols through their exported containers.
   //
   // We skip exported symbols here because they're already handled by cross-file
-  // reference tracking in step 2 above.
-  if cross_file_refs.is_empty() && !analyzer.is_symbol_exported(file_path, symbol_name) {
+  // reference tracking in step 2 above. Member paths never get here (they are
+  // traced by `process_changed_member`); plain names keep this fallback in
+  // member-precision mode too, since it is what carries a local helper through
+  // `export { helper as api }` or `export default helper`. Exports step 1
+  // already followed at member granularity are not widened back to the whole
+  // class or object.
+  if !symbol_name.contains([MEMBER_SEPARATOR, PROPERTY_SEPARATOR])
+    && cross_file_refs.is_empty()
+    && !analyzer.is_symbol_exported(file_path, symbol_name)
+  {
     debug!(
       "Symbol '{}' has no cross-file references and is not exported. Checking if exported symbols use it.",
       symbol_name
     );
 
-    let exported_symbols_using = analyzer.find_exported_symbols_using(file_path, symbol_name)?;
+    let exported_symbols_using = analyzer.find_exported_symbols_using(
+      file_path,
+      symbol_name,
+      state.propagation == Propagation::All,
+    )?;
     debug!(
       "Found {} exported symbols using '{}': {:?}",
       exported_symbols_using.len(),
@@ -867,7 +1140,10 @@ fn process_changed_symbol(
 
     // Recursively process each exported symbol that uses this local symbol
     // This propagates the change through the export boundary
-    for exported_symbol in exported_symbols_using {
+    for exported_symbol in exported_symbols_using
+      .into_iter()
+      .filter(|exported| !member_owners.contains(exported))
+    {
       process_changed_symbol(
         analyzer,
         reference_finder,
@@ -881,77 +1157,223 @@ fn process_changed_symbol(
 
   // For each cross-file reference, recursively process the containing symbol in that file
   for reference in cross_file_refs {
-    // Mark all matching packages as affected
-    let ref_packages = project_index.get_package_names_by_path(&reference.file_path);
-    for pkg in &ref_packages {
-      state.affected_packages.insert(pkg.clone());
-
-      // Track cause if generating report
-      if let Some(ref mut causes_map) = state.project_causes {
-        for src_proj in &source_projects {
-          causes_map
-            .entry(pkg.clone())
-            .or_default()
-            .push(AffectCause::ImportedSymbol {
-              source_project: src_proj.clone(),
-              symbol: symbol_name.to_string(),
-              via_file: reference.file_path.clone(),
-              source_file: file_path.to_path_buf(),
-            });
-        }
+    process_cross_file_reference(
+      analyzer,
+      reference_finder,
+      file_path,
+      symbol_name,
+      &reference,
+      &source_projects,
+      project_index,
+      state,
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Mark the projects owning a cross-file `reference` to `symbol_name` as
+/// affected, then trace the symbol enclosing the reference in its file.
+#[allow(clippy::too_many_arguments)]
+fn process_cross_file_reference(
+  analyzer: &WorkspaceAnalyzer,
+  reference_finder: &ReferenceFinder,
+  file_path: &Path,
+  symbol_name: &str,
+  reference: &Reference,
+  source_projects: &[String],
+  project_index: &ProjectIndex,
+  state: &mut AffectedState,
+) -> Result<()> {
+  if !state.follows(reference) {
+    debug!(
+      "Skipping type-only reference to '{}' at {:?}:{}",
+      symbol_name, reference.file_path, reference.line
+    );
+    return Ok(());
+  }
+
+  let ref_packages = project_index.get_package_names_by_path(&reference.file_path);
+  if state.stops_at_boundary(
+    analyzer,
+    file_path,
+    symbol_name,
+    source_projects,
+    &ref_packages,
+  ) {
+    debug!(
+      "Signature of '{}' unchanged; not crossing into {:?}",
+      symbol_name, ref_packages
+    );
+    return Ok(());
+  }
+
+  if let Some(files) = state.reached_files.as_deref_mut() {
+    files.insert(reference.file_path.clone());
+  }
+
+  // Mark all matching packages as affected
+  for pkg in &ref_packages {
+    state.affected_packages.insert(pkg.clone());
+
+    // Track cause if generating report
+    if let Some(ref mut causes_map) = state.project_causes {
+      for src_proj in source_projects {
+        causes_map
+          .entry(pkg.clone())
+          .or_default()
+          .push(AffectCause::ImportedSymbol {
+            source_project: src_proj.clone(),
+            symbol: symbol_name.to_string(),
+            via_file: reference.file_path.clone(),
+            source_file: file_path.to_path_buf(),
+            type_only: reference.is_type_only,
+          });
       }
     }
+  }
 
-    // Special case: line=0,column=0 is a sentinel for "entire file affected"
-    // In this case, we need to process all exports from that file
-    if reference.line == 0 && reference.column == 0 {
-      debug!(
-        "File {:?} is conservatively affected (entire-file sentinel). Processing all its exports.",
-        reference.file_path
-      );
+  // Special case: line=0,column=0 is a sentinel for "entire file affected"
+  // In this case, we need to process all exports from that file
+  if reference.line == 0 && reference.column == 0 {
+    debug!(
+      "File {:?} is conservatively affected (entire-file sentinel). Processing all its exports.",
+      reference.file_path
+    );
 
-      // Get all exports from the affected file
-      if let Some(exports) = analyzer.exports.get(&reference.file_path) {
-        for export in exports {
-          // Skip re-exports - those are handled separately
-          if export.re_export_from.is_some() {
-            continue;
-          }
+    // Get all exports from the affected file
+    if let Some(exports) = analyzer.exports.get(&reference.file_path) {
+      for export in exports {
+        // Skip re-exports - those are handled separately
+        if export.re_export_from.is_some() {
+          continue;
+        }
 
-          // Get the local name (what's actually defined in the file)
-          let local_name = export.local_name.as_ref().unwrap_or(&export.exported_name);
+        // Get the local name (what's actually defined in the file)
+        let local_name = export.local_name.as_ref().unwrap_or(&export.exported_name);
 
-          debug!(
-            "Processing exported symbol '{}' from conservatively affected file {:?}",
-            local_name, reference.file_path
-          );
+        debug!(
+          "Processing exported symbol '{}' from conservatively affected file {:?}",
+          local_name, reference.file_path
+        );
 
-          // Recursively process this exported symbol
-          process_changed_symbol(
-            analyzer,
-            reference_finder,
-            &reference.file_path,
-            local_name,
-            project_index,
-            state,
-          )?;
-        }
+        // Recursively process this exported symbol
+        process_changed_symbol(
+          analyzer,
+          reference_finder,
+          &reference.file_path,
+          local_name,
+          project_index,
+          state,
+        )?;
+      }
+    }
+  } else {
+    // Normal case: find the root symbol containing this reference in the other file
+    if let Ok(mut container_symbols) = symbols_at(
+      analyzer,
+      &reference.file_path,
+      reference.line,
+      reference.column,
+      state.member_precision,
+    ) {
+      // A reference from top-level code of a worker entry has no container,
+      // but the worker as a whole is what its spawners load.
+      if container_symbols.is_empty() && analyzer.is_module_url_entry(&reference.file_path) {
+        container_symbols.push(WHOLE_MODULE_SYMBOL.to_string());
+      }
+      state.carry_signature_change(
+        analyzer,
+        file_path,
+        symbol_name,
+        reference,
+        &container_symbols,
+      );
+      for container_symbol in container_symbols {
+        debug!(
+          "Cross-file reference in '{}' at {:?}:{}",
+          container_symbol, reference.file_path, reference.line
+        );
+        // Recursively process the containing symbol in the importing file
+        process_changed_symbol(
+          analyzer,
+          reference_finder,
+          &reference.file_path,
+          &container_symbol,
+          project_index,
+          state,
+        )?;
       }
+    }
+  }
+
+  Ok(())
+}
+
+/// Trace a change to one member of a class (`ApiClient#getUser`), object
+/// literal (`ROUTES.billing`) or enum (`Permission.Read`) in member-precision
+/// mode. `separator` tells which of the two path kinds `owner` is.
+///
+/// Instead of every reference to the owner, only references that can reach
+/// the member propagate: for classes, other members reading it through
+/// `this`, static reads, and reads on instances created with `new`
+/// (see [`WorkspaceAnalyzer::find_class_member_usage`]); for objects and enums,
+/// member access and destructuring
+/// (see [`WorkspaceAnalyzer::find_property_usage`]).
+#[allow(clippy::too_many_arguments)]
+fn process_changed_member(
+  analyzer: &WorkspaceAnalyzer,
+  reference_finder: &ReferenceFinder,
+  file_path: &Path,
+  owner: &str,
+  separator: char,
+  member: &str,
+  project_index: &ProjectIndex,
+  state: &mut AffectedState,
+) -> Result<()> {
+  let symbol_name = format!("{}{}{}", owner, separator, member);
+  debug!("Processing member '{}' in {:?}", symbol_name, file_path);
+
+  let is_class = separator == MEMBER_SEPARATOR;
+  let member_usage = |reference: &Reference| {
+    if is_class {
+      analyzer.find_class_member_usage(reference, owner, member)
     } else {
-      // Normal case: find the root symbol containing this reference in the other file
-      if let Ok(container_symbols) =
-        analyzer.find_node_at_line(&reference.file_path, reference.line, reference.column)
-      {
-        for container_symbol in container_symbols {
-          debug!(
-            "Cross-file reference in '{}' at {:?}:{}",
-            container_symbol, reference.file_path, reference.line
-          );
-          // Recursively process the containing symbol in the importing file
+      analyzer.find_property_usage(reference, owner, member)
+    }
+  };
+  let source_projects = project_index.get_package_names_by_path(file_path);
+
+  // 1. Other members of the same declaration whose value depends on this one
+  let dependents = if is_class {
+    analyzer.find_class_member_dependents(file_path, owner, member)?
+  } else {
+    analyzer.find_enum_member_dependents(file_path, owner, member)?
+  };
+  for dependent in dependents {
+    debug!("Member '{}' is read by '{}'", symbol_name, dependent);
+    process_changed_symbol(
+      analyzer,
+      reference_finder,
+      file_path,
+      &dependent,
+      project_index,
+      state,
+    )?;
+  }
+
+  // 2. Uses of the owner elsewhere in the same file
+  for owner_ref in analyzer.find_local_references(file_path, owner)? {
+    for usage in member_usage(&owner_ref)? {
+      if !state.follows(&usage) {
+        continue;
+      }
+      for container_symbol in symbols_at(analyzer, file_path, usage.line, usage.column, true)? {
+        if container_symbol != symbol_name {
           process_changed_symbol(
             analyzer,
             reference_finder,
-            &reference.file_path,
+            file_path,
             &container_symbol,
             project_index,
             state,
@@ -961,9 +1383,55 @@ fn process_changed_symbol(
     }
   }
 
+  // 3. Consumers of the owner in other files
+  for owner_ref in reference_finder.find_cross_file_references(owner, file_path)? {
+    // The entire-file sentinel has no expression to narrow
+    let usages = if owner_ref.line == 0 && owner_ref.column == 0 {
+      vec![owner_ref]
+    } else {
+      member_usage(&owner_ref)?
+    };
+    for usage in usages {
+      process_cross_file_reference(
+        analyzer,
+        reference_finder,
+        file_path,
+        &symbol_name,
+        &usage,
+        &source_projects,
+        project_index,
+        state,
+      )?;
+    }
+  }
+
   Ok(())
 }
 
+/// Top-level symbols enclosing `line`/`column`, narrowed to a `Class#member`
+/// or `OBJECT.key` path when `member_precision` is on and the position lies
+/// inside a single member of that declaration.
+fn symbols_at(
+  analyzer: &WorkspaceAnalyzer,
+  file_path: &Path,
+  line: usize,
+  column: usize,
+  member_precision: bool,
+) -> Result<Vec<String>> {
+  let symbols = analyzer.find_node_at_line(file_path, line, column)?;
+  if member_precision && symbols.len() == 1 {
+    if let Some(path) = analyzer.find_member_at_line(file_path, line, column)? {
+      let owner = path
+        .split_once([MEMBER_SEPARATOR, PROPERTY_SEPARATOR])
+        .map(|(owner, _)| owner);
+      if owner == Some(symbols[0].as_str()) {
+        return Ok(vec![path]);
+      }
+    }
+  }
+  Ok(symbols)
+}
+
 /// Whether an Nx `implicitDependencies` entry should be treated as a glob
 /// (matched against known project **names**, not paths).
 fn is_implicit_dep_glob(pattern: &str) -> bool {
@@ -1099,6 +1567,7 @@ mod tests {
         .map(str::to_string)
         .collect(),
       targets: vec![],
+      tags: vec![],
     }
   }
 
@@ -0,0 +1,343 @@
+//! Import cycle detection (`domino cycles`).
+//!
+//! The import and re-export indexes built by the [`WorkspaceAnalyzer`] are
+//! turned into a directed graph of files, and its strongly connected
+//! components are the cycles. At file granularity only edges that exist at
+//! runtime count — type-only, dynamic and `new URL()` imports cannot cause
+//! initialization-order problems. At project granularity every static import
+//! counts, since a type-only import still makes one project depend on another.
+//!
+//! With a base revision, the same analysis runs over a temporary worktree of
+//! that revision, and only cycles that are not part of a base cycle are kept,
+//! so a long-standing cycle does not fail every pull request.
+
+use crate::error::Result;
+use crate::git;
+use crate::profiler::Profiler;
+use crate::semantic::WorkspaceAnalyzer;
+use crate::types::{CycleReport, ImportCycle, Project};
+use crate::utils::ProjectIndex;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Directed graph over file paths or project names, ordered so that cycles
+/// and their paths come out the same on every run
+type Graph = BTreeMap<String, BTreeSet<String>>;
+
+/// Find every file and project cycle in the working tree
+pub fn find_cycles(
+  cwd: &Path,
+  projects: Vec<Project>,
+  profiler: Arc<Profiler>,
+) -> Result<CycleReport> {
+  let analyzer = WorkspaceAnalyzer::new(projects.clone(), cwd, profiler)?;
+  Ok(cycles_in(&analyzer, &projects, cwd))
+}
+
+/// Find the cycles in the working tree that are not already present at the
+/// base revision (see [`git::get_base_revision`])
+pub fn find_new_cycles(
+  cwd: &Path,
+  base: &str,
+  head: Option<&str>,
+  projects: Vec<Project>,
+  profiler: Arc<Profiler>,
+) -> Result<CycleReport> {
+  let revision = git::get_base_revision(cwd, base, head)?;
+  let current = find_cycles(cwd, projects.clone(), profiler.clone())?;
+  if current.is_empty() {
+    return Ok(current);
+  }
+
+  let worktree = git::Worktree::add(cwd, &revision)?;
+  let previous = find_cycles(&worktree.path(), projects, profiler)?;
+  debug!(
+    "Base revision {} has {} file and {} project cycles",
+    revision,
+    previous.files.len(),
+    previous.projects.len()
+  );
+
+  Ok(CycleReport {
+    files: new_cycles(current.files, &previous.files),
+    projects: new_cycles(current.projects, &previous.projects),
+  })
+}
+
+/// Cycles of `current` whose members are not all inside one `previous` cycle.
+///
+/// A cycle that merely grew a new member is still new: the members were
+/// already entangled, but the added file or project is now part of it.
+fn new_cycles(current: Vec<ImportCycle>, previous: &[ImportCycle]) -> Vec<ImportCycle> {
+  current
+    .into_iter()
+    .filter(|cycle| {
+      !previous.iter().any(|old| {
+        cycle
+          .members
+          .iter()
+          .all(|member| old.members.contains(member))
+      })
+    })
+    .collect()
+}
+
+fn cycles_in(analyzer: &WorkspaceAnalyzer, projects: &[Project], cwd: &Path) -> CycleReport {
+  let mut runtime = Graph::new();
+  let mut all = Graph::new();
+
+  for ((source_file, _), values) in &analyzer.import_index {
+    for value in values {
+      if value.is_dynamic || value.is_module_url {
+        continue;
+      }
+      let from = value.importing_file.to_string_lossy().into_owned();
+      let to = source_file.to_string_lossy().into_owned();
+      if !value.is_type_only {
+        runtime.entry(from.clone()).or_default().insert(to.clone());
+      }
+      all.entry(from).or_default().insert(to);
+    }
+  }
+  for (source_file, entries) in &analyzer.reexport_index {
+    for (reexporting_file, _) in entries {
+      let from = reexporting_file.to_string_lossy().into_owned();
+      let to = source_file.to_string_lossy().into_owned();
+      runtime.entry(from.clone()).or_default().insert(to.clone());
+      all.entry(from).or_default().insert(to);
+    }
+  }
+
+  // Collapse file edges onto projects, remembering the first file edge seen
+  // for every project edge as an example
+  let index = ProjectIndex::new(projects, cwd);
+  let mut project_graph = Graph::new();
+  let mut examples: BTreeMap<(String, String), (String, String)> = BTreeMap::new();
+  for (from, targets) in &all {
+    let from_projects = index.get_package_names_by_path(Path::new(from));
+    for to in targets {
+      for to_project in index.get_package_names_by_path(Path::new(to)) {
+        for from_project in &from_projects {
+          if *from_project == to_project {
+            continue;
+          }
+          project_graph
+            .entry(from_project.clone())
+            .or_default()
+            .insert(to_project.clone());
+          examples
+            .entry((from_project.clone(), to_project.clone()))
+            .or_insert_with(|| (from.clone(), to.clone()));
+        }
+      }
+    }
+  }
+
+  let files = strongly_connected(&runtime)
+    .into_iter()
+    .map(|members| cycle(&runtime, members))
+    .collect();
+  let projects = strongly_connected(&project_graph)
+    .into_iter()
+    .map(|members| {
+      let mut cycle = cycle(&project_graph, members);
+      cycle.via = cycle
+        .path
+        .windows(2)
+        .filter_map(|hop| examples.get(&(hop[0].clone(), hop[1].clone())).cloned())
+        .collect();
+      cycle
+    })
+    .collect();
+
+  CycleReport { files, projects }
+}
+
+/// Strongly connected components that form a cycle — more than one node, or a
+/// node importing itself — each sorted, in order of their first member.
+///
+/// Tarjan's algorithm, driven by an explicit stack so deep import chains
+/// cannot overflow the call stack.
+fn strongly_connected(graph: &Graph) -> Vec<Vec<String>> {
+  let nodes: Vec<&String> = graph
+    .iter()
+    .flat_map(|(from, targets)| std::iter::once(from).chain(targets))
+    .collect::<BTreeSet<_>>()
+    .into_iter()
+    .collect();
+  let position: BTreeMap<&String, usize> = nodes
+    .iter()
+    .enumerate()
+    .map(|(i, node)| (*node, i))
+    .collect();
+  let edges: Vec<Vec<usize>> = nodes
+    .iter()
+    .map(|node| {
+      graph
+        .get(*node)
+        .map(|targets| targets.iter().map(|target| position[target]).collect())
+        .unwrap_or_default()
+    })
+    .collect();
+
+  let mut order: Vec<Option<usize>> = vec![None; nodes.len()];
+  let mut lowlink = vec![0; nodes.len()];
+  let mut on_stack = vec![false; nodes.len()];
+  let mut stack = Vec::new();
+  let mut next = 0;
+  let mut components = Vec::new();
+
+  for root in 0..nodes.len() {
+    if order[root].is_some() {
+      continue;
+    }
+    let mut work = vec![(root, 0)];
+    while let Some((node, edge)) = work.pop() {
+      if order[node].is_none() {
+        order[node] = Some(next);
+        lowlink[node] = next;
+        next += 1;
+        stack.push(node);
+        on_stack[node] = true;
+      }
+
+      if let Some(&target) = edges[node].get(edge) {
+        work.push((node, edge + 1));
+        match order[target] {
+          None => work.push((target, 0)),
+          Some(target_order) if on_stack[target] => {
+            lowlink[node] = lowlink[node].min(target_order);
+          }
+          Some(_) => {}
+        }
+        continue;
+      }
+
+      if Some(lowlink[node]) == order[node] {
+        let mut component = Vec::new();
+        while let Some(member) = stack.pop() {
+          on_stack[member] = false;
+          component.push(member);
+          if member == node {
+            break;
+          }
+        }
+        if component.len() > 1 || edges[node].contains(&node) {
+          let mut members: Vec<String> = component.iter().map(|&i| nodes[i].clone()).collect();
+          members.sort();
+          components.push(members);
+        }
+      }
+      if let Some(&(parent, _)) = work.last() {
+        lowlink[parent] = lowlink[parent].min(lowlink[node]);
+      }
+    }
+  }
+
+  components.sort();
+  components
+}
+
+/// Describe a component with one shortest path from its first member back to
+/// itself, staying inside the component
+fn cycle(graph: &Graph, members: Vec<String>) -> ImportCycle {
+  let start = &members[0];
+  let inside: BTreeSet<&String> = members.iter().collect();
+
+  let mut previous: BTreeMap<&String, &String> = BTreeMap::new();
+  let mut queue = VecDeque::from([start]);
+  let mut closing = None;
+  'search: while let Some(node) = queue.pop_front() {
+    for target in graph.get(node).into_iter().flatten() {
+      if target == start {
+        closing = Some(node);
+        break 'search;
+      }
+      if inside.contains(target) && !previous.contains_key(target) {
+        previous.insert(target, node);
+        queue.push_back(target);
+      }
+    }
+  }
+
+  // Every member of a strongly connected component reaches every other, so
+  // the search always finds its way back to the start
+  let mut path = vec![start.clone()];
+  let mut node = closing.unwrap_or(start);
+  while node != start {
+    path.push(node.clone());
+    node = previous[node];
+  }
+  path.push(start.clone());
+  path.reverse();
+
+  ImportCycle {
+    members,
+    path,
+    via: Vec::new(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn graph(edges: &[(&str, &str)]) -> Graph {
+    let mut graph = Graph::new();
+    for (from, to) in edges {
+      graph
+        .entry(from.to_string())
+        .or_default()
+        .insert(to.to_string());
+    }
+    graph
+  }
+
+  #[test]
+  fn test_strongly_connected_components() {
+    let graph = graph(&[
+      ("a", "b"),
+      ("b", "c"),
+      ("c", "a"),
+      ("c", "d"),
+      ("d", "e"),
+      ("e", "d"),
+      ("f", "f"),
+      ("g", "a"),
+    ]);
+    assert_eq!(
+      strongly_connected(&graph),
+      vec![
+        vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        vec!["d".to_string(), "e".to_string()],
+        vec!["f".to_string()],
+      ]
+    );
+  }
+
+  #[test]
+  fn test_cycle_path_is_shortest_loop() {
+    let graph = graph(&[("a", "b"), ("b", "c"), ("c", "a"), ("b", "a"), ("f", "f")]);
+    let members = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    assert_eq!(cycle(&graph, members).path, ["a", "b", "a"]);
+    assert_eq!(cycle(&graph, vec!["f".to_string()]).path, ["f", "f"]);
+  }
+
+  #[test]
+  fn test_new_cycles_ignores_cycles_inside_base_cycles() {
+    let with = |members: &[&str]| ImportCycle {
+      members: members.iter().map(|m| m.to_string()).collect(),
+      path: Vec::new(),
+      via: Vec::new(),
+    };
+    let previous = [with(&["a", "b", "c"])];
+    let current = vec![with(&["a", "b"]), with(&["a", "b", "d"]), with(&["x", "y"])];
+    let members: Vec<Vec<String>> = new_cycles(current, &previous)
+      .into_iter()
+      .map(|cycle| cycle.members)
+      .collect();
+    assert_eq!(members, [vec!["a", "b", "d"], vec!["x", "y"]]);
+  }
+}
@@ -1,7 +1,7 @@
 use crate::error::{DominoError, Result};
 use crate::types::ChangedFile;
 use regex::Regex;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::LazyLock;
 use tracing::{debug, warn};
@@ -180,20 +180,100 @@ pub fn get_changed_files(
 ) -> Result<(Vec<ChangedFile>, String)> {
   debug!("Getting diff for base: {}", base);
 
-  let (diff_base, merge_base) = if head.is_some() {
+  let merge_base = get_base_revision(repo_path, base, head)?;
+  let diff = get_diff(repo_path, &merge_base, head)?;
+  let files = parse_diff(&diff)?;
+
+  Ok((files, merge_base))
+}
+
+/// Resolve the revision that `head` is compared against.
+///
+/// With an explicit `head` this is `base` itself (commit-to-commit); against
+/// the working tree it is `merge-base(base, HEAD)`, so commits that landed on
+/// `base` after the branch point are not reported as changes.
+pub fn get_base_revision(repo_path: &Path, base: &str, head: Option<&str>) -> Result<String> {
+  if head.is_some() {
     debug!("Explicit head provided, using base ref directly");
-    let resolved = resolve_ref(repo_path, base)?;
-    (resolved.clone(), resolved)
+    resolve_ref(repo_path, base)
   } else {
     let mb = get_merge_base(repo_path, base, "HEAD")?;
     debug!("Merge base: {}", mb);
-    (mb.clone(), mb)
-  };
+    Ok(mb)
+  }
+}
 
-  let diff = get_diff(repo_path, &diff_base, head)?;
-  let files = parse_diff(&diff)?;
+/// A detached checkout of a revision in a temporary directory, removed again
+/// when dropped.
+///
+/// Used to run the analyzer over the base revision's full tree rather than
+/// single files read through `git show`.
+pub struct Worktree {
+  repo_path: PathBuf,
+  root: PathBuf,
+  prefix: PathBuf,
+}
 
-  Ok((files, merge_base))
+impl Worktree {
+  /// Check out `revision` into a fresh temporary worktree of the repository
+  /// containing `repo_path`
+  pub fn add(repo_path: &Path, revision: &str) -> Result<Self> {
+    let prefix = run_git(repo_path, &["rev-parse", "--show-prefix"])?;
+    let short = &revision[..revision.len().min(12)];
+    let root = std::env::temp_dir().join(format!("domino-{}-{}", std::process::id(), short));
+
+    let root_arg = root.to_string_lossy();
+    run_git(
+      repo_path,
+      &[
+        "worktree", "add", "--detach", "--quiet", &root_arg, revision,
+      ],
+    )?;
+    debug!("Checked out {} into {}", revision, root.display());
+
+    Ok(Self {
+      repo_path: repo_path.to_path_buf(),
+      root,
+      prefix: PathBuf::from(prefix),
+    })
+  }
+
+  /// The checkout's counterpart of the `repo_path` it was created from
+  pub fn path(&self) -> PathBuf {
+    self.root.join(&self.prefix)
+  }
+}
+
+impl Drop for Worktree {
+  fn drop(&mut self) {
+    let root_arg = self.root.to_string_lossy();
+    if let Err(e) = run_git(
+      &self.repo_path,
+      &["worktree", "remove", "--force", &root_arg],
+    ) {
+      warn!("Failed to remove worktree {}: {}", self.root.display(), e);
+    }
+  }
+}
+
+/// Run a git command and return its trimmed stdout
+fn run_git(repo_path: &Path, args: &[&str]) -> Result<String> {
+  let output = Command::new("git")
+    .args(args)
+    .current_dir(repo_path)
+    .output()
+    .map_err(|e| DominoError::Other(format!("Failed to execute git {}: {}", args[0], e)))?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(DominoError::Other(format!(
+      "Git {} failed: {}",
+      args[0],
+      stderr.trim()
+    )));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
 /// Parse git diff output into ChangedFile structs
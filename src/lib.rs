@@ -1,16 +1,27 @@
 #![deny(clippy::all)]
 
+pub mod affected_tests;
+pub mod api_diff;
+pub mod boundaries;
+pub mod changeset;
+pub mod chunks;
 pub mod cli;
 pub mod core;
+pub mod cycles;
 pub mod error;
 pub mod git;
 pub mod lockfile;
 pub mod named_inputs;
 pub mod profiler;
+pub mod project_graph;
 pub mod report;
+pub mod routes;
 pub mod semantic;
+pub mod stories;
+pub mod test_files;
 pub mod tsconfig;
 pub mod types;
+pub mod unused_exports;
 pub mod utils;
 pub mod workspace;
 
@@ -36,6 +47,8 @@ mod napi_bindings {
     pub ts_config: Option<String>,
     pub implicit_dependencies: Vec<String>,
     pub targets: Vec<String>,
+    /// Project tags (project.json `tags` or package.json `domino.tags`)
+    pub tags: Option<Vec<String>>,
   }
 
   impl From<Project> for NapiProject {
@@ -47,6 +60,7 @@ mod napi_bindings {
         ts_config: project.ts_config.map(|p| p.to_string_lossy().to_string()),
         implicit_dependencies: project.implicit_dependencies,
         targets: project.targets,
+        tags: Some(project.tags),
       }
     }
   }
@@ -66,6 +80,7 @@ mod napi_bindings {
         ts_config: project.ts_config.map(PathBuf::from),
         implicit_dependencies: project.implicit_dependencies,
         targets: project.targets,
+        tags: project.tags.unwrap_or_default(),
       }
     }
   }
@@ -80,6 +95,12 @@ mod napi_bindings {
     pub enable_profiling: Option<bool>,
     /// Lockfile change detection strategy: "none", "direct", "full" (default: "direct")
     pub lockfile_strategy: Option<String>,
+    /// Trace changes to individual class, object literal and enum members (default: false)
+    pub member_precision: Option<bool>,
+    /// Which dependency edges propagate changes: "all", "runtime" (default: "all")
+    pub propagation: Option<String>,
+    /// Whether implementation-only changes cross project boundaries: "full", "signature" (default: "full")
+    pub boundary_mode: Option<String>,
   }
 
   #[napi(object)]
@@ -102,12 +123,29 @@ mod napi_bindings {
       .transpose()?
       .unwrap_or_default();
 
+    let propagation = options
+      .propagation
+      .as_deref()
+      .map(|s| s.parse::<Propagation>().map_err(Error::from_reason))
+      .transpose()?
+      .unwrap_or_default();
+
+    let boundary_mode = options
+      .boundary_mode
+      .as_deref()
+      .map(|s| s.parse::<BoundaryMode>().map_err(Error::from_reason))
+      .transpose()?
+      .unwrap_or_default();
+
     let config = TrueAffectedConfig {
       cwd,
       base: options.base,
       head: options.head,
       projects,
       lockfile_strategy,
+      member_precision: options.member_precision.unwrap_or(false),
+      propagation,
+      boundary_mode,
     };
 
     let result =
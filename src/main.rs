@@ -1,14 +1,25 @@
+mod affected_tests;
+mod api_diff;
+mod boundaries;
+mod changeset;
+mod chunks;
 mod cli;
 mod core;
+mod cycles;
 mod error;
 mod git;
 mod lockfile;
 mod named_inputs;
 mod profiler;
+mod project_graph;
 mod report;
+mod routes;
 mod semantic;
+mod stories;
+mod test_files;
 mod tsconfig;
 mod types;
+mod unused_exports;
 mod utils;
 mod workspace;
 
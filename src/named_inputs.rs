@@ -1,9 +1,10 @@
-use crate::types::{ChangedFile, GlobalTrigger, Project};
+use crate::types::{ChangedFile, GlobalTrigger, Project, ProjectGraph, ProjectTarget};
+use crate::utils::{ProjectIndex, MATCH_OPTIONS};
 use glob::Pattern;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
 /// A compiled global-invalidation pattern that retains the name of the
@@ -25,12 +26,42 @@ pub struct ResolvedNamedInputs {
   /// Pre-compiled negation glob patterns for project-root files to exclude
   /// e.g., "**/*.figma.tsx"
   pub negation_patterns: Vec<Pattern>,
+  /// Workspace-root files excluded from `global_patterns`
+  /// e.g., "docs/**" from `!{workspaceRoot}/docs/**`
+  pub global_negations: Vec<GlobalNegation>,
+}
+
+/// A `!{workspaceRoot}/…` entry of a namedInput. Like Nx, it only excludes
+/// files from the global patterns of the input that declares it and of the
+/// inputs that one references.
+#[derive(Debug, Clone)]
+pub struct GlobalNegation {
+  /// namedInputs whose global patterns the negation applies to
+  pub scope: HashSet<String>,
+  pub pattern: Pattern,
 }
 
 #[derive(Debug, Deserialize)]
 struct NxJson {
   #[serde(default, rename = "namedInputs")]
   named_inputs: HashMap<String, Vec<serde_json::Value>>,
+  #[serde(default, rename = "targetDefaults")]
+  target_defaults: HashMap<String, NxTargetConfig>,
+}
+
+/// The parts of project.json that define inputs
+#[derive(Debug, Default, Deserialize)]
+struct NxProjectInputsJson {
+  #[serde(default, rename = "namedInputs")]
+  named_inputs: HashMap<String, Vec<serde_json::Value>>,
+  #[serde(default)]
+  targets: HashMap<String, NxTargetConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NxTargetConfig {
+  inputs: Option<Vec<serde_json::Value>>,
+  options: Option<serde_json::Value>,
 }
 
 /// Parse and resolve namedInputs from nx.json.
@@ -69,6 +100,7 @@ pub fn resolve_from_nx_json(cwd: &Path) -> Option<ResolvedNamedInputs> {
 
   let mut global_patterns: Vec<GlobalPattern> = Vec::new();
   let mut negation_patterns = Vec::new();
+  let mut global_negations = Vec::new();
 
   for (origin, pattern_str) in &resolved_patterns {
     if let Some(negated) = pattern_str.strip_prefix('!') {
@@ -84,10 +116,21 @@ pub fn resolve_from_nx_json(cwd: &Path) -> Option<ResolvedNamedInputs> {
           }
         }
       } else if let Some(suffix) = negated.strip_prefix("{workspaceRoot}/") {
-        debug!(
-          "Negation pattern (workspace-root): !{} — skipping (not yet supported)",
-          suffix
-        );
+        match Pattern::new(suffix) {
+          Ok(pat) => {
+            debug!(
+              "Negation pattern (workspace-root) from '{}': !{}",
+              origin, suffix
+            );
+            global_negations.push(GlobalNegation {
+              scope: referenced_inputs(origin, &nx_json.named_inputs),
+              pattern: pat,
+            });
+          }
+          Err(e) => {
+            warn!("Invalid negation glob pattern '{}': {}", suffix, e);
+          }
+        }
       }
     } else if let Some(suffix) = pattern_str.strip_prefix("{workspaceRoot}/") {
       // Global workspace-root pattern
@@ -122,6 +165,7 @@ pub fn resolve_from_nx_json(cwd: &Path) -> Option<ResolvedNamedInputs> {
   Some(ResolvedNamedInputs {
     global_patterns,
     negation_patterns,
+    global_negations,
   })
 }
 
@@ -211,6 +255,7 @@ pub fn resolve_from_turbo_json(cwd: &Path) -> Option<ResolvedNamedInputs> {
   Some(ResolvedNamedInputs {
     global_patterns,
     negation_patterns: Vec::new(),
+    global_negations: Vec::new(),
   })
 }
 
@@ -284,6 +329,30 @@ fn resolve_named_input(
   }
 }
 
+/// `name` and every namedInput it references, transitively
+fn referenced_inputs(
+  name: &str,
+  all_inputs: &HashMap<String, Vec<serde_json::Value>>,
+) -> HashSet<String> {
+  let mut inputs = HashSet::new();
+  let mut stack = vec![name.to_string()];
+  while let Some(name) = stack.pop() {
+    if !inputs.insert(name.clone()) {
+      continue;
+    }
+    stack.extend(
+      all_inputs
+        .get(&name)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.as_str())
+        .filter(|entry| !entry.starts_with('{') && !entry.starts_with('!'))
+        .map(str::to_string),
+    );
+  }
+  inputs
+}
+
 impl ResolvedNamedInputs {
   /// Check if a changed file matches any global invalidation pattern.
   /// `file_path` should be relative to workspace root. Returns the matching
@@ -298,15 +367,27 @@ impl ResolvedNamedInputs {
     };
 
     for gp in &self.global_patterns {
-      if gp.pattern.matches_with(path_str, opts) {
+      if !gp.pattern.matches_with(path_str, opts) {
+        continue;
+      }
+      if let Some(negation) = self.global_negations.iter().find(|negation| {
+        negation.scope.contains(&gp.named_input) && negation.pattern.matches_with(path_str, opts)
+      }) {
         debug!(
-          "File '{}' matches global pattern '{}' from namedInput '{}'",
+          "File '{}' excluded from namedInput '{}' by '!{{workspaceRoot}}/{}'",
           path_str,
-          gp.pattern.as_str(),
-          gp.named_input
+          gp.named_input,
+          negation.pattern.as_str()
         );
-        return Some(gp);
+        continue;
       }
+      debug!(
+        "File '{}' matches global pattern '{}' from namedInput '{}'",
+        path_str,
+        gp.pattern.as_str(),
+        gp.named_input
+      );
+      return Some(gp);
     }
     None
   }
@@ -420,6 +501,430 @@ pub fn filter_negated_files(
   filtered
 }
 
+/// Inputs Nx uses for a target whose project.json and `targetDefaults` define
+/// none
+const DEFAULT_TARGET_INPUTS: &[&str] = &["default", "^default"];
+
+/// The resolved inputs of one target (or one named input) of a project
+#[derive(Debug, Default)]
+pub struct TargetInputs {
+  /// Workspace-relative globs of the files the target reads
+  pub files: Vec<Pattern>,
+  /// Workspace-relative globs removed from `files` by `!` entries
+  pub excluded: Vec<Pattern>,
+  /// Named inputs read from every dependency project (`^production`)
+  pub dependency_inputs: Vec<String>,
+  /// npm packages listed under `externalDependencies`
+  pub external_dependencies: Vec<String>,
+  /// `runtime` commands whose output is part of the hash
+  pub runtime: Vec<String>,
+  /// `env` variables whose value is part of the hash
+  pub env: Vec<String>,
+}
+
+impl TargetInputs {
+  /// Whether the workspace-relative `file_path` is one of the input files
+  pub fn matches(&self, file_path: &Path) -> bool {
+    self
+      .files
+      .iter()
+      .any(|pat| pat.matches_path_with(file_path, MATCH_OPTIONS))
+      && !self
+        .excluded
+        .iter()
+        .any(|pat| pat.matches_path_with(file_path, MATCH_OPTIONS))
+  }
+}
+
+/// Input configuration of one project from its project.json
+#[derive(Debug, Default)]
+struct ProjectInputs {
+  root: PathBuf,
+  named_inputs: HashMap<String, Vec<serde_json::Value>>,
+  target_inputs: HashMap<String, Vec<serde_json::Value>>,
+  /// `main` and `browser` options of the `build` target
+  build_entries: Vec<PathBuf>,
+}
+
+/// Evaluator for Nx task inputs across the workspace.
+///
+/// Follows Nx's resolution rules:
+/// - a target's inputs come from its project.json `inputs`, else from the
+///   `targetDefaults` entry for its name (or a glob key matching it), else
+///   `["default", "^default"]`
+/// - project.json `namedInputs` override nx.json ones of the same name, and an
+///   undefined `default` means `{projectRoot}/**/*`
+/// - `^name`, or `{ "input": name, "dependencies": true }`, reads the named
+///   input of every dependency project
+/// - `!` entries remove files from the set, for `{projectRoot}` and
+///   `{workspaceRoot}` patterns alike
+///
+/// `externalDependencies`, `runtime` and `env` entries are collected but can't
+/// be evaluated against a git diff; `dependentTasksOutputFiles` is ignored.
+#[derive(Debug, Default)]
+pub struct NxInputs {
+  named_inputs: HashMap<String, Vec<serde_json::Value>>,
+  target_defaults: Vec<(String, Vec<serde_json::Value>)>,
+  projects: HashMap<String, ProjectInputs>,
+}
+
+impl NxInputs {
+  /// Read nx.json and every project's project.json. Returns None outside an
+  /// Nx workspace.
+  pub fn load(cwd: &Path, projects: &[Project]) -> Option<Self> {
+    if !crate::workspace::nx::is_nx_workspace(cwd) {
+      return None;
+    }
+    let nx_json: Option<NxJson> =
+      fs::read_to_string(cwd.join("nx.json"))
+        .ok()
+        .and_then(|content| match serde_json::from_str(&content) {
+          Ok(nx_json) => Some(nx_json),
+          Err(e) => {
+            warn!("Failed to parse nx.json inputs: {}", e);
+            None
+          }
+        });
+    let (named_inputs, target_defaults) = nx_json
+      .map(|nx_json| (nx_json.named_inputs, nx_json.target_defaults))
+      .unwrap_or_default();
+
+    // Exact target names win over glob keys, so they are tried first
+    let mut target_defaults: Vec<(String, Vec<serde_json::Value>)> = target_defaults
+      .into_iter()
+      .filter_map(|(key, config)| Some((key, config.inputs?)))
+      .collect();
+    target_defaults.sort_by_key(|(key, _)| (key.contains('*'), key.clone()));
+
+    let projects = projects
+      .iter()
+      .map(|project| {
+        let project_json: NxProjectInputsJson =
+          fs::read_to_string(cwd.join(&project.root).join("project.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let build_entries = project_json
+          .targets
+          .get("build")
+          .and_then(|build| build.options.as_ref())
+          .map(|options| {
+            ["main", "browser"]
+              .iter()
+              .filter_map(|option| options.get(option)?.as_str())
+              .map(|entry| option_path(entry, project))
+              .collect()
+          })
+          .unwrap_or_default();
+        let inputs = ProjectInputs {
+          root: project.root.clone(),
+          build_entries,
+          named_inputs: project_json.named_inputs,
+          target_inputs: project_json
+            .targets
+            .into_iter()
+            .filter_map(|(target, config)| Some((target, config.inputs?)))
+            .collect(),
+        };
+        (project.name.clone(), inputs)
+      })
+      .collect();
+
+    Some(Self {
+      named_inputs,
+      target_defaults,
+      projects,
+    })
+  }
+
+  /// Bundle entry files of `project`: the `main` and `browser` options of
+  /// its `build` target, relative to the workspace root
+  pub fn build_entries(&self, project: &str) -> &[PathBuf] {
+    self
+      .projects
+      .get(project)
+      .map_or(&[], |inputs| inputs.build_entries.as_slice())
+  }
+
+  /// The inputs of `target` in `project`
+  pub fn target_inputs(&self, project: &str, target: &str) -> TargetInputs {
+    let defaults: Vec<serde_json::Value>;
+    let entries = match self
+      .projects
+      .get(project)
+      .and_then(|inputs| inputs.target_inputs.get(target))
+    {
+      Some(entries) => entries,
+      None => match self
+        .target_defaults
+        .iter()
+        .find(|(key, _)| key == target || Pattern::new(key).is_ok_and(|pat| pat.matches(target)))
+      {
+        Some((_, entries)) => entries,
+        None => {
+          defaults = DEFAULT_TARGET_INPUTS
+            .iter()
+            .map(|input| serde_json::Value::from(*input))
+            .collect();
+          &defaults
+        }
+      },
+    };
+
+    let mut inputs = TargetInputs::default();
+    self.resolve_entries(project, entries, &mut inputs, &mut HashSet::new());
+    inputs
+  }
+
+  /// The files and other inputs `name` stands for in `project`
+  pub fn named_input(&self, project: &str, name: &str) -> TargetInputs {
+    let mut inputs = TargetInputs::default();
+    self.resolve_named(project, name, &mut inputs, &mut HashSet::new());
+    inputs
+  }
+
+  fn resolve_named(
+    &self,
+    project: &str,
+    name: &str,
+    inputs: &mut TargetInputs,
+    visited: &mut HashSet<String>,
+  ) {
+    if !visited.insert(name.to_string()) {
+      debug!("Circular reference detected in namedInputs: {}", name);
+      return;
+    }
+    let entries = self
+      .projects
+      .get(project)
+      .and_then(|project_inputs| project_inputs.named_inputs.get(name))
+      .or_else(|| self.named_inputs.get(name));
+    match entries {
+      Some(entries) => self.resolve_entries(project, entries, inputs, visited),
+      None if name == "default" => self.add_fileset(project, "{projectRoot}/**/*", inputs),
+      None => debug!("Named input '{}' not found for project {}", name, project),
+    }
+  }
+
+  fn resolve_entries(
+    &self,
+    project: &str,
+    entries: &[serde_json::Value],
+    inputs: &mut TargetInputs,
+    visited: &mut HashSet<String>,
+  ) {
+    for entry in entries {
+      match entry {
+        serde_json::Value::String(s) => {
+          if let Some(dependency_input) = s.strip_prefix('^') {
+            inputs.dependency_inputs.push(dependency_input.to_string());
+          } else if s.starts_with('{') || s.starts_with('!') {
+            self.add_fileset(project, s, inputs);
+          } else {
+            self.resolve_named(project, s, inputs, visited);
+          }
+        }
+        serde_json::Value::Object(object) => {
+          let string = |key: &str| object.get(key).and_then(|value| value.as_str());
+          if let Some(fileset) = string("fileset") {
+            self.add_fileset(project, fileset, inputs);
+          } else if let Some(name) = string("input") {
+            let from_dependencies = string("projects") == Some("dependencies")
+              || object.get("dependencies").and_then(|value| value.as_bool()) == Some(true);
+            if from_dependencies {
+              inputs.dependency_inputs.push(name.to_string());
+            } else if object
+              .get("projects")
+              .is_none_or(|projects| *projects == "self")
+            {
+              self.resolve_named(project, name, inputs, visited);
+            } else {
+              debug!("Skipping input '{}' of other projects in {}", name, project);
+            }
+          } else if let Some(packages) = object
+            .get("externalDependencies")
+            .and_then(|value| value.as_array())
+          {
+            inputs.external_dependencies.extend(
+              packages
+                .iter()
+                .filter_map(|package| package.as_str().map(str::to_string)),
+            );
+          } else if let Some(command) = string("runtime") {
+            inputs.runtime.push(command.to_string());
+          } else if let Some(variable) = string("env") {
+            inputs.env.push(variable.to_string());
+          } else {
+            debug!("Skipping unsupported input entry in {}: {}", project, entry);
+          }
+        }
+        _ => debug!("Skipping unexpected input entry in {}: {}", project, entry),
+      }
+    }
+  }
+
+  /// Compile a `{projectRoot}`/`{workspaceRoot}` fileset into a
+  /// workspace-relative glob. Bare patterns are relative to the project root.
+  fn add_fileset(&self, project: &str, fileset: &str, inputs: &mut TargetInputs) {
+    let (negated, fileset) = match fileset.strip_prefix('!') {
+      Some(rest) => (true, rest),
+      None => (false, fileset),
+    };
+    let project_root = self
+      .projects
+      .get(project)
+      .map(|project_inputs| project_inputs.root.to_string_lossy().into_owned())
+      .unwrap_or_default();
+    let relative_to_project = |rest: &str| {
+      if project_root.is_empty() || project_root == "." {
+        rest.to_string()
+      } else {
+        format!("{}/{}", project_root, rest)
+      }
+    };
+    let pattern = if let Some(rest) = fileset.strip_prefix("{workspaceRoot}/") {
+      rest.to_string()
+    } else if let Some(rest) = fileset.strip_prefix("{projectRoot}/") {
+      relative_to_project(rest)
+    } else {
+      relative_to_project(fileset.trim_start_matches("./"))
+    };
+
+    match Pattern::new(&pattern) {
+      Ok(pat) if negated => inputs.excluded.push(pat),
+      Ok(pat) => inputs.files.push(pat),
+      Err(e) => warn!("Invalid input glob pattern '{}': {}", fileset, e),
+    }
+  }
+}
+
+/// An executor option naming a workspace-relative path, with Nx's
+/// `{workspaceRoot}`, `{projectRoot}` and `{projectName}` tokens replaced
+fn option_path(value: &str, project: &Project) -> PathBuf {
+  let root = project.root.to_string_lossy();
+  let value = value
+    .replace("{workspaceRoot}/", "")
+    .replace("{projectName}", &project.name);
+  let value = if root.is_empty() || root == "." {
+    value.replace("{projectRoot}/", "")
+  } else {
+    value.replace("{projectRoot}", &root)
+  };
+  PathBuf::from(value.trim_start_matches("./"))
+}
+
+/// Keep the `project:target` pairs whose inputs include a changed file.
+///
+/// A file owned by the target's project counts when it matches the target's
+/// own file inputs; a file owned by one of the project's dependencies in
+/// `graph`, direct or transitive, counts when it matches a named input the
+/// target reads from dependencies (`^production`), which leaves a target
+/// whose project is affected only through a dependency's spec file out.
+/// Workspace files outside every project count when they match a
+/// `{workspaceRoot}` input. Lockfile changes keep every target, since the
+/// external packages a target reads are not evaluated.
+pub fn filter_targets_by_inputs(
+  inputs: &NxInputs,
+  targets: Vec<ProjectTarget>,
+  changed_files: &[ChangedFile],
+  index: &ProjectIndex,
+  graph: &ProjectGraph,
+  lockfile_changed: bool,
+) -> Vec<ProjectTarget> {
+  let owners: Vec<(&Path, Vec<String>)> = changed_files
+    .iter()
+    .map(|file| {
+      (
+        file.file_path.as_path(),
+        index.get_owning_packages_by_path(&file.file_path),
+      )
+    })
+    .collect();
+  let mut dependency_inputs: HashMap<(String, String), TargetInputs> = HashMap::new();
+  let mut dependencies: HashMap<String, HashSet<String>> = HashMap::new();
+
+  targets
+    .into_iter()
+    .filter(|pair| {
+      if lockfile_changed {
+        return true;
+      }
+      let target_inputs = inputs.target_inputs(&pair.project, &pair.target);
+      let project_dependencies = dependencies
+        .entry(pair.project.clone())
+        .or_insert_with(|| transitive_dependencies(graph, &pair.project));
+      let affected = owners.iter().any(|(file, file_owners)| {
+        if file_owners.is_empty() || file_owners.contains(&pair.project) {
+          return target_inputs.matches(file);
+        }
+        file_owners
+          .iter()
+          .filter(|owner| project_dependencies.contains(*owner))
+          .flat_map(|owner| {
+            target_inputs
+              .dependency_inputs
+              .iter()
+              .map(move |name| (owner, name))
+          })
+          .any(|(owner, name)| {
+            dependency_inputs
+              .entry((owner.clone(), name.clone()))
+              .or_insert_with(|| inputs.named_input(owner, name))
+              .matches(file)
+          })
+      });
+      if !affected {
+        debug!("No changed file is an input of {}", pair);
+      }
+      affected
+    })
+    .collect()
+}
+
+/// The `project:target` pairs whose own inputs include a changed workspace
+/// file outside every project, such as a `{workspaceRoot}/jest.preset.js`
+/// that `production` lists but `default` does not. Global invalidation only
+/// follows `default`, so these targets can belong to unaffected projects.
+pub fn targets_reading_workspace_files(
+  inputs: &NxInputs,
+  targets: Vec<ProjectTarget>,
+  changed_files: &[ChangedFile],
+  index: &ProjectIndex,
+) -> Vec<ProjectTarget> {
+  let workspace_files: Vec<&Path> = changed_files
+    .iter()
+    .map(|file| file.file_path.as_path())
+    .filter(|file| index.get_owning_packages_by_path(file).is_empty())
+    .collect();
+  if workspace_files.is_empty() {
+    return Vec::new();
+  }
+  targets
+    .into_iter()
+    .filter(|pair| {
+      let target_inputs = inputs.target_inputs(&pair.project, &pair.target);
+      workspace_files
+        .iter()
+        .any(|file| target_inputs.matches(file))
+    })
+    .collect()
+}
+
+/// Every project `project` depends on in `graph`, directly or transitively
+fn transitive_dependencies(graph: &ProjectGraph, project: &str) -> HashSet<String> {
+  let mut seen = HashSet::new();
+  let mut queue = vec![project];
+  while let Some(current) = queue.pop() {
+    for dependency in graph.dependencies.get(current).into_iter().flatten() {
+      if seen.insert(dependency.target.clone()) {
+        queue.push(&dependency.target);
+      }
+    }
+  }
+  seen.remove(project);
+  seen
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -869,4 +1374,272 @@ mod tests {
     let dir = tempfile::TempDir::new().unwrap();
     assert!(resolve_global_inputs(dir.path()).is_none());
   }
+
+  #[test]
+  fn test_workspace_root_negations_exclude_global_patterns() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+
+    write_nx_json(
+      root,
+      r#"{
+        "namedInputs": {
+          "default": ["{projectRoot}/**/*", "sharedGlobals"],
+          "sharedGlobals": ["{workspaceRoot}/tools/**/*", "!{workspaceRoot}/tools/**/*.md"]
+        }
+      }"#,
+    );
+
+    let resolved = resolve_from_nx_json(root).unwrap();
+    assert!(resolved
+      .matches_global_pattern(&PathBuf::from("tools/build.js"))
+      .is_some());
+    assert!(resolved
+      .matches_global_pattern(&PathBuf::from("tools/README.md"))
+      .is_none());
+  }
+
+  #[test]
+  fn test_workspace_root_negations_stay_in_their_named_input() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+
+    write_nx_json(
+      root,
+      r#"{
+        "namedInputs": {
+          "default": ["{projectRoot}/**/*", "sharedGlobals", "docsInputs"],
+          "sharedGlobals": ["{workspaceRoot}/docs/**/*"],
+          "docsInputs": ["{workspaceRoot}/guides/**/*", "!{workspaceRoot}/**/*.md"]
+        }
+      }"#,
+    );
+
+    let resolved = resolve_from_nx_json(root).unwrap();
+    // docsInputs' negation does not reach sharedGlobals' patterns
+    let trigger = resolved
+      .matches_global_pattern(&PathBuf::from("docs/README.md"))
+      .unwrap();
+    assert_eq!(trigger.named_input, "sharedGlobals");
+    assert!(resolved
+      .matches_global_pattern(&PathBuf::from("guides/intro.md"))
+      .is_none());
+    assert!(resolved
+      .matches_global_pattern(&PathBuf::from("guides/setup.sh"))
+      .is_some());
+  }
+
+  fn nx_inputs(root: &Path, projects: &[(&str, &str, &str)]) -> NxInputs {
+    let projects: Vec<Project> = projects
+      .iter()
+      .map(|(name, project_root, project_json)| {
+        fs::create_dir_all(root.join(project_root)).unwrap();
+        fs::write(root.join(project_root).join("project.json"), project_json).unwrap();
+        Project {
+          name: name.to_string(),
+          root: PathBuf::from(project_root),
+          source_root: PathBuf::from(project_root).join("src"),
+          ts_config: None,
+          implicit_dependencies: vec![],
+          targets: vec![],
+          tags: vec![],
+        }
+      })
+      .collect();
+    NxInputs::load(root, &projects).unwrap()
+  }
+
+  #[test]
+  fn test_build_entries() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    write_nx_json(root, "{}");
+    let nx = nx_inputs(
+      root,
+      &[
+        (
+          "web",
+          "apps/web",
+          r#"{ "targets": { "build": { "options": {
+            "main": "{projectRoot}/src/main.ts",
+            "browser": "./apps/{projectName}/src/browser.ts",
+            "tsConfig": "{workspaceRoot}/apps/web/tsconfig.app.json"
+          } } } }"#,
+        ),
+        ("ui", "libs/ui", r#"{ "targets": { "test": {} } }"#),
+      ],
+    );
+    assert_eq!(
+      nx.build_entries("web"),
+      [
+        PathBuf::from("apps/web/src/main.ts"),
+        PathBuf::from("apps/web/src/browser.ts"),
+      ]
+    );
+    assert!(nx.build_entries("ui").is_empty());
+    assert!(nx.build_entries("missing").is_empty());
+  }
+
+  #[test]
+  fn test_target_inputs_resolution() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    write_nx_json(
+      root,
+      r#"{
+        "namedInputs": {
+          "default": ["{projectRoot}/**/*", "sharedGlobals"],
+          "sharedGlobals": ["{workspaceRoot}/babel.config.json"],
+          "production": ["default", "!{projectRoot}/**/*.spec.ts"]
+        },
+        "targetDefaults": {
+          "build": { "inputs": ["production", "^production", { "env": "NODE_ENV" }] },
+          "e2e-*": { "inputs": ["default", { "externalDependencies": ["playwright"] }] }
+        }
+      }"#,
+    );
+    let inputs = nx_inputs(
+      root,
+      &[
+        ("ui", "libs/ui", r#"{ "name": "ui" }"#),
+        (
+          "web",
+          "apps/web",
+          r#"{
+            "name": "web",
+            "namedInputs": { "production": ["{projectRoot}/src/**/*", "!{projectRoot}/src/**/*.test.ts"] },
+            "targets": { "lint": { "inputs": [{ "fileset": "{projectRoot}/**/*.ts" }, { "runtime": "node -v" }] } }
+          }"#,
+        ),
+      ],
+    );
+
+    let build = inputs.target_inputs("ui", "build");
+    assert!(build.matches(Path::new("libs/ui/src/button.ts")));
+    assert!(build.matches(Path::new("babel.config.json")));
+    assert!(!build.matches(Path::new("libs/ui/src/button.spec.ts")));
+    assert_eq!(build.dependency_inputs, ["production"]);
+    assert_eq!(build.env, ["NODE_ENV"]);
+
+    // Targets without configured inputs read `default` and `^default`
+    let test = inputs.target_inputs("ui", "test");
+    assert!(test.matches(Path::new("libs/ui/src/button.spec.ts")));
+    assert_eq!(test.dependency_inputs, ["default"]);
+
+    let e2e = inputs.target_inputs("ui", "e2e-ci");
+    assert_eq!(e2e.external_dependencies, ["playwright"]);
+
+    // project.json namedInputs override nx.json ones
+    let production = inputs.named_input("web", "production");
+    assert!(production.matches(Path::new("apps/web/src/page.spec.ts")));
+    assert!(!production.matches(Path::new("apps/web/src/page.test.ts")));
+    assert!(!production.matches(Path::new("apps/web/jest.config.ts")));
+
+    let lint = inputs.target_inputs("web", "lint");
+    assert!(lint.matches(Path::new("apps/web/src/page.ts")));
+    assert!(!lint.matches(Path::new("babel.config.json")));
+    assert_eq!(lint.runtime, ["node -v"]);
+  }
+
+  #[test]
+  fn test_filter_targets_by_inputs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    write_nx_json(
+      root,
+      r#"{
+        "namedInputs": {
+          "production": ["default", "!{projectRoot}/**/*.spec.ts"]
+        },
+        "targetDefaults": {
+          "build": { "inputs": ["production", "^production", "{workspaceRoot}/tsconfig.base.json"] },
+          "test": { "inputs": ["default", "^production"] }
+        }
+      }"#,
+    );
+    let inputs = nx_inputs(
+      root,
+      &[
+        ("ui", "libs/ui", r#"{ "name": "ui" }"#),
+        ("web", "apps/web", r#"{ "name": "web" }"#),
+        ("docs", "apps/docs", r#"{ "name": "docs" }"#),
+      ],
+    );
+    let projects: Vec<Project> = ["ui", "web", "docs"]
+      .iter()
+      .zip(["libs/ui", "apps/web", "apps/docs"])
+      .map(|(name, project_root)| Project {
+        name: name.to_string(),
+        root: PathBuf::from(project_root),
+        source_root: PathBuf::from(project_root),
+        ts_config: None,
+        implicit_dependencies: vec![],
+        targets: vec![],
+        tags: vec![],
+      })
+      .collect();
+    let index = ProjectIndex::new(&projects, root);
+    // docs depends on nothing, so ui's files are none of its inputs
+    let mut graph = ProjectGraph::default();
+    graph.dependencies.insert(
+      "web".to_string(),
+      vec![crate::types::ProjectDependency {
+        source: "web".to_string(),
+        target: "ui".to_string(),
+        dependency_type: crate::types::DependencyType::Static,
+      }],
+    );
+    let pairs = || {
+      ["ui", "web", "docs"]
+        .iter()
+        .flat_map(|project| {
+          ["build", "test"].iter().map(move |target| ProjectTarget {
+            project: project.to_string(),
+            target: target.to_string(),
+          })
+        })
+        .collect::<Vec<_>>()
+    };
+    let changed = |path: &str| ChangedFile {
+      file_path: PathBuf::from(path),
+      changed_lines: vec![1],
+      deleted_lines: vec![],
+    };
+    let affected = |files: &[ChangedFile], lockfile_changed: bool| -> Vec<String> {
+      filter_targets_by_inputs(&inputs, pairs(), files, &index, &graph, lockfile_changed)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+    };
+
+    assert_eq!(
+      affected(&[changed("libs/ui/src/button.spec.ts")], false),
+      ["ui:test"]
+    );
+    assert_eq!(
+      affected(&[changed("libs/ui/src/button.ts")], false),
+      ["ui:build", "ui:test", "web:build", "web:test"]
+    );
+    assert_eq!(
+      affected(&[changed("libs/ui/src/button.spec.ts")], true).len(),
+      6
+    );
+
+    let workspace_targets =
+      targets_reading_workspace_files(&inputs, pairs(), &[changed("tsconfig.base.json")], &index);
+    assert_eq!(
+      workspace_targets
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      ["ui:build", "web:build", "docs:build"]
+    );
+    assert!(targets_reading_workspace_files(
+      &inputs,
+      pairs(),
+      &[changed("libs/ui/src/button.ts")],
+      &index
+    )
+    .is_empty());
+  }
 }
@@ -0,0 +1,305 @@
+//! Project dependency graph derived from source imports (`domino project-graph`).
+//!
+//! Every resolved import and re-export between files of different projects
+//! becomes a project edge, typed by the strongest import behind it: a static
+//! runtime import beats a dynamic `import()` (or `new URL()` module
+//! reference), which beats a type-only import: `import type`, or an import
+//! whose binding is only used in type positions. Nx-style
+//! `implicitDependencies` are added as `implicit` edges where no import
+//! already connects the two projects.
+//!
+//! For projects with a package.json, the edges are checked against the
+//! workspace packages it declares: imported packages missing from the
+//! manifest and declared ones that nothing imports are reported.
+
+use crate::error::Result;
+use crate::profiler::Profiler;
+use crate::semantic::{ImportIndexValue, WorkspaceAnalyzer};
+use crate::types::{
+  DependencyDrift, DependencyType, Project, ProjectDependency, ProjectGraph, ProjectGraphNode,
+  ProjectGraphNodeData, ProjectGraphReport,
+};
+use crate::utils::ProjectIndex;
+use crate::workspace::workspaces::declared_dependencies;
+use rustc_hash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Whether every reference to the importing binding is in a type position, so
+/// the import is erased from the emitted JavaScript like `import type`. An
+/// import nothing references keeps its runtime edge.
+fn only_used_as_type(analyzer: &WorkspaceAnalyzer, importer: &ImportIndexValue) -> bool {
+  let Some(symbol) = importer.local_symbol else {
+    return false;
+  };
+  analyzer
+    .find_symbol_references(&importer.importing_file, &[symbol])
+    .is_ok_and(|references| {
+      !references.is_empty() && references.iter().all(|reference| reference.is_type_only)
+    })
+}
+
+/// Build the project graph of the working tree and compare it with the
+/// projects' package.json dependencies
+pub fn build_project_graph(
+  cwd: &Path,
+  projects: Vec<Project>,
+  profiler: Arc<Profiler>,
+) -> Result<ProjectGraphReport> {
+  let analyzer = WorkspaceAnalyzer::new(projects, cwd, profiler)?;
+  Ok(build_project_graph_using(cwd, &analyzer))
+}
+
+/// [`build_project_graph`] on an already built `analyzer` of the workspace
+pub fn build_project_graph_using(cwd: &Path, analyzer: &WorkspaceAnalyzer) -> ProjectGraphReport {
+  let projects = &analyzer.projects;
+  let mut file_edges: Vec<(&PathBuf, &PathBuf, DependencyType)> = Vec::new();
+  for ((source_file, _), importers) in &analyzer.import_index {
+    for importer in importers {
+      let dependency_type = if importer.is_dynamic || importer.is_module_url {
+        DependencyType::Dynamic
+      } else if importer.is_type_only || only_used_as_type(analyzer, importer) {
+        DependencyType::TypeOnly
+      } else {
+        DependencyType::Static
+      };
+      file_edges.push((&importer.importing_file, source_file, dependency_type));
+    }
+  }
+  for (source_file, reexporters) in &analyzer.reexport_index {
+    for (reexporting_file, _) in reexporters {
+      file_edges.push((reexporting_file, source_file, DependencyType::Static));
+    }
+  }
+
+  let index = ProjectIndex::new(projects, cwd);
+  let mut edges: BTreeMap<(String, String), DependencyType> = BTreeMap::new();
+  for (from, to, dependency_type) in file_edges {
+    let targets = index.get_package_names_by_path(to);
+    for source in index.get_package_names_by_path(from) {
+      for target in &targets {
+        if source == *target {
+          continue;
+        }
+        edges
+          .entry((source.clone(), target.clone()))
+          .and_modify(|existing| *existing = strongest(*existing, dependency_type))
+          .or_insert(dependency_type);
+      }
+    }
+  }
+  for project in projects {
+    for dependency in &project.implicit_dependencies {
+      // `!name` entries exclude rather than add a dependency
+      if dependency.starts_with('!') || *dependency == project.name {
+        continue;
+      }
+      edges
+        .entry((project.name.clone(), dependency.clone()))
+        .or_insert(DependencyType::Implicit);
+    }
+  }
+  debug!("Derived {} project dependencies", edges.len());
+
+  let mut graph = ProjectGraph::default();
+  for project in projects {
+    graph.nodes.insert(
+      project.name.clone(),
+      ProjectGraphNode {
+        name: project.name.clone(),
+        project_type: project_type(cwd, project).to_string(),
+        data: ProjectGraphNodeData {
+          root: project.root.clone(),
+          source_root: project.source_root.clone(),
+        },
+      },
+    );
+    graph.dependencies.insert(project.name.clone(), Vec::new());
+  }
+  for ((source, target), dependency_type) in &edges {
+    graph
+      .dependencies
+      .entry(source.clone())
+      .or_default()
+      .push(ProjectDependency {
+        source: source.clone(),
+        target: target.clone(),
+        dependency_type: *dependency_type,
+      });
+  }
+
+  let (undeclared_dependencies, unused_dependencies) = dependency_drift(cwd, projects, &edges);
+  ProjectGraphReport {
+    graph,
+    undeclared_dependencies,
+    unused_dependencies,
+  }
+}
+
+/// The more binding of two dependency types between the same projects
+fn strongest(a: DependencyType, b: DependencyType) -> DependencyType {
+  let rank = |dependency_type| match dependency_type {
+    DependencyType::Static => 3,
+    DependencyType::Dynamic => 2,
+    DependencyType::TypeOnly => 1,
+    DependencyType::Implicit => 0,
+  };
+  if rank(b) > rank(a) {
+    b
+  } else {
+    a
+  }
+}
+
+/// Nx node type from project.json's `projectType`, defaulting to `lib`
+fn project_type(cwd: &Path, project: &Project) -> &'static str {
+  let project_type = fs::read_to_string(cwd.join(&project.root).join("project.json"))
+    .ok()
+    .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    .and_then(|json| json.get("projectType")?.as_str().map(str::to_string));
+  match project_type.as_deref() {
+    Some("application") if project.name.ends_with("-e2e") => "e2e",
+    Some("application") => "app",
+    _ => "lib",
+  }
+}
+
+/// Workspace package dependencies imported but not declared, and declared
+/// but not imported, for every project with a package.json
+fn dependency_drift(
+  cwd: &Path,
+  projects: &[Project],
+  edges: &BTreeMap<(String, String), DependencyType>,
+) -> (Vec<DependencyDrift>, Vec<DependencyDrift>) {
+  let manifests: FxHashMap<&str, (String, BTreeSet<String>)> = projects
+    .iter()
+    .filter_map(|project| {
+      declared_dependencies(&cwd.join(&project.root))
+        .map(|manifest| (project.name.as_str(), manifest))
+    })
+    .collect();
+  let project_by_package: FxHashMap<&str, &str> = manifests
+    .iter()
+    .map(|(project, (package, _))| (package.as_str(), *project))
+    .collect();
+
+  let mut undeclared = Vec::new();
+  let mut unused = Vec::new();
+  for project in projects {
+    let Some((_, declared)) = manifests.get(project.name.as_str()) else {
+      continue;
+    };
+
+    for ((source, target), dependency_type) in edges.range((project.name.clone(), String::new())..)
+    {
+      if *source != project.name {
+        break;
+      }
+      if *dependency_type == DependencyType::Implicit {
+        continue;
+      }
+      let Some((package, _)) = manifests.get(target.as_str()) else {
+        continue;
+      };
+      if !declared.contains(package) {
+        undeclared.push(DependencyDrift {
+          project: project.name.clone(),
+          dependency: package.clone(),
+        });
+      }
+    }
+
+    for package in declared {
+      let Some(target) = project_by_package.get(package.as_str()) else {
+        continue;
+      };
+      if *target != project.name && !edges.contains_key(&(project.name.clone(), target.to_string()))
+      {
+        unused.push(DependencyDrift {
+          project: project.name.clone(),
+          dependency: package.clone(),
+        });
+      }
+    }
+  }
+
+  (undeclared, unused)
+}
+
+/// Render the graph as Graphviz DOT: solid edges are static, dashed dynamic,
+/// dotted type-only and grey implicit
+pub fn to_dot(graph: &ProjectGraph) -> String {
+  let mut out = String::from("digraph projects {\n  rankdir=LR;\n  node [shape=box];\n");
+  for name in graph.nodes.keys() {
+    out.push_str(&format!("  \"{}\";\n", name));
+  }
+  for dependency in graph.dependencies.values().flatten() {
+    let style = match dependency.dependency_type {
+      DependencyType::Static => "",
+      DependencyType::Dynamic => " [style=dashed]",
+      DependencyType::TypeOnly => " [style=dotted]",
+      DependencyType::Implicit => " [color=gray]",
+    };
+    out.push_str(&format!(
+      "  \"{}\" -> \"{}\"{};\n",
+      dependency.source, dependency.target, style
+    ));
+  }
+  out.push_str("}\n");
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_strongest_dependency_type() {
+    assert_eq!(
+      strongest(DependencyType::TypeOnly, DependencyType::Static),
+      DependencyType::Static
+    );
+    assert_eq!(
+      strongest(DependencyType::Dynamic, DependencyType::TypeOnly),
+      DependencyType::Dynamic
+    );
+    assert_eq!(
+      strongest(DependencyType::Implicit, DependencyType::TypeOnly),
+      DependencyType::TypeOnly
+    );
+  }
+
+  #[test]
+  fn test_to_dot() {
+    let mut graph = ProjectGraph::default();
+    for name in ["app", "ui"] {
+      graph.nodes.insert(
+        name.to_string(),
+        ProjectGraphNode {
+          name: name.to_string(),
+          project_type: "lib".to_string(),
+          data: ProjectGraphNodeData {
+            root: PathBuf::from(name),
+            source_root: PathBuf::from(name),
+          },
+        },
+      );
+    }
+    graph.dependencies.insert(
+      "app".to_string(),
+      vec![ProjectDependency {
+        source: "app".to_string(),
+        target: "ui".to_string(),
+        dependency_type: DependencyType::Dynamic,
+      }],
+    );
+
+    assert_eq!(
+      to_dot(&graph),
+      "digraph projects {\n  rankdir=LR;\n  node [shape=box];\n  \"app\";\n  \"ui\";\n  \"app\" -> \"ui\" [style=dashed];\n}\n"
+    );
+  }
+}
@@ -1,5 +1,5 @@
 use crate::error::Result;
-use crate::types::{AffectCause, AffectedProjectInfo, AffectedReport};
+use crate::types::{AffectCause, AffectedProjectInfo, AffectedReport, AffectedScope};
 use std::fs;
 use std::path::Path;
 
@@ -1110,6 +1110,10 @@ fn generate_html(report: &AffectedReport) -> String {
             border-color: rgba(121, 192, 255, 0.30);
         }}
 
+        .cause-type.type-only {{
+            border-style: dashed;
+        }}
+
         .cause-type.reexported {{
             color: var(--accent-2);
             background: rgba(210, 168, 255, 0.10);
@@ -1506,13 +1510,24 @@ fn generate_cytoscape_data(report: &AffectedReport) -> String {
         AffectCause::DirectChange { .. } => {
           direct_changes.insert(project.name.clone());
         }
-        AffectCause::ImportedSymbol { source_project, .. } => {
+        AffectCause::ImportedSymbol {
+          source_project,
+          type_only,
+          ..
+        } => {
           relationships
             .entry(source_project.clone())
             .or_default()
             .entry(project.name.clone())
             .or_default()
-            .push("imported".to_string());
+            .push(
+              if *type_only {
+                "imported_type"
+              } else {
+                "imported"
+              }
+              .to_string(),
+            );
         }
         AffectCause::ReExported { .. } => {
           // Re-exports are internal to a project, don't show as separate edges
@@ -1575,7 +1590,9 @@ fn generate_cytoscape_data(report: &AffectedReport) -> String {
       }
 
       // Count cause types
-      let import_count = cause_types.iter().filter(|t| *t == "imported").count();
+      let type_import_count = cause_types.iter().filter(|t| *t == "imported_type").count();
+      let import_count =
+        cause_types.iter().filter(|t| *t == "imported").count() + type_import_count;
       let implicit_count = cause_types.iter().filter(|t| *t == "implicit").count();
 
       if implicit_count > 0 {
@@ -1585,10 +1602,16 @@ fn generate_cytoscape_data(report: &AffectedReport) -> String {
           js_str(&target_id)
         ));
       } else if import_count > 0 {
+        // Label edges that only exist in the type graph
+        let kind = if type_import_count == import_count {
+          "type import"
+        } else {
+          "import"
+        };
         let label = if import_count == 1 {
-          "1 import".to_string()
+          format!("1 {}", kind)
         } else {
-          format!("{} imports", import_count)
+          format!("{} {}s", import_count, kind)
         };
         edges.push(format!(
           r#"{{ data: {{ source: {}, target: {}, label: {} }} }}"#,
@@ -1824,7 +1847,7 @@ fn generate_details_html(report: &AffectedReport) -> String {
                     <summary>
                         <div class="project-name">{icon}<span>{name}</span></div>
                         <div class="badge-container">
-                            {badge}
+                            {badge}{scope}
                             <span class="affect-badge">{count} cause{plural}</span>
                         </div>
                     </summary>
@@ -1835,6 +1858,11 @@ fn generate_details_html(report: &AffectedReport) -> String {
       icon = ICON_PACKAGE,
       name = html_escape(&project.name),
       badge = badge,
+      scope = if project.scope == AffectedScope::TestOnly {
+        r#"<span class="affect-badge">Test-only</span>"#
+      } else {
+        ""
+      },
       count = project.causes.len(),
       plural = if project.causes.len() == 1 { "" } else { "s" }
     ));
@@ -1873,8 +1901,13 @@ fn generate_details_html(report: &AffectedReport) -> String {
           symbol,
           via_file,
           source_file,
+          type_only,
         } => {
-          html.push_str("<span class=\"cause-type imported\">Imported Symbol</span>");
+          if *type_only {
+            html.push_str("<span class=\"cause-type imported type-only\">Type-only Import</span>");
+          } else {
+            html.push_str("<span class=\"cause-type imported\">Imported Symbol</span>");
+          }
           html.push_str("<div class=\"cause-details\">");
           html.push_str(&format!(
             "Symbol: <span class=\"symbol\">{}</span><br/>",
@@ -2042,6 +2075,7 @@ mod tests {
     AffectedProjectInfo {
       name: name.to_string(),
       causes,
+      scope: AffectedScope::Production,
     }
   }
 
@@ -2101,6 +2135,7 @@ mod tests {
           symbol: "Logger".to_string(),
           via_file: PathBuf::from("libs/shared-utils/src/log.ts"),
           source_file: PathBuf::from("libs/core/src/logger.ts"),
+          type_only: false,
         },
         AffectCause::GlobalInvalidation {
           file: PathBuf::from(".github/workflows/ci.yml"),
@@ -2136,6 +2171,7 @@ mod tests {
             symbol: "formatDate".to_string(),
             via_file: PathBuf::from(format!("apps/app-{}/src/index.ts", i)),
             source_file: PathBuf::from("libs/shared-utils/src/format.ts"),
+            type_only: false,
           },
           AffectCause::GlobalInvalidation {
             file: PathBuf::from(".github/workflows/ci.yml"),
@@ -2233,6 +2269,7 @@ mod tests {
           symbol: "formatDate".to_string(),
           via_file: PathBuf::from("apps/web/src/main.ts"),
           source_file: PathBuf::from("libs/shared-utils/src/format.ts"),
+          type_only: false,
         }],
       ),
       make_project(
@@ -2242,6 +2279,7 @@ mod tests {
           symbol: "Logger".to_string(),
           via_file: PathBuf::from("libs/shared-utils/src/log.ts"),
           source_file: PathBuf::from("libs/core/src/logger.ts"),
+          type_only: false,
         }],
       ),
       make_project(
@@ -2501,6 +2539,31 @@ mod tests {
     assert!(!near_imported.contains("cause-type direct"));
   }
 
+  #[test]
+  fn type_only_import_is_labelled() {
+    let mut report = synth_normal_report();
+    report.projects.push(make_project(
+      "app-docs",
+      vec![AffectCause::ImportedSymbol {
+        source_project: "shared-utils".to_string(),
+        symbol: "DateFormat".to_string(),
+        via_file: PathBuf::from("apps/docs/src/main.ts"),
+        source_file: PathBuf::from("libs/shared-utils/src/format.ts"),
+        type_only: true,
+      }],
+    ));
+    let html = generate_html(&report);
+
+    let pill = html
+      .find("Type-only Import")
+      .expect("expected a Type-only Import pill");
+    assert!(html[pill.saturating_sub(80)..pill].contains("cause-type imported type-only"));
+    assert!(
+      html.contains(r#"label: "1 type import""#),
+      "graph edge backed only by type imports should say so"
+    );
+  }
+
   #[test]
   fn relative_time_helper_buckets() {
     use std::time::{SystemTime, UNIX_EPOCH};
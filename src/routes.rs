@@ -0,0 +1,420 @@
+//! Routes affected by a change (`domino affected-routes`).
+//!
+//! File-system routers map files to URL paths by convention. Routers are
+//! detected per project — Next.js `pages/` and `app/` (in `src/` or the
+//! project root) next to a `next.config.*`, and Remix `app/routes/` next to
+//! `app/root.*` — and more can be declared in the root package.json:
+//!
+//! ```json
+//! { "domino": { "routes": [{ "dir": "apps/docs/pages", "convention": "next-pages" }] } }
+//! ```
+//!
+//! A route is affected when its route module is reached by the change, or a
+//! module that renders around it is: `_app` / `_document` for the pages
+//! router, `layout`, `template` and friends for the directories below them in
+//! the app router, and parent routes and `root` for Remix.
+
+use crate::core;
+use crate::error::Result;
+use crate::profiler::Profiler;
+use crate::types::{AffectedRoute, AffectedRoutes, Project, TrueAffectedConfig};
+use crate::utils::{domino_setting, ProjectIndex};
+use crate::workspace;
+use rustc_hash::FxHashSet;
+use serde::Deserialize;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Extensions of route modules
+const ROUTE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mdx", "md"];
+
+/// Pages router files rendered around every page
+const NEXT_PAGES_SHELLS: &[&str] = &["_app", "_document", "_error"];
+
+/// App router files rendered around every route below their directory
+const NEXT_APP_LAYOUTS: &[&str] = &[
+  "layout",
+  "template",
+  "loading",
+  "error",
+  "not-found",
+  "default",
+];
+
+/// File-system routing conventions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RouteConvention {
+  /// Next.js `pages/`: every module is a route, `index` is the directory
+  NextPages,
+  /// Next.js `app/`: `page` and `route` modules, `(group)` and `@slot`
+  /// directories do not appear in the URL
+  NextApp,
+  /// Remix flat routes: `billing.$id.tsx` or `billing.$id/route.tsx`
+  Remix,
+}
+
+/// A directory routed by one convention
+#[derive(Debug, Clone, Deserialize)]
+pub struct Router {
+  /// Directory relative to the workspace root
+  pub dir: PathBuf,
+  pub convention: RouteConvention,
+}
+
+/// A route module of a router
+struct RouteModule {
+  project: String,
+  route: String,
+  file: PathBuf,
+  /// Directory the module belongs to, relative to the router
+  dir: PathBuf,
+  /// Remix route key (the dot-separated file or folder name)
+  key: String,
+}
+
+/// Find the routes whose modules, or the modules rendered around them, are
+/// reached by the change
+pub fn find_affected_routes(
+  config: TrueAffectedConfig,
+  profiler: Arc<Profiler>,
+) -> Result<AffectedRoutes> {
+  let cwd = config.cwd.clone();
+  let routers = discover_routers(&cwd, &config.projects);
+  debug!("Found {} file-system routers", routers.len());
+  let index = ProjectIndex::new(&config.projects, &cwd);
+
+  let files = workspace::workspace_files(&cwd)?;
+  let modules: Vec<RouteModule> = routers
+    .iter()
+    .flat_map(|router| route_modules(router, &files, &index))
+    .collect();
+
+  let affected = core::find_affected_files(config, profiler)?;
+  let global = !affected.global_triggers.is_empty();
+  let mut selected: FxHashSet<usize> = FxHashSet::default();
+  if global {
+    selected.extend(0..modules.len());
+  } else {
+    for file in &affected.files {
+      for router in &routers {
+        let relative = file.strip_prefix(&router.dir).ok();
+        let renders_all = renders_root(router, file);
+        if relative.is_none() && !renders_all {
+          continue;
+        }
+        selected.extend(
+          modules
+            .iter()
+            .enumerate()
+            .filter(|(_, module)| module.file.starts_with(&router.dir))
+            .filter(|(_, module)| {
+              renders_all || relative.is_some_and(|relative| renders(router, relative, module))
+            })
+            .map(|(i, _)| i),
+        );
+      }
+    }
+  }
+
+  let mut routes: Vec<AffectedRoute> = selected
+    .into_iter()
+    .map(|i| AffectedRoute {
+      project: modules[i].project.clone(),
+      route: modules[i].route.clone(),
+      file: modules[i].file.clone(),
+    })
+    .collect();
+  routes.sort_by(|a, b| (&a.project, &a.route, &a.file).cmp(&(&b.project, &b.route, &b.file)));
+  debug!("{} affected routes", routes.len());
+  Ok(AffectedRoutes { routes, global })
+}
+
+/// Routers declared in `domino.routes` plus those detected in `projects`
+pub fn discover_routers(cwd: &Path, projects: &[Project]) -> Vec<Router> {
+  let mut routers: Vec<Router> = domino_setting(cwd, "routes").unwrap_or_default();
+  for project in projects {
+    let root = &project.root;
+    let is_next = ["js", "mjs", "cjs", "ts"].iter().any(|ext| {
+      cwd
+        .join(root)
+        .join(format!("next.config.{}", ext))
+        .is_file()
+    });
+    if is_next {
+      for base in [root.join("src"), root.clone()] {
+        for (dir, convention) in [
+          ("pages", RouteConvention::NextPages),
+          ("app", RouteConvention::NextApp),
+        ] {
+          if cwd.join(&base).join(dir).is_dir() {
+            routers.push(Router {
+              dir: base.join(dir),
+              convention,
+            });
+          }
+        }
+      }
+    }
+
+    let app = root.join("app");
+    if cwd.join(&app).join("routes").is_dir() && has_module(&cwd.join(&app), "root") {
+      routers.push(Router {
+        dir: app.join("routes"),
+        convention: RouteConvention::Remix,
+      });
+    }
+  }
+  routers
+}
+
+fn has_module(dir: &Path, stem: &str) -> bool {
+  ROUTE_EXTENSIONS
+    .iter()
+    .any(|ext| dir.join(format!("{}.{}", stem, ext)).is_file())
+}
+
+/// The route modules among `files` that `router` serves
+fn route_modules(router: &Router, files: &[PathBuf], index: &ProjectIndex) -> Vec<RouteModule> {
+  files
+    .iter()
+    .filter_map(|file| {
+      let relative = file.strip_prefix(&router.dir).ok()?;
+      let (route, key) = route_path(router.convention, relative)?;
+      let project = index
+        .get_owning_packages_by_path(file)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| router.dir.to_string_lossy().into_owned());
+      Some(RouteModule {
+        project,
+        route,
+        file: file.clone(),
+        dir: relative.parent().unwrap_or(Path::new("")).to_path_buf(),
+        key,
+      })
+    })
+    .collect()
+}
+
+/// The URL path (and Remix route key) of a route module at `relative` to its
+/// router, or `None` when the file is not a route module
+pub fn route_path(convention: RouteConvention, relative: &Path) -> Option<(String, String)> {
+  let (stem, ext) = split_module(relative)?;
+  if !ROUTE_EXTENSIONS.contains(&ext) {
+    return None;
+  }
+  let dirs: Vec<&str> = relative
+    .parent()
+    .map(|parent| parent.components().filter_map(normal).collect())
+    .unwrap_or_default();
+
+  match convention {
+    RouteConvention::NextPages => {
+      if dirs.is_empty() && NEXT_PAGES_SHELLS.contains(&stem) {
+        return None;
+      }
+      let mut segments = dirs;
+      if stem != "index" {
+        segments.push(stem);
+      }
+      Some((url(segments), String::new()))
+    }
+    RouteConvention::NextApp => {
+      if stem != "page" && stem != "route" {
+        return None;
+      }
+      let segments = dirs
+        .into_iter()
+        .filter(|dir| !is_hidden_segment(dir))
+        .collect();
+      Some((url(segments), String::new()))
+    }
+    RouteConvention::Remix => {
+      let key = match dirs.as_slice() {
+        [] => stem,
+        [folder] if stem == "route" => folder,
+        _ => return None,
+      };
+      let segments = remix_segments(key)
+        .into_iter()
+        .filter(|segment| !segment.starts_with('_'))
+        .map(|segment| segment.trim_end_matches('_'))
+        .collect();
+      let route = url(segments).replace(['[', ']'], "");
+      Some((route, key.to_string()))
+    }
+  }
+}
+
+/// Whether the module at `relative` to `router` renders `module`
+fn renders(router: &Router, relative: &Path, module: &RouteModule) -> bool {
+  let Some((stem, _)) = split_module(relative) else {
+    return false;
+  };
+  let dir = relative.parent().unwrap_or(Path::new(""));
+  let is_module = module.file == router.dir.join(relative);
+  match router.convention {
+    RouteConvention::NextPages => {
+      (dir.as_os_str().is_empty() && NEXT_PAGES_SHELLS.contains(&stem)) || is_module
+    }
+    RouteConvention::NextApp => {
+      (NEXT_APP_LAYOUTS.contains(&stem) && module.dir.starts_with(dir)) || is_module
+    }
+    RouteConvention::Remix => match route_path(RouteConvention::Remix, relative) {
+      Some((_, key)) => {
+        module.key == key
+          || (module.key.starts_with(&key) && module.key[key.len()..].starts_with('.'))
+      }
+      None => false,
+    },
+  }
+}
+
+/// Remix `root` renders every route, but lives next to the routes directory
+/// rather than in it, so it is matched against the router's parent
+fn renders_root(router: &Router, file: &Path) -> bool {
+  router.convention == RouteConvention::Remix
+    && file.parent() == router.dir.parent()
+    && split_module(file).is_some_and(|(stem, _)| stem == "root")
+}
+
+/// Split the file name of `path` into stem and extension
+fn split_module(path: &Path) -> Option<(&str, &str)> {
+  let name = path.file_name()?.to_str()?;
+  name.rsplit_once('.')
+}
+
+/// App router `(group)` and `@slot` directories, which organize routes
+/// without appearing in the URL
+fn is_hidden_segment(dir: &str) -> bool {
+  dir.starts_with('@') || (dir.starts_with('(') && dir.ends_with(')'))
+}
+
+fn normal(component: Component<'_>) -> Option<&str> {
+  match component {
+    Component::Normal(name) => name.to_str(),
+    _ => None,
+  }
+}
+
+/// Remix route key segments: dots separate segments except inside `[…]`
+fn remix_segments(key: &str) -> Vec<&str> {
+  let mut segments = Vec::new();
+  let mut start = 0;
+  let mut escaped = false;
+  for (i, c) in key.char_indices() {
+    match c {
+      '[' => escaped = true,
+      ']' => escaped = false,
+      '.' if !escaped => {
+        segments.push(&key[start..i]);
+        start = i + 1;
+      }
+      _ => {}
+    }
+  }
+  segments.push(&key[start..]);
+  segments
+}
+
+fn url(segments: Vec<&str>) -> String {
+  format!("/{}", segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn route(convention: RouteConvention, path: &str) -> Option<String> {
+    route_path(convention, Path::new(path)).map(|(route, _)| route)
+  }
+
+  #[test]
+  fn test_route_path() {
+    use RouteConvention::*;
+    assert_eq!(route(NextPages, "index.tsx").as_deref(), Some("/"));
+    assert_eq!(
+      route(NextPages, "billing/[id].tsx").as_deref(),
+      Some("/billing/[id]")
+    );
+    assert_eq!(
+      route(NextPages, "api/users/index.ts").as_deref(),
+      Some("/api/users")
+    );
+    assert_eq!(route(NextPages, "_app.tsx"), None);
+    assert_eq!(route(NextPages, "styles.css"), None);
+
+    assert_eq!(
+      route(NextApp, "(marketing)/billing/[id]/page.tsx").as_deref(),
+      Some("/billing/[id]")
+    );
+    assert_eq!(route(NextApp, "@modal/page.tsx").as_deref(), Some("/"));
+    assert_eq!(
+      route(NextApp, "api/health/route.ts").as_deref(),
+      Some("/api/health")
+    );
+    assert_eq!(route(NextApp, "billing/invoice-table.tsx"), None);
+
+    assert_eq!(route(Remix, "_index.tsx").as_deref(), Some("/"));
+    assert_eq!(
+      route(Remix, "billing.$id.tsx").as_deref(),
+      Some("/billing/$id")
+    );
+    assert_eq!(
+      route(Remix, "_auth.login/route.tsx").as_deref(),
+      Some("/login")
+    );
+    assert_eq!(
+      route(Remix, "sitemap[.]xml.ts").as_deref(),
+      Some("/sitemap.xml")
+    );
+    assert_eq!(
+      route(Remix, "billing_.edit.tsx").as_deref(),
+      Some("/billing/edit")
+    );
+    assert_eq!(route(Remix, "_auth.login/form.tsx"), None);
+  }
+
+  #[test]
+  fn test_root_modules_do_not_render_nested_siblings() {
+    let module = |router: &Router, relative: &str| RouteModule {
+      project: "web".to_string(),
+      route: String::new(),
+      file: router.dir.join(relative),
+      dir: Path::new(relative)
+        .parent()
+        .unwrap_or(Path::new(""))
+        .to_path_buf(),
+      key: String::new(),
+    };
+    let rendered = |router: &Router, changed: &str, modules: &[&str]| -> Vec<String> {
+      modules
+        .iter()
+        .filter(|relative| renders(router, Path::new(changed), &module(router, relative)))
+        .map(|relative| relative.to_string())
+        .collect()
+    };
+
+    let app = Router {
+      dir: PathBuf::from("apps/web/app"),
+      convention: RouteConvention::NextApp,
+    };
+    let app_modules = ["page.tsx", "billing/page.tsx", "admin/page.tsx"];
+    assert_eq!(rendered(&app, "page.tsx", &app_modules), ["page.tsx"]);
+    assert_eq!(rendered(&app, "layout.tsx", &app_modules), app_modules);
+
+    let pages = Router {
+      dir: PathBuf::from("apps/web/pages"),
+      convention: RouteConvention::NextPages,
+    };
+    let pages_modules = ["index.tsx", "billing/index.tsx", "admin/index.tsx"];
+    assert_eq!(rendered(&pages, "index.tsx", &pages_modules), ["index.tsx"]);
+    assert_eq!(
+      rendered(&pages, "billing/index.tsx", &pages_modules),
+      ["billing/index.tsx"]
+    );
+    assert_eq!(rendered(&pages, "_app.tsx", &pages_modules), pages_modules);
+  }
+}
@@ -11,6 +11,8 @@ use oxc_ast_visit::Visit;
 use oxc_parser::Parser;
 use oxc_semantic::SemanticBuilder;
 use oxc_span::{GetSpan, SourceType, Span};
+use oxc_syntax::node::NodeId;
+use oxc_syntax::symbol::SymbolId;
 use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::HashMap;
@@ -21,8 +23,25 @@ use std::time::Instant;
 use tracing::{debug, warn};
 use walkdir::WalkDir;
 
-/// Type alias for a single import index value: (importing_file, local_name, from_module, is_dynamic)
-type ImportIndexValue = (PathBuf, String, String, bool);
+/// A single import index value: one import of a `(source_file, symbol_name)` key.
+#[derive(Debug, Clone)]
+pub struct ImportIndexValue {
+  /// File containing the import
+  pub importing_file: PathBuf,
+  /// Local binding name in the importing file
+  pub local_name: String,
+  /// The module specifier as written, kept for re-export checking
+  pub from_module: String,
+  /// Whether the import is a dynamic `import()` expression
+  pub is_dynamic: bool,
+  /// Whether the import is a `new URL('…', import.meta.url)` module reference
+  pub is_module_url: bool,
+  /// Module-scope symbol of the importing binding. `None` for dynamic and
+  /// module URL imports, which bind nothing in the importing file.
+  pub local_symbol: Option<SymbolId>,
+  /// Whether the import is `import type` / `import { type X }`
+  pub is_type_only: bool,
+}
 /// Type alias for import index entries: a list of values for a given (source_file, symbol_name) key
 type ImportIndexEntry = Vec<ImportIndexValue>;
 /// Type alias for the import index map: (source_file, symbol_name) -> entries
@@ -109,14 +128,21 @@ pub struct FileSemanticData {
   #[allow(dead_code)]
   allocator: Allocator,
   semantic: oxc_semantic::Semantic<'static>,
+  template: Option<super::sfc::Template>,
 }
 
 impl FileSemanticData {
-  /// The file's source text.
+  /// The file's source text. For a single-file component, only its script
+  /// blocks, with the markup blanked out.
   pub fn source(&self) -> &str {
     &self.source
   }
 
+  /// The markup of a Vue, Svelte or Astro component.
+  pub fn template(&self) -> Option<&super::sfc::Template> {
+    self.template.as_ref()
+  }
+
   /// The file's semantic model.
   ///
   /// The returned reference is deliberately `&Semantic<'_>` rather than
@@ -156,9 +182,8 @@ pub struct WorkspaceAnalyzer {
   pub exports: HashMap<PathBuf, Vec<Export>>,
   /// Projects in the workspace
   pub projects: Vec<Project>,
-  /// Reverse import index: (source_file, symbol_name) -> [(importing_file, local_name, from_module)]
+  /// Reverse import index: (source_file, symbol_name) -> [ImportIndexValue]
   /// This index maps from a file+symbol to all the places that import it
-  /// The from_module is kept for re-export checking
   pub import_index: ImportIndexMap,
   /// Reverse re-export index: resolved_source_file -> [(reexporting_file, export)]
   ///
@@ -173,6 +198,11 @@ pub struct WorkspaceAnalyzer {
   /// Nx project names can differ from the npm package names / tsconfig aliases
   /// that code actually imports.
   pub tsconfig_path_prefixes: Vec<String>,
+  /// Module Federation remotes, resolving host specifiers like `shop/Button`
+  /// to the remote's exposed files
+  pub federation: super::ModuleFederation,
+  /// Angular template usages: (declaring file, class) -> (template owner, line)
+  pub template_usages: super::angular::TemplateUsageIndex,
   /// Profiler for performance measurement
   pub profiler: Arc<Profiler>,
 }
@@ -181,6 +211,7 @@ impl WorkspaceAnalyzer {
   /// Create a new workspace analyzer
   pub fn new(projects: Vec<Project>, cwd: &Path, profiler: Arc<Profiler>) -> Result<Self> {
     let tsconfig_path_prefixes = super::parse_tsconfig_path_prefixes(cwd);
+    let federation = super::ModuleFederation::load(cwd, &projects);
 
     let mut analyzer = Self {
       files: HashMap::new(),
@@ -190,11 +221,16 @@ impl WorkspaceAnalyzer {
       import_index: FxHashMap::default(),
       reexport_index: FxHashMap::default(),
       tsconfig_path_prefixes,
+      federation,
+      template_usages: FxHashMap::default(),
       profiler,
     };
 
     analyzer.analyze_workspace(cwd)?;
 
+    // Link Angular declarations to the templates using them
+    analyzer.template_usages = super::angular::build_template_usages(cwd, &analyzer.files);
+
     // Build import index
     analyzer.build_import_index(cwd)?;
 
@@ -211,28 +247,24 @@ impl WorkspaceAnalyzer {
   /// resolve, or that resolve outside of `cwd`. Keeping this in one place guarantees
   /// the import index, the re-export index and the on-demand resolution in
   /// `ReferenceFinder` agree on what a specifier points at.
-  fn resolve_workspace_specifier(
+  pub(crate) fn resolve_workspace_specifier(
     &self,
     resolver: &oxc_resolver::Resolver,
     cwd: &Path,
     from_file: &Path,
     specifier: &str,
   ) -> Option<PathBuf> {
-    let from_path = cwd.join(from_file);
-    let context = from_path.parent()?;
-
-    if !super::is_workspace_specifier(specifier, &self.projects, &self.tsconfig_path_prefixes) {
-      return None;
-    }
-
-    match resolver.resolve(context, specifier) {
-      Ok(resolution) => resolution
-        .path()
-        .strip_prefix(cwd)
-        .ok()
-        .map(|p| p.to_path_buf()),
-      Err(_) => super::simple_resolve_relative(cwd, context, specifier),
+    if let Some(exposed) = self.federation.resolve(from_file, specifier) {
+      return Some(exposed);
     }
+    super::resolve_workspace_specifier(
+      resolver,
+      cwd,
+      &self.projects,
+      &self.tsconfig_path_prefixes,
+      from_file,
+      specifier,
+    )
   }
 
   /// Build the reverse re-export index: resolved_source_file -> [(reexporting_file, export)]
@@ -275,7 +307,7 @@ impl WorkspaceAnalyzer {
     Ok(())
   }
 
-  /// Build reverse import index: (source_file, symbol) -> [(importing_file, local_name, from_module)]
+  /// Build reverse import index: (source_file, symbol) -> [ImportIndexValue]
   /// This must be called after analyze_workspace and needs a resolver
   ///
   /// Resolution (the expensive part — real filesystem work via `oxc_resolver`:
@@ -297,6 +329,7 @@ impl WorkspaceAnalyzer {
     // captured, which keeps this compatible with the `&mut self` receiver.
     let projects = &self.projects;
     let tsconfig_path_prefixes = &self.tsconfig_path_prefixes;
+    let federation = &self.federation;
 
     // Flatten to a list of (importing_file, import) work items.
     let work_items: Vec<(&PathBuf, &Import)> = self
@@ -316,7 +349,8 @@ impl WorkspaceAnalyzer {
     //
     // NOTE: We intentionally do NOT skip type-only imports. Even though they
     // don't exist at runtime, they represent semantic dependencies — if a
-    // type changes, files that import it need to be re-type-checked.
+    // type changes, files that import it need to be re-type-checked. They are
+    // flagged instead, so `Propagation::Runtime` can drop them downstream.
     let resolved_entries: Vec<((PathBuf, String), ImportIndexValue)> = work_items
       .into_par_iter()
       .filter_map(|(importing_file, import)| {
@@ -324,31 +358,49 @@ impl WorkspaceAnalyzer {
         let from_path = cwd.join(importing_file);
         let context = from_path.parent()?;
 
-        if !super::is_workspace_specifier(&import.from_module, projects, tsconfig_path_prefixes) {
-          return None;
-        }
+        let resolved = if let Some(exposed) =
+          federation.resolve(importing_file, &import.from_module)
+        {
+          exposed
+        } else {
+          if !super::is_workspace_specifier(&import.from_module, projects, tsconfig_path_prefixes) {
+            return None;
+          }
 
-        let resolved = match resolver.resolve(context, &import.from_module) {
-          Ok(resolution) => resolution.path().strip_prefix(cwd).ok()?.to_path_buf(),
-          Err(_) => super::simple_resolve_relative(cwd, context, &import.from_module)?,
+          match resolver.resolve(context, &import.from_module) {
+            Ok(resolution) => resolution.path().strip_prefix(cwd).ok()?.to_path_buf(),
+            Err(_) => super::simple_resolve_relative(cwd, context, &import.from_module)?,
+          }
         };
 
-        // (resolved_file, imported_symbol) -> (importing_file, local_name, from_module, is_dynamic)
         let key = (resolved, import.imported_name.clone());
-        let value = (
-          importing_file.clone(),
-          import.local_name.clone(),
-          import.from_module.clone(),
-          import.is_dynamic,
-        );
+        let value = ImportIndexValue {
+          importing_file: importing_file.clone(),
+          local_name: import.local_name.clone(),
+          from_module: import.from_module.clone(),
+          is_dynamic: import.is_dynamic,
+          is_module_url: import.is_module_url,
+          local_symbol: None,
+          is_type_only: import.is_type_only,
+        };
         Some((key, value))
       })
       .collect();
 
     // Merge sequentially — cheap relative to the parallel resolution work
-    // above — into the final index map.
+    // above — into the final index map. The importing binding's symbol is
+    // looked up here rather than in the closure: `FileSemanticData` owns an
+    // arena allocator and is not `Sync`.
     let mut index: ImportIndexMap = FxHashMap::default();
-    for (key, value) in resolved_entries {
+    for (key, mut value) in resolved_entries {
+      if !value.is_dynamic && !value.is_module_url {
+        value.local_symbol = self.files.get(&value.importing_file).and_then(|file_data| {
+          file_data
+            .semantic()
+            .scoping()
+            .get_root_binding(&value.local_name)
+        });
+      }
       index.entry(key).or_default().push(value);
     }
 
@@ -360,7 +412,11 @@ impl WorkspaceAnalyzer {
     // is deterministic and reproducible across runs, rather than depending on
     // thread-scheduling order.
     for entries in index.values_mut() {
-      entries.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+      entries.sort_by(|a, b| {
+        a.importing_file
+          .cmp(&b.importing_file)
+          .then_with(|| a.local_name.cmp(&b.local_name))
+      });
     }
 
     let unique_symbols = index
@@ -439,7 +495,7 @@ impl WorkspaceAnalyzer {
         e.map_err(|err| warn!("Failed to read directory entry: {}", err))
           .ok()
       })
-      .filter(|e| e.file_type().is_file() && crate::utils::is_source_file(e.path()))
+      .filter(|e| e.file_type().is_file() && crate::utils::is_analyzable_file(e.path()))
       .map(|e| {
         let abs_path = e.into_path();
         let rel_path = abs_path
@@ -455,9 +511,7 @@ impl WorkspaceAnalyzer {
   /// This is a pure function with no `&self` — safe to call from parallel iterators.
   fn parse_single_file(file_path: &Path, relative_path: PathBuf) -> Result<ParseResult> {
     let source = fs::read_to_string(file_path)?;
-
-    let source_type = SourceType::from_path(file_path)
-      .unwrap_or_else(|_| SourceType::default().with_typescript(true));
+    let (source, source_type, template) = Self::prepare_source(file_path, source);
 
     let allocator = Allocator::default();
 
@@ -516,6 +570,7 @@ impl WorkspaceAnalyzer {
         source,
         allocator,
         semantic,
+        template,
       },
       imports,
       exports,
@@ -526,11 +581,11 @@ impl WorkspaceAnalyzer {
   /// [`FileSemanticData`], picking the source type from `file_path`'s
   /// extension. Unlike [`parse_single_file`], this reads no file from disk and
   /// skips import/export extraction — it exists to run [`find_top_level_symbols`]
-  /// against a base-revision snapshot of deleted code, which is never added to
+  /// against a base-revision snapshot of deleted code, and to read a project's
+  /// public API at the base revision (`api_diff`). Neither is ever added to
   /// `self.files`.
-  fn parse_source(file_path: &Path, source: String) -> Result<FileSemanticData> {
-    let source_type = SourceType::from_path(file_path)
-      .unwrap_or_else(|_| SourceType::default().with_typescript(true));
+  pub(crate) fn parse_source(file_path: &Path, source: String) -> Result<FileSemanticData> {
+    let (source, source_type, template) = Self::prepare_source(file_path, source);
 
     let allocator = Allocator::default();
     let parser = Parser::new(&allocator, &source, source_type);
@@ -568,14 +623,38 @@ impl WorkspaceAnalyzer {
       source,
       allocator,
       semantic,
+      template,
     })
   }
+
+  /// The text Oxc parses for `file_path` and its source type. Single-file
+  /// components are reduced to their script blocks, with the markup returned
+  /// separately.
+  fn prepare_source(
+    file_path: &Path,
+    source: String,
+  ) -> (String, SourceType, Option<super::sfc::Template>) {
+    match super::sfc::extract(file_path, &source) {
+      Some((script, source_type, template)) => (script, source_type, Some(template)),
+      None => {
+        let source_type = SourceType::from_path(file_path)
+          .unwrap_or_else(|_| SourceType::default().with_typescript(true));
+        (source, source_type, None)
+      }
+    }
+  }
 }
 
-/// Visitor to collect dynamic imports (import() expressions)
+/// Visitor to collect dynamic imports (import() expressions) and
+/// `new URL('…', import.meta.url)` module references
 struct DynamicImportVisitor<'a> {
   imports: Vec<Import>,
   dynamic_count: usize,
+  module_url_count: usize,
+  /// Spans of `new URL(…)` expressions passed straight to a worker constructor
+  /// or `serviceWorker.register`. Those are module references even when the
+  /// specifier has no source extension (`new URL('./worker', import.meta.url)`).
+  worker_url_spans: FxHashSet<Span>,
   /// Phantom data to maintain lifetime parameter
   /// This zero-sized type marker ensures the visitor maintains the correct lifetime
   _phantom: std::marker::PhantomData<&'a ()>,
@@ -586,6 +665,8 @@ impl<'a> DynamicImportVisitor<'a> {
     Self {
       imports: Vec::new(),
       dynamic_count: 0,
+      module_url_count: 0,
+      worker_url_spans: FxHashSet::default(),
       _phantom: std::marker::PhantomData,
     }
   }
@@ -603,8 +684,80 @@ impl<'a> DynamicImportVisitor<'a> {
       resolved_file: None,
       is_type_only: false,
       is_dynamic: true,
+      is_module_url: false,
+    }
+  }
+
+  /// Create a whole-module import for a `new URL('…', import.meta.url)` reference.
+  ///
+  /// The referenced module runs as its own entry point (worker, service worker),
+  /// so there is no binding to narrow on: the import is recorded as a namespace
+  /// import flagged `is_module_url`, and `ReferenceFinder` treats every symbol of
+  /// the target module as reaching the `new URL` site.
+  fn create_module_url_import(&self, from_module: &str) -> Import {
+    Import {
+      imported_name: "*".to_string(),
+      local_name: format!("__module_url_{}", self.module_url_count),
+      from_module: from_module.to_string(),
+      resolved_file: None,
+      is_type_only: false,
+      is_dynamic: false,
+      is_module_url: true,
     }
   }
+
+  /// Remember the `new URL(…)` argument of a worker constructor or
+  /// `serviceWorker.register` call so `visit_new_expression` records it.
+  fn mark_worker_url_argument(&mut self, arguments: &[oxc_ast::ast::Argument<'a>]) {
+    if let Some(Expression::NewExpression(url_expr)) =
+      arguments.first().and_then(|arg| arg.as_expression())
+    {
+      if module_url_specifier(url_expr).is_some() {
+        self.worker_url_spans.insert(url_expr.span);
+      }
+    }
+  }
+}
+
+/// Return the specifier of a `new URL('<literal>', import.meta.url)` expression.
+fn module_url_specifier<'b>(expr: &'b oxc_ast::ast::NewExpression<'_>) -> Option<&'b str> {
+  let Expression::Identifier(callee) = &expr.callee else {
+    return None;
+  };
+  if callee.name != "URL" || expr.arguments.len() < 2 {
+    return None;
+  }
+
+  let Some(Expression::StaticMemberExpression(base)) = expr.arguments[1].as_expression() else {
+    return None;
+  };
+  let Expression::MetaProperty(meta) = &base.object else {
+    return None;
+  };
+  if base.property.name != "url" || meta.meta.name != "import" || meta.property.name != "meta" {
+    return None;
+  }
+
+  match expr.arguments[0].as_expression()? {
+    Expression::StringLiteral(lit) => Some(lit.value.as_str()),
+    _ => None,
+  }
+}
+
+/// Whether `callee` is `navigator.serviceWorker.register` (or any
+/// `*.serviceWorker.register`, e.g. `window.navigator.serviceWorker.register`).
+fn is_service_worker_register(callee: &Expression) -> bool {
+  let Expression::StaticMemberExpression(register) = callee else {
+    return false;
+  };
+  if register.property.name != "register" {
+    return false;
+  }
+  match &register.object {
+    Expression::StaticMemberExpression(container) => container.property.name == "serviceWorker",
+    Expression::Identifier(ident) => ident.name == "serviceWorker",
+    _ => false,
+  }
 }
 
 impl<'a> Visit<'a> for DynamicImportVisitor<'a> {
@@ -634,6 +787,407 @@ impl<'a> Visit<'a> for DynamicImportVisitor<'a> {
     // Continue walking the AST
     walk::walk_import_expression(self, expr);
   }
+
+  fn visit_new_expression(&mut self, expr: &oxc_ast::ast::NewExpression<'a>) {
+    if let Some(specifier) = module_url_specifier(expr) {
+      // A bare `new URL('./logo.svg', import.meta.url)` is an asset reference and
+      // is left to `AssetReferenceFinder`; only source modules (or anything handed
+      // directly to a worker) become import edges.
+      if crate::utils::is_source_file(Path::new(specifier))
+        || self.worker_url_spans.contains(&expr.span)
+      {
+        debug!("Found module URL reference: {}", specifier);
+        let import = self.create_module_url_import(specifier);
+        self.imports.push(import);
+        self.module_url_count += 1;
+      }
+    } else if let Expression::Identifier(callee) = &expr.callee {
+      if matches!(callee.name.as_str(), "Worker" | "SharedWorker") {
+        self.mark_worker_url_argument(&expr.arguments);
+      }
+    }
+
+    walk::walk_new_expression(self, expr);
+  }
+
+  fn visit_call_expression(&mut self, expr: &oxc_ast::ast::CallExpression<'a>) {
+    if is_service_worker_register(&expr.callee) {
+      self.mark_worker_url_argument(&expr.arguments);
+    }
+
+    walk::walk_call_expression(self, expr);
+  }
+}
+
+/// Collects the references that read one property from a module namespace
+/// object, following the namespace through aliases, destructuring, `await`
+/// and `.then()` callbacks. See [`WorkspaceAnalyzer::find_namespace_member_access`].
+///
+/// Class instances are tracked the same way (`new ApiClient().getUser`), with
+/// the member name as the property; see [`WorkspaceAnalyzer::find_class_member_usage`].
+struct NamespaceUsage<'s> {
+  analyzer: &'s WorkspaceAnalyzer,
+  file_path: &'s Path,
+  file_data: &'s FileSemanticData,
+  property_name: &'s str,
+  /// Bindings already followed, so alias chains (`const a = ns; const b = a`)
+  /// are walked once.
+  visited_bindings: FxHashSet<SymbolId>,
+  references: Vec<Reference>,
+}
+
+impl<'s> NamespaceUsage<'s> {
+  fn new(
+    analyzer: &'s WorkspaceAnalyzer,
+    file_path: &'s Path,
+    file_data: &'s FileSemanticData,
+    property_name: &'s str,
+  ) -> Self {
+    Self {
+      analyzer,
+      file_path,
+      file_data,
+      property_name,
+      visited_bindings: FxHashSet::default(),
+      references: Vec::new(),
+    }
+  }
+
+  fn push(&mut self, span: Span) {
+    self.push_reference(span, false);
+  }
+
+  /// Record a use that only reads the property's type (`ns.Type`).
+  fn push_type(&mut self, span: Span) {
+    self.push_reference(span, true);
+  }
+
+  fn push_reference(&mut self, span: Span, is_type_only: bool) {
+    let (line, column) = self
+      .analyzer
+      .span_to_line_col(self.file_data.source(), span);
+    self.references.push(Reference {
+      file_path: self.file_path.to_path_buf(),
+      line,
+      column,
+      is_type_only,
+    });
+  }
+
+  /// Record a use of the whole value at `id`, which may reach any property.
+  /// A bare identifier in a type position (`x: Client`, `typeof routes`) only
+  /// reaches its type.
+  fn escape(&mut self, id: NodeId) {
+    let semantic = self.file_data.semantic();
+    let span = semantic.nodes().kind(id).span();
+    let in_type_position = match semantic.nodes().kind(id) {
+      AstKind::IdentifierReference(ident) => ident.reference_id.get().is_some_and(|reference_id| {
+        is_type_position(semantic.scoping().get_reference(reference_id).flags())
+      }),
+      _ => false,
+    };
+    self.push_reference(span, in_type_position);
+  }
+
+  /// Follow every read of the binding `symbol_id`.
+  fn binding(&mut self, symbol_id: SymbolId) {
+    if !self.visited_bindings.insert(symbol_id) {
+      return;
+    }
+
+    let reads: Vec<NodeId> = self
+      .file_data
+      .semantic()
+      .symbol_references(symbol_id)
+      .map(|reference| reference.node_id())
+      .collect();
+
+    for id in reads {
+      self.value(id);
+    }
+  }
+
+  /// Classify a node whose value is a class constructor: static member reads
+  /// match directly, and `new` produces an instance whose member reads match
+  /// like a namespace's.
+  fn class_value(&mut self, id: NodeId) {
+    let nodes = self.file_data.semantic().nodes();
+    let value_span = nodes.kind(id).span();
+    let parent_id = self.parent(id);
+
+    match nodes.kind(parent_id) {
+      AstKind::StaticMemberExpression(member) if member.object.span() == value_span => {
+        if member.property.name == self.property_name {
+          self.push(member.span);
+        }
+      }
+      AstKind::ComputedMemberExpression(member) if member.object.span() == value_span => {
+        match member.static_property_name() {
+          Some(name) if name != self.property_name => {}
+          _ => self.push(member.span),
+        }
+      }
+      AstKind::NewExpression(new_expr) if new_expr.callee.span() == value_span => {
+        self.value(parent_id);
+      }
+      AstKind::ExportSpecifier(_) => {}
+      // Subclassing, type annotations, passing the class along: any member
+      // may be reached
+      _ => self.escape(id),
+    }
+  }
+
+  /// Parent of `id`, skipping expression wrappers that pass the value through
+  /// unchanged (parentheses, TypeScript assertions, call arguments).
+  fn parent(&self, id: NodeId) -> NodeId {
+    let nodes = self.file_data.semantic().nodes();
+    let mut current = id;
+    loop {
+      let parent = nodes.parent_id(current);
+      if parent == current {
+        return parent;
+      }
+      match nodes.kind(parent) {
+        AstKind::ParenthesizedExpression(_)
+        | AstKind::TSAsExpression(_)
+        | AstKind::TSSatisfiesExpression(_)
+        | AstKind::TSNonNullExpression(_)
+        | AstKind::Argument(_) => current = parent,
+        _ => return parent,
+      }
+    }
+  }
+
+  /// Classify a node whose value is the namespace object itself.
+  fn value(&mut self, id: NodeId) {
+    let nodes = self.file_data.semantic().nodes();
+    let value_span = nodes.kind(id).span();
+    let parent_id = self.parent(id);
+
+    match nodes.kind(parent_id) {
+      AstKind::StaticMemberExpression(member) if member.object.span() == value_span => {
+        if member.property.name == self.property_name {
+          self.push(member.span);
+        }
+      }
+      AstKind::ComputedMemberExpression(member) if member.object.span() == value_span => {
+        match member.static_property_name() {
+          Some(name) if name != self.property_name => {}
+          // The named property, or a non-literal key that could be any export
+          _ => self.push(member.span),
+        }
+      }
+      AstKind::VariableDeclarator(declarator)
+        if declarator.init.as_ref().map(|init| init.span()) == Some(value_span) =>
+      {
+        self.pattern(&declarator.id);
+      }
+      AstKind::AwaitExpression(_) => self.value(parent_id),
+      AstKind::ExpressionStatement(_) => self.returned_from_arrow(parent_id),
+      // `export { ns }` forwards the binding; re-export tracing follows it
+      AstKind::ExportSpecifier(_) => {}
+      // `ns.Type` in type position reads only its right-hand member
+      AstKind::TSQualifiedName(qualified) if qualified.left.span() == value_span => {
+        if qualified.right.name == self.property_name {
+          self.push_type(qualified.span);
+        }
+      }
+      _ => self.escape(id),
+    }
+  }
+
+  /// Classify a node whose value is a promise of the namespace (`import()`).
+  fn promise(&mut self, id: NodeId) {
+    let nodes = self.file_data.semantic().nodes();
+    let promise_span = nodes.kind(id).span();
+    let parent_id = self.parent(id);
+
+    match nodes.kind(parent_id) {
+      AstKind::AwaitExpression(_) => self.value(parent_id),
+      AstKind::StaticMemberExpression(member)
+        if member.object.span() == promise_span && member.property.name == "then" =>
+      {
+        let call_id = nodes.parent_id(parent_id);
+        let AstKind::CallExpression(call) = nodes.kind(call_id) else {
+          self.push(promise_span);
+          return;
+        };
+        let callback = call.arguments.first().and_then(|arg| arg.as_expression());
+        match callback {
+          Some(Expression::ArrowFunctionExpression(func)) => {
+            if let Some(param) = func.params.items.first() {
+              self.pattern(&param.pattern);
+            }
+          }
+          Some(Expression::FunctionExpression(func)) => {
+            if let Some(param) = func.params.items.first() {
+              self.pattern(&param.pattern);
+            }
+          }
+          _ => self.push(promise_span),
+        }
+      }
+      AstKind::ExpressionStatement(_) => self.returned_from_arrow(parent_id),
+      _ => self.push(promise_span),
+    }
+  }
+
+  /// A namespace (or its promise) bound to `pattern`: follow the binding, or
+  /// match destructured keys. A rest element keeps every remaining export.
+  fn pattern(&mut self, pattern: &oxc_ast::ast::BindingPattern) {
+    match &pattern.kind {
+      oxc_ast::ast::BindingPatternKind::BindingIdentifier(ident) => {
+        if let Some(symbol_id) = ident.symbol_id.get() {
+          self.binding(symbol_id);
+        }
+      }
+      oxc_ast::ast::BindingPatternKind::ObjectPattern(object) => {
+        for property in &object.properties {
+          match property.key.static_name() {
+            Some(name) if name != self.property_name => {}
+            _ => self.push(property.span),
+          }
+        }
+        if let Some(rest) = &object.rest {
+          self.push(rest.span);
+        }
+      }
+      oxc_ast::ast::BindingPatternKind::AssignmentPattern(assignment) => {
+        self.pattern(&assignment.left);
+      }
+      oxc_ast::ast::BindingPatternKind::ArrayPattern(array) => self.push(array.span),
+    }
+  }
+
+  /// A value in expression-statement position is unused, unless the statement
+  /// is the expression body of an arrow function — then it is returned. An
+  /// arrow handed to `lazy()` / `defineAsyncComponent()` only has its `default`
+  /// export read; any other returned namespace escapes.
+  fn returned_from_arrow(&mut self, statement_id: NodeId) {
+    let nodes = self.file_data.semantic().nodes();
+    let statement_span = nodes.kind(statement_id).span();
+    let body_id = nodes.parent_id(statement_id);
+    if !matches!(nodes.kind(body_id), AstKind::FunctionBody(_)) {
+      return;
+    }
+    let arrow_id = nodes.parent_id(body_id);
+    let AstKind::ArrowFunctionExpression(arrow) = nodes.kind(arrow_id) else {
+      return;
+    };
+    if !arrow.expression {
+      return;
+    }
+
+    let call_id = self.parent(arrow_id);
+    let is_lazy_loader = match nodes.kind(call_id) {
+      AstKind::CallExpression(call) => {
+        let callee_name = match &call.callee {
+          Expression::Identifier(ident) => Some(ident.name.as_str()),
+          Expression::StaticMemberExpression(member) => Some(member.property.name.as_str()),
+          _ => None,
+        };
+        matches!(callee_name, Some("lazy" | "defineAsyncComponent"))
+          && call.arguments.first().map(|arg| arg.span()) == Some(arrow.span)
+      }
+      _ => false,
+    };
+
+    if !is_lazy_loader || self.property_name == "default" {
+      self.push(statement_span);
+    }
+  }
+}
+
+/// Name of a class declared at the top level of a module, as
+/// [`WorkspaceAnalyzer::find_node_at_line`] reports it. `None` for class
+/// expressions and classes nested in other code.
+fn top_level_class_name(class: &oxc_ast::ast::Class, parent: AstKind) -> Option<String> {
+  match parent {
+    AstKind::Program(_) | AstKind::ExportNamedDeclaration(_) => {
+      class.id.as_ref().map(|id| id.name.to_string())
+    }
+    AstKind::ExportDefaultDeclaration(_) => Some("default".to_string()),
+    _ => None,
+  }
+}
+
+/// Key of a class member in a `Class#member` symbol path: its static name, or
+/// `#name` for private members. Constructors, static blocks and index
+/// signatures have none — changes there affect the class as a whole.
+fn class_member_key(element: &oxc_ast::ast::ClassElement) -> Option<String> {
+  use oxc_ast::ast::{ClassElement, MethodDefinitionKind, PropertyKey};
+
+  let key = match element {
+    ClassElement::MethodDefinition(method) if method.kind == MethodDefinitionKind::Constructor => {
+      return None;
+    }
+    ClassElement::MethodDefinition(method) => &method.key,
+    ClassElement::PropertyDefinition(property) => &property.key,
+    ClassElement::AccessorProperty(accessor) => &accessor.key,
+    ClassElement::StaticBlock(_) | ClassElement::TSIndexSignature(_) => return None,
+  };
+  match key {
+    PropertyKey::PrivateIdentifier(private) => Some(format!("#{}", private.name)),
+    _ => key.static_name().map(|name| name.to_string()),
+  }
+}
+
+/// Whether a reference with `flags` only reads a type: a type annotation or a
+/// `typeof` query.
+fn is_type_position(flags: oxc_syntax::reference::ReferenceFlags) -> bool {
+  flags.is_type() || flags.is_value_as_type()
+}
+
+/// Whether a declaration whose parent node is `parent` sits at the top level
+/// of the module (exported or not).
+fn is_top_level_declaration(parent: AstKind) -> bool {
+  matches!(
+    parent,
+    AstKind::Program(_) | AstKind::ExportNamedDeclaration(_)
+  )
+}
+
+/// The object literal initializing a top-level `const` declarator, with the
+/// constant's name. `as const` and `satisfies` wrappers are looked through.
+fn top_level_object_literal<'a>(
+  declarator: &'a oxc_ast::ast::VariableDeclarator<'a>,
+  nodes: &oxc_semantic::AstNodes<'a>,
+  declarator_id: NodeId,
+) -> Option<(&'a str, &'a oxc_ast::ast::ObjectExpression<'a>)> {
+  if declarator.kind != oxc_ast::ast::VariableDeclarationKind::Const {
+    return None;
+  }
+  let declaration_id = nodes.parent_id(declarator_id);
+  if !is_top_level_declaration(nodes.parent_kind(declaration_id)) {
+    return None;
+  }
+  let oxc_ast::ast::BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+    return None;
+  };
+  match declarator.init.as_ref()?.get_inner_expression() {
+    Expression::ObjectExpression(object) => Some((ident.name.as_str(), object)),
+    _ => None,
+  }
+}
+
+/// Key of an object literal property in an `OBJECT.key` symbol path. Spreads
+/// and computed keys have none.
+fn object_property_key(property: &oxc_ast::ast::ObjectPropertyKind) -> Option<String> {
+  match property {
+    oxc_ast::ast::ObjectPropertyKind::ObjectProperty(property) if !property.computed => {
+      property.key.static_name().map(|name| name.to_string())
+    }
+    _ => None,
+  }
+}
+
+/// Key of an enum member in an `Enum.Member` symbol path.
+fn enum_member_key(member: &oxc_ast::ast::TSEnumMember) -> Option<String> {
+  match &member.id {
+    oxc_ast::ast::TSEnumMemberName::Identifier(ident) => Some(ident.name.to_string()),
+    oxc_ast::ast::TSEnumMemberName::String(literal) => Some(literal.value.to_string()),
+    _ => None,
+  }
 }
 
 impl WorkspaceAnalyzer {
@@ -654,13 +1208,15 @@ impl WorkspaceAnalyzer {
     let mut visitor = DynamicImportVisitor::new();
     visitor.visit_program(program);
     let dynamic_count = visitor.dynamic_count;
+    let module_url_count = visitor.module_url_count;
     imports.extend(visitor.imports);
 
     debug!(
-      "Extracted {} total imports ({} static, {} dynamic) from {:?}",
+      "Extracted {} total imports ({} static, {} dynamic, {} module URL) from {:?}",
       imports.len(),
       static_count,
       dynamic_count,
+      module_url_count,
       file_path
     );
     imports
@@ -685,6 +1241,7 @@ impl WorkspaceAnalyzer {
               resolved_file: None, // Will be resolved later
               is_type_only: is_type_only || spec.import_kind.is_type(),
               is_dynamic: false,
+              is_module_url: false,
             });
           }
           ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
@@ -695,6 +1252,7 @@ impl WorkspaceAnalyzer {
               resolved_file: None,
               is_type_only,
               is_dynamic: false,
+              is_module_url: false,
             });
           }
           ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
@@ -705,6 +1263,7 @@ impl WorkspaceAnalyzer {
               resolved_file: None,
               is_type_only,
               is_dynamic: false,
+              is_module_url: false,
             });
           }
         }
@@ -715,7 +1274,7 @@ impl WorkspaceAnalyzer {
   }
 
   /// Extract exports from an AST
-  fn extract_exports(program: &oxc_ast::ast::Program) -> Vec<Export> {
+  pub(crate) fn extract_exports(program: &oxc_ast::ast::Program) -> Vec<Export> {
     let mut exports = Vec::new();
 
     for node in program.body.iter() {
@@ -728,14 +1287,22 @@ impl WorkspaceAnalyzer {
             exported_name: "default".to_string(),
             local_name: None,
             re_export_from: None,
+            is_namespace: false,
           });
         }
         oxc_ast::ast::Statement::ExportAllDeclaration(export_all) => {
           let from = export_all.source.value.as_str().to_string();
+          // `export * as ns from '...'` binds the whole module to a single
+          // exported name; a bare `export *` forwards each export as-is.
+          let (exported_name, is_namespace) = match &export_all.exported {
+            Some(exported) => (exported.name().to_string(), true),
+            None => ("*".to_string(), false),
+          };
           exports.push(Export {
-            exported_name: "*".to_string(),
+            exported_name,
             local_name: None,
             re_export_from: Some(from),
+            is_namespace,
           });
         }
         _ => {}
@@ -761,6 +1328,7 @@ impl WorkspaceAnalyzer {
         exported_name,
         local_name,
         re_export_from: re_export_from.clone(),
+        is_namespace: false,
       });
     }
 
@@ -774,6 +1342,7 @@ impl WorkspaceAnalyzer {
                 exported_name: id.name.to_string(),
                 local_name: None,
                 re_export_from: None,
+                is_namespace: false,
               });
             }
           }
@@ -784,6 +1353,7 @@ impl WorkspaceAnalyzer {
               exported_name: id.name.to_string(),
               local_name: None,
               re_export_from: None,
+              is_namespace: false,
             });
           }
         }
@@ -793,6 +1363,7 @@ impl WorkspaceAnalyzer {
               exported_name: id.name.to_string(),
               local_name: None,
               re_export_from: None,
+              is_namespace: false,
             });
           }
         }
@@ -808,6 +1379,43 @@ impl WorkspaceAnalyzer {
     &self,
     file_path: &Path,
     symbol_name: &str,
+  ) -> Result<Vec<Reference>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+    let symbol_ids = Self::symbols_named(file_data, symbol_name);
+
+    self.find_symbol_references(file_path, &symbol_ids)
+  }
+
+  /// The symbols a top-level name refers to in a file.
+  ///
+  /// Prefers the module-scope binding: a `const config` inside a function is
+  /// a different symbol from the top-level `config`, however common the name.
+  /// Names that have no module-scope binding (declarations nested in a
+  /// top-level call such as `describe(() => { function helper() {} })`) fall
+  /// back to every symbol with that name.
+  fn symbols_named(file_data: &FileSemanticData, name: &str) -> Vec<SymbolId> {
+    let scoping = file_data.semantic().scoping();
+    match scoping.get_root_binding(name) {
+      Some(symbol_id) => vec![symbol_id],
+      None => scoping
+        .symbol_ids()
+        .filter(|&symbol_id| scoping.symbol_name(symbol_id) == name)
+        .collect(),
+    }
+  }
+
+  /// Find all references to specific symbols within a file.
+  ///
+  /// Keyed on `SymbolId` rather than name, so shadowing bindings in nested
+  /// scopes are never conflated with the requested ones. Used with
+  /// [`ImportIndexValue::local_symbol`] to follow an import's local binding.
+  pub fn find_symbol_references(
+    &self,
+    file_path: &Path,
+    symbol_ids: &[SymbolId],
   ) -> Result<Vec<Reference>> {
     let start = if self.profiler.is_enabled() {
       Some(Instant::now())
@@ -822,92 +1430,513 @@ impl WorkspaceAnalyzer {
 
     let mut references = Vec::new();
 
-    // Iterate through all symbols in the file
-    for symbol_id in file_data.semantic().scoping().symbol_ids() {
-      let name = file_data.semantic().scoping().symbol_name(symbol_id);
+    for &symbol_id in symbol_ids {
+      // Get all references to this symbol using the Semantic API directly
+      for reference in file_data.semantic().symbol_references(symbol_id) {
+        let span = file_data.semantic().reference_span(reference);
+        let (line, column) = self.span_to_line_col(file_data.source(), span);
+
+        references.push(Reference {
+          file_path: file_path.to_path_buf(),
+          line,
+          column,
+          is_type_only: is_type_position(reference.flags()),
+        });
+      }
+
+      // A component's markup reads the top-level bindings of its script
+      if let Some(template) = file_data.template() {
+        let scoping = file_data.semantic().scoping();
+        if scoping.symbol_scope_id(symbol_id) == scoping.root_scope_id() {
+          if let Some(offset) = template.find_usage(scoping.symbol_name(symbol_id)) {
+            let (line, column) = crate::utils::offset_to_line_col(file_data.source(), offset);
+            references.push(Reference {
+              file_path: file_path.to_path_buf(),
+              line,
+              column,
+              is_type_only: false,
+            });
+          }
+        }
+      }
+    }
+
+    if let Some(start_time) = start {
+      self
+        .profiler
+        .record_local_reference(start_time.elapsed().as_nanos() as u64);
+    }
+
+    Ok(references)
+  }
+
+  /// Find all references to a namespace member access pattern (e.g., `theme.DatePicker`)
+  ///
+  /// This is used for namespace imports like `import * as theme from '...'`
+  /// to check if a specific symbol from the namespace is actually accessed.
+  ///
+  /// Unlike `find_local_references` which finds all references to the namespace identifier,
+  /// this function specifically looks for the places that read the given property:
+  /// `theme.DatePicker`, `theme['DatePicker']`, `const { DatePicker } = theme`, the
+  /// `theme.DatePicker` type name, and member access on aliases (`const t = theme`).
+  ///
+  /// When the namespace object escapes — it is spread, passed to a function,
+  /// returned, stored in another object, or indexed with a non-literal key —
+  /// the escaping site is returned as well, since any export may be read from it.
+  pub fn find_namespace_member_access(
+    &self,
+    file_path: &Path,
+    namespace_name: &str,
+    property_name: &str,
+  ) -> Result<Vec<Reference>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+
+    let mut usage = NamespaceUsage::new(self, file_path, file_data, property_name);
+    for symbol_id in Self::symbols_named(file_data, namespace_name) {
+      usage.binding(symbol_id);
+    }
+    // Globals (`declare const ui`, UMD namespaces) have no binding to follow
+    let scoping = file_data.semantic().scoping();
+    let unresolved: Vec<NodeId> = scoping
+      .root_unresolved_references()
+      .get(namespace_name)
+      .into_iter()
+      .flatten()
+      .map(|&reference_id| scoping.get_reference(reference_id).node_id())
+      .collect();
+    for id in unresolved {
+      usage.value(id);
+    }
+
+    Ok(usage.references)
+  }
+
+  /// Find the places in `file_path` that read `property_name` from the module
+  /// loaded by `import(specifier)`.
+  ///
+  /// Dynamic imports have no binding of their own, so each `import(specifier)`
+  /// expression is followed to wherever its namespace lands:
+  ///
+  /// - `const m = await import('./page')` and `.then(m => …)` callbacks track
+  ///   member access on `m` like [`WorkspaceAnalyzer::find_namespace_member_access`];
+  /// - `const { Page } = await import('./page')` and `.then(({ Page }) => …)`
+  ///   match the destructured keys;
+  /// - `lazy(() => import('./page'))` (React `lazy`, Vue `defineAsyncComponent`)
+  ///   reads the `default` export;
+  /// - a bare `import('./page');` statement reads nothing.
+  ///
+  /// Any other use — returning the promise from a route loader, storing it,
+  /// passing it along — escapes, and the `import()` site is returned for every
+  /// property.
+  pub fn find_dynamic_import_member_access(
+    &self,
+    file_path: &Path,
+    specifier: &str,
+    property_name: &str,
+  ) -> Result<Vec<Reference>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+
+    let mut usage = NamespaceUsage::new(self, file_path, file_data, property_name);
+
+    for node in file_data.semantic().nodes().iter() {
+      if let AstKind::ImportExpression(import_expr) = node.kind() {
+        if matches!(&import_expr.source, Expression::StringLiteral(lit) if lit.value == specifier) {
+          usage.promise(node.id());
+        }
+      }
+    }
+
+    Ok(usage.references)
+  }
+
+  /// Find the `new URL(specifier, import.meta.url)` expressions in a file that
+  /// reference `specifier` (e.g. `new Worker(new URL('./worker.ts', import.meta.url))`).
+  ///
+  /// Module URL imports have no local binding to look up, so the reference is
+  /// the `new URL` expression itself — its enclosing top-level symbol is what
+  /// spawns the worker.
+  pub fn find_module_url_references(
+    &self,
+    file_path: &Path,
+    specifier: &str,
+  ) -> Result<Vec<Reference>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+
+    let mut references = Vec::new();
+
+    for node in file_data.semantic().nodes().iter() {
+      if let AstKind::NewExpression(new_expr) = node.kind() {
+        if module_url_specifier(new_expr) == Some(specifier) {
+          let (line, column) = self.span_to_line_col(file_data.source(), new_expr.span);
+          references.push(Reference {
+            file_path: file_path.to_path_buf(),
+            line,
+            column,
+            is_type_only: false,
+          });
+        }
+      }
+    }
+
+    Ok(references)
+  }
+
+  /// Whether some workspace file loads `file_path` through a
+  /// `new URL('…', import.meta.url)` module reference (a worker entry point).
+  ///
+  /// Code at the top level of such a module (`self.onmessage = …`) has no
+  /// enclosing symbol to trace, yet still changes what the spawning file runs.
+  pub fn is_module_url_entry(&self, file_path: &Path) -> bool {
+    self
+      .import_index
+      .get(&(file_path.to_path_buf(), "*".to_string()))
+      .is_some_and(|importers| importers.iter().any(|importer| importer.is_module_url))
+  }
+
+  /// Resolve the member enclosing `line`/`column` as a symbol path, for
+  /// member-precision mode: `Class#member` for class members, and
+  /// `OBJECT.key` / `Enum.Member` for properties of top-level `const` object
+  /// literals and members of top-level enums.
+  ///
+  /// Declarations are named the same way [`WorkspaceAnalyzer::find_node_at_line`]
+  /// names them (`default` for `export default class`). Column 0 means the
+  /// whole line, which must fall inside a single member. Constructors, static
+  /// blocks, spreads, computed keys, the declaration header and anything
+  /// spanning several members return `None`: those changes affect the
+  /// declaration as a whole. So does any change to an object literal that uses
+  /// `this`, since its properties can then read each other.
+  pub fn find_member_at_line(
+    &self,
+    file_path: &Path,
+    line: usize,
+    column: usize,
+  ) -> Result<Option<String>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+    let source = file_data.source();
+
+    let line_start = crate::utils::line_to_offset(source, line)
+      .ok_or_else(|| DominoError::Other(format!("Invalid line number: {}", line)))?;
+    let (range_start, range_end) = if column == 0 {
+      let line_end = crate::utils::line_to_offset(source, line + 1).unwrap_or(source.len());
+      let text = &source[line_start..line_end];
+      let trimmed_start = line_start + (text.len() - text.trim_start().len());
+      // A trailing comma separates object and enum members but isn't part of either
+      let trimmed_end = line_start + text.trim_end().trim_end_matches(',').len();
+      if trimmed_start >= trimmed_end {
+        return Ok(None);
+      }
+      (trimmed_start as u32, trimmed_end as u32)
+    } else {
+      let offset = (line_start + column) as u32;
+      (offset, offset)
+    };
+
+    let contains = |span: Span| span.start <= range_start && range_end <= span.end;
+    let nodes = file_data.semantic().nodes();
+    for node in nodes.iter() {
+      match node.kind() {
+        AstKind::Class(class) if contains(class.span) => {
+          let Some(class_name) = top_level_class_name(class, nodes.parent_kind(node.id())) else {
+            continue;
+          };
+          return Ok(
+            class
+              .body
+              .body
+              .iter()
+              .find(|element| contains(element.span()))
+              .and_then(class_member_key)
+              .map(|member| format!("{}#{}", class_name, member)),
+          );
+        }
+        AstKind::VariableDeclarator(declarator) if contains(declarator.span) => {
+          let Some((object_name, object)) = top_level_object_literal(declarator, nodes, node.id())
+          else {
+            continue;
+          };
+          let uses_this = nodes.iter().any(|node| match node.kind() {
+            AstKind::ThisExpression(this) => object.span.contains_inclusive(this.span),
+            _ => false,
+          });
+          if uses_this {
+            return Ok(None);
+          }
+          return Ok(
+            object
+              .properties
+              .iter()
+              .find(|property| contains(property.span()))
+              .and_then(object_property_key)
+              .map(|key| format!("{}.{}", object_name, key)),
+          );
+        }
+        AstKind::TSEnumDeclaration(enum_decl) if contains(enum_decl.span) => {
+          if !is_top_level_declaration(nodes.parent_kind(node.id())) {
+            continue;
+          }
+          return Ok(
+            enum_decl
+              .body
+              .members
+              .iter()
+              .find(|member| contains(member.span))
+              .and_then(enum_member_key)
+              .map(|key| format!("{}.{}", enum_decl.id.name, key)),
+          );
+        }
+        _ => {}
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Members of the top-level class `class_name` whose bodies read `member`
+  /// through `this.member` (or `ClassName.member` for statics), as symbol
+  /// paths. A constructor or static block that reads it yields the bare class
+  /// name, since that change reaches every instance.
+  pub fn find_class_member_dependents(
+    &self,
+    file_path: &Path,
+    class_name: &str,
+    member: &str,
+  ) -> Result<Vec<String>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+    let nodes = file_data.semantic().nodes();
+
+    let Some(class) = nodes.iter().find_map(|node| match node.kind() {
+      AstKind::Class(class)
+        if top_level_class_name(class, nodes.parent_kind(node.id())).as_deref()
+          == Some(class_name) =>
+      {
+        Some(class)
+      }
+      _ => None,
+    }) else {
+      return Ok(Vec::new());
+    };
+    let local_class_name = class.id.as_ref().map(|id| id.name.as_str());
+
+    let reads_member = |object: &Expression| match object {
+      Expression::ThisExpression(_) => true,
+      Expression::Identifier(ident) => Some(ident.name.as_str()) == local_class_name,
+      _ => false,
+    };
+    let access_spans: Vec<Span> = nodes
+      .iter()
+      .filter_map(|node| match node.kind() {
+        AstKind::StaticMemberExpression(access)
+          if access.property.name == member && reads_member(&access.object) =>
+        {
+          Some(access.span)
+        }
+        AstKind::PrivateFieldExpression(access)
+          if member.strip_prefix('#') == Some(access.field.name.as_str())
+            && reads_member(&access.object) =>
+        {
+          Some(access.span)
+        }
+        _ => None,
+      })
+      .filter(|span| class.span.contains_inclusive(*span))
+      .collect();
+
+    let mut dependents = Vec::new();
+    for element in &class.body.body {
+      let element_span = element.span();
+      if !access_spans
+        .iter()
+        .any(|span| element_span.contains_inclusive(*span))
+      {
+        continue;
+      }
+      let dependent = match class_member_key(element) {
+        Some(key) if key == member => continue,
+        Some(key) => format!("{}#{}", class_name, key),
+        None => class_name.to_string(),
+      };
+      if !dependents.contains(&dependent) {
+        dependents.push(dependent);
+      }
+    }
+
+    Ok(dependents)
+  }
+
+  /// Members of the top-level enum `enum_name` whose value depends on
+  /// `member`, as `Enum.Member` symbol paths: the member right after it when
+  /// that one is auto-incremented, and members whose initializer names it
+  /// (`ReadWrite = Read | Write`). Reads through `Enum.member` are ordinary
+  /// references and are found by reference tracing instead.
+  pub fn find_enum_member_dependents(
+    &self,
+    file_path: &Path,
+    enum_name: &str,
+    member: &str,
+  ) -> Result<Vec<String>> {
+    let file_data = self
+      .files
+      .get(file_path)
+      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+    let nodes = file_data.semantic().nodes();
+
+    let Some(enum_decl) = nodes.iter().find_map(|node| match node.kind() {
+      AstKind::TSEnumDeclaration(enum_decl)
+        if enum_decl.id.name == enum_name
+          && is_top_level_declaration(nodes.parent_kind(node.id())) =>
+      {
+        Some(enum_decl)
+      }
+      _ => None,
+    }) else {
+      return Ok(Vec::new());
+    };
 
-      if name == symbol_name {
-        // Get all references to this symbol using the Semantic API directly
-        for reference in file_data.semantic().symbol_references(symbol_id) {
-          let span = file_data.semantic().reference_span(reference);
-          let (line, column) = self.span_to_line_col(file_data.source(), span);
+    let name_spans: Vec<Span> = nodes
+      .iter()
+      .filter_map(|node| match node.kind() {
+        AstKind::IdentifierReference(ident) if ident.name == member => Some(ident.span),
+        _ => None,
+      })
+      .collect();
 
-          references.push(Reference {
-            file_path: file_path.to_path_buf(),
-            line,
-            column,
-          });
-        }
+    let members = &enum_decl.body.members;
+    let mut dependents = Vec::new();
+    for (index, enum_member) in members.iter().enumerate() {
+      let Some(key) = enum_member_key(enum_member) else {
+        continue;
+      };
+      if key == member {
+        continue;
+      }
+      let follows_member = index > 0
+        && enum_member.initializer.is_none()
+        && enum_member_key(&members[index - 1]).as_deref() == Some(member);
+      let names_member = enum_member.initializer.as_ref().is_some_and(|initializer| {
+        name_spans
+          .iter()
+          .any(|span| initializer.span().contains_inclusive(*span))
+      });
+      if follows_member || names_member {
+        dependents.push(format!("{}.{}", enum_name, key));
       }
     }
 
-    if let Some(start_time) = start {
-      self
-        .profiler
-        .record_local_reference(start_time.elapsed().as_nanos() as u64);
-    }
-
-    Ok(references)
+    Ok(dependents)
   }
 
-  /// Find all references to a namespace member access pattern (e.g., `theme.DatePicker`)
-  ///
-  /// This is used for namespace imports like `import * as theme from '...'`
-  /// to check if a specific symbol from the namespace is actually accessed.
+  /// Narrow a reference to a class (as returned by `find_local_references` or
+  /// `ReferenceFinder`) to the places that actually reach `member`.
   ///
-  /// Unlike `find_local_references` which finds all references to the namespace identifier,
-  /// this function specifically looks for member expressions where the namespace is accessed
-  /// with the given property name.
-  pub fn find_namespace_member_access(
+  /// Static reads (`ApiClient.create`) match by name, and instances created
+  /// with `new` are followed like namespace objects (`new ApiClient().getUser`,
+  /// `const client = new ApiClient(); client.getUser()`). Anything that could
+  /// reach an arbitrary member — subclassing, type annotations, passing the
+  /// class or an instance along — is returned as-is. When the reference can't
+  /// be matched to an identifier for the class it is also returned unchanged.
+  pub fn find_class_member_usage(
     &self,
-    file_path: &Path,
-    namespace_name: &str,
-    property_name: &str,
+    reference: &Reference,
+    class_name: &str,
+    member: &str,
+  ) -> Result<Vec<Reference>> {
+    self.find_member_usage(reference, class_name, member, |usage, id| {
+      usage.class_value(id)
+    })
+  }
+
+  /// Narrow a reference to a top-level object literal or enum to the places
+  /// that read `property` from it: static and literal computed member access
+  /// (`ROUTES.billing`, `Permission.Read` in value or type position) and
+  /// destructuring. Whole-object uses — spreading it, passing it along,
+  /// `Object.keys`, `typeof` — are returned as-is.
+  pub fn find_property_usage(
+    &self,
+    reference: &Reference,
+    object_name: &str,
+    property: &str,
+  ) -> Result<Vec<Reference>> {
+    self.find_member_usage(reference, object_name, property, |usage, id| {
+      usage.value(id)
+    })
+  }
+
+  /// Shared by [`WorkspaceAnalyzer::find_class_member_usage`] and
+  /// [`WorkspaceAnalyzer::find_property_usage`]: locate the identifier at
+  /// `reference` and hand the expression holding `owner` to `follow`.
+  fn find_member_usage(
+    &self,
+    reference: &Reference,
+    owner: &str,
+    member: &str,
+    follow: fn(&mut NamespaceUsage, NodeId),
   ) -> Result<Vec<Reference>> {
     let file_data = self
       .files
-      .get(file_path)
-      .ok_or_else(|| DominoError::FileNotFound(file_path.display().to_string()))?;
+      .get(&reference.file_path)
+      .ok_or_else(|| DominoError::FileNotFound(reference.file_path.display().to_string()))?;
+    let source = file_data.source();
+    let semantic = file_data.semantic();
+    let nodes = semantic.nodes();
+
+    // Reference columns are 1-based
+    let Some(offset) = crate::utils::line_to_offset(source, reference.line)
+      .map(|line_start| (line_start + reference.column.saturating_sub(1)) as u32)
+    else {
+      return Ok(vec![reference.clone()]);
+    };
 
-    let mut references = Vec::new();
+    let Some((ident_id, ident)) = nodes.iter().find_map(|node| match node.kind() {
+      AstKind::IdentifierReference(ident) if ident.span.start == offset => Some((node.id(), ident)),
+      _ => None,
+    }) else {
+      return Ok(vec![reference.clone()]);
+    };
 
-    for node in file_data.semantic().nodes().iter() {
-      match node.kind() {
-        AstKind::StaticMemberExpression(member_expr)
-          if member_expr.property.name.as_str() == property_name =>
-        {
-          if let Expression::Identifier(ident) = &member_expr.object {
-            if ident.name.as_str() == namespace_name {
-              let span = member_expr.span;
-              let (line, column) = self.span_to_line_col(file_data.source(), span);
-              references.push(Reference {
-                file_path: file_path.to_path_buf(),
-                line,
-                column,
-              });
-            }
-          }
-        }
-        AstKind::TSQualifiedName(qualified_name)
-          if qualified_name.right.name.as_str() == property_name =>
+    // Which kind of binding the identifier reads: the declaration itself, or a
+    // namespace object it is a member of (`lib.ApiClient`)
+    let declaration = ident
+      .reference_id
+      .get()
+      .and_then(|reference_id| semantic.scoping().get_reference(reference_id).symbol_id())
+      .map(|symbol_id| nodes.kind(semantic.scoping().symbol_declaration(symbol_id)));
+    let is_owner = match declaration {
+      Some(AstKind::ImportSpecifier(specifier)) => specifier.imported.name() == owner,
+      Some(AstKind::ImportDefaultSpecifier(_)) => owner == "default",
+      Some(AstKind::ImportNamespaceSpecifier(_)) => false,
+      _ => ident.name == owner,
+    };
+
+    let mut usage = NamespaceUsage::new(self, &reference.file_path, file_data, member);
+    if is_owner {
+      follow(&mut usage, ident_id);
+    } else {
+      match nodes.parent_kind(ident_id) {
+        AstKind::StaticMemberExpression(access)
+          if access.object.span() == ident.span && access.property.name == owner =>
         {
-          if let oxc_ast::ast::TSTypeName::IdentifierReference(ident) = &qualified_name.left {
-            if ident.name.as_str() == namespace_name {
-              let span = qualified_name.span;
-              let (line, column) = self.span_to_line_col(file_data.source(), span);
-              references.push(Reference {
-                file_path: file_path.to_path_buf(),
-                line,
-                column,
-              });
-            }
-          }
+          follow(&mut usage, nodes.parent_id(ident_id));
         }
-        _ => {}
+        _ => return Ok(vec![reference.clone()]),
       }
     }
 
-    Ok(references)
+    Ok(usage.references)
   }
 
   /// Convert span to line and column
@@ -979,11 +2008,13 @@ impl WorkspaceAnalyzer {
   }
 
   /// Get all exported symbols that use a given local symbol
-  /// This is used to find which exported APIs are affected when an internal symbol changes
+  /// This is used to find which exported APIs are affected when an internal symbol changes.
+  /// With `include_type_only` false, uses in type positions don't count.
   pub fn find_exported_symbols_using(
     &self,
     file_path: &Path,
     local_symbol: &str,
+    include_type_only: bool,
   ) -> Result<Vec<String>> {
     let mut exported_symbols = Vec::new();
 
@@ -1012,7 +2043,10 @@ impl WorkspaceAnalyzer {
     // Build a set of container symbols that reference the local symbol
     // This is O(refs) instead of O(exports × refs)
     let mut containers = FxHashSet::default();
-    for reference in refs {
+    for reference in refs
+      .into_iter()
+      .filter(|reference| include_type_only || !reference.is_type_only)
+    {
       let containers_on_line =
         self.find_node_at_line(file_path, reference.line, reference.column)?;
       for container in containers_on_line {
@@ -1096,6 +2130,14 @@ impl WorkspaceAnalyzer {
       .unwrap_or(file_data.source().len());
     let line_end_inclusive = line_end.saturating_sub(1);
 
+    // A component's markup belongs to its default export
+    if file_data
+      .template()
+      .is_some_and(|template| !template.is_script(line_start..line_end_inclusive))
+    {
+      return Ok(vec!["default".to_string()]);
+    }
+
     let specifier_names_on_line = |export_decl: &ExportNamedDeclaration| -> Vec<String> {
       export_decl
         .specifiers
@@ -1190,6 +2232,13 @@ impl WorkspaceAnalyzer {
         found_export_wrapper = true;
         top_level_name = Some("default".to_string());
       }
+      AstKind::ExportAllDeclaration(export_all) => {
+        // `export * as ns from '...'` changes the `ns` binding; a bare
+        // `export *` has no name of its own to track.
+        if let Some(exported) = &export_all.exported {
+          return Ok(vec![exported.name().to_string()]);
+        }
+      }
       AstKind::VariableDeclaration(var_decl) => {
         // Handle non-exported variable declarations (e.g., `const x = ...`).
         // At column 0 the cursor lands on the `const`/`let`/`var` keyword, which is
@@ -1236,6 +2285,11 @@ impl WorkspaceAnalyzer {
           found_export_wrapper = true;
           top_level_name = Some("default".to_string());
         }
+        AstKind::ExportAllDeclaration(export_all) => {
+          if let Some(exported) = &export_all.exported {
+            return Ok(vec![exported.name().to_string()]);
+          }
+        }
         // Top-level declarations that can be exported
         AstKind::Function(func) if !found_export_wrapper => {
           if let Some(id) = &func.id {
@@ -1444,6 +2498,7 @@ export { MemoizedComponent };"#;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -1501,6 +2556,7 @@ export const MY_REGEX = new RegExp(combined)"#;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -1551,6 +2607,7 @@ var localVar = 2"#;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -1686,6 +2743,7 @@ export const Kept = 2;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -1910,6 +2968,70 @@ const DynamicImport = await import('./dynamic');
     assert_eq!(dynamic_imports[0].imported_name, "*");
   }
 
+  #[test]
+  fn test_extract_module_url_references() {
+    let source = r#"
+const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });
+const shared = new SharedWorker(new URL('./shared', import.meta.url));
+navigator.serviceWorker.register(new URL('./sw.js', import.meta.url));
+const workletUrl = new URL('./worklet.mts', import.meta.url);
+
+// Assets and non-`import.meta.url` bases are not module references
+const logo = new URL('./logo.svg', import.meta.url);
+const remote = new URL('./remote.ts', 'https://example.com');
+const bare = new URL('./bare', import.meta.url);
+"#;
+
+    let file_path = Path::new("main.ts");
+    let source_type = SourceType::from_path(file_path)
+      .unwrap_or_else(|_| SourceType::default().with_typescript(true));
+    let allocator = Allocator::default();
+    let parser = Parser::new(&allocator, source, source_type);
+    let parse_result = parser.parse();
+
+    let imports = WorkspaceAnalyzer::extract_imports(&parse_result.program, file_path);
+
+    let mut modules: Vec<_> = imports.iter().map(|imp| imp.from_module.as_str()).collect();
+    modules.sort();
+    assert_eq!(
+      modules,
+      vec!["./shared", "./sw.js", "./worker.ts", "./worklet.mts"]
+    );
+    for imp in &imports {
+      assert!(imp.is_module_url);
+      assert!(!imp.is_dynamic);
+      assert_eq!(imp.imported_name, "*");
+    }
+  }
+
+  #[test]
+  fn test_find_module_url_references() {
+    let source = r#"export function startWorker() {
+  return new Worker(new URL('./worker.ts', import.meta.url));
+}
+
+const other = new URL('./other.ts', import.meta.url);
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "spawn.ts");
+
+    let refs = analyzer
+      .find_module_url_references(&file_path, "./worker.ts")
+      .expect("Should not error");
+    assert_eq!(refs.len(), 1);
+    assert_eq!(refs[0].line, 2);
+    assert_eq!(
+      analyzer
+        .find_node_at_line(&file_path, refs[0].line, refs[0].column)
+        .unwrap(),
+      vec!["startWorker".to_string()]
+    );
+
+    assert!(analyzer
+      .find_module_url_references(&file_path, "./missing.ts")
+      .unwrap()
+      .is_empty());
+  }
+
   #[test]
   fn test_find_node_at_line_export_default_named() {
     // Test finding a named default export
@@ -1943,6 +3065,7 @@ const DynamicImport = await import('./dynamic');
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -1989,6 +3112,7 @@ const DynamicImport = await import('./dynamic');
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -2034,6 +3158,7 @@ export const { a, b } = obj;"#;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -2079,6 +3204,7 @@ export function third() {
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -2139,6 +3265,7 @@ type Props = ui.ButtonProps;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -2207,6 +3334,401 @@ type Props = ui.ButtonProps;
     );
   }
 
+  #[test]
+  fn test_find_namespace_member_access_destructuring_computed_and_escape() {
+    let source = r#"import * as utils from './utils';
+import * as other from './other';
+
+const { formatDate, slugify: toSlug } = utils;
+const parse = utils['parseDate'];
+const alias = utils;
+const viaAlias = alias.trim;
+const unknownKey = other[key];
+register(other);
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "consumer.ts");
+    let lines = |namespace: &str, property: &str| -> Vec<usize> {
+      analyzer
+        .find_namespace_member_access(&file_path, namespace, property)
+        .expect("Should not error")
+        .iter()
+        .map(|r| r.line)
+        .collect()
+    };
+
+    assert_eq!(lines("utils", "formatDate"), vec![4]);
+    assert_eq!(lines("utils", "slugify"), vec![4]);
+    assert_eq!(lines("utils", "parseDate"), vec![5]);
+    assert_eq!(lines("utils", "trim"), vec![7]);
+    assert!(lines("utils", "unused").is_empty());
+
+    // `other` escapes through a non-literal key and a call argument
+    assert_eq!(lines("other", "anything"), vec![8, 9]);
+  }
+
+  #[test]
+  fn test_find_dynamic_import_member_access() {
+    let source = r#"export async function awaited() {
+  const page = await import('./page');
+  return page.Page;
+}
+
+export async function destructured() {
+  const { Header } = await import('./page');
+  return Header;
+}
+
+export const viaThen = () => import('./page').then((m) => m.Footer);
+
+export const Lazy = lazy(() => import('./page'));
+
+export const routes = [{ load: () => import('./page') }];
+
+import('./page');
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "routes.tsx");
+    let lines = |property: &str| -> Vec<usize> {
+      let mut lines: Vec<usize> = analyzer
+        .find_dynamic_import_member_access(&file_path, "./page", property)
+        .expect("Should not error")
+        .iter()
+        .map(|r| r.line)
+        .collect();
+      lines.sort();
+      lines
+    };
+
+    // Line 15 is the escaping route loader, which reaches every export
+    assert_eq!(lines("Page"), vec![3, 15]);
+    assert_eq!(lines("Header"), vec![7, 15]);
+    assert_eq!(lines("Footer"), vec![11, 15]);
+    assert_eq!(lines("default"), vec![13, 15]);
+    assert_eq!(lines("Unused"), vec![15]);
+
+    assert!(analyzer
+      .find_dynamic_import_member_access(&file_path, "./missing", "Page")
+      .expect("Should not error")
+      .is_empty());
+  }
+
+  #[test]
+  fn test_find_local_references_excludes_shadowed_bindings() {
+    let source = r#"export const config = { retries: 3 };
+
+export function useConfig() {
+  return config.retries;
+}
+
+export function build(options) {
+  const config = { ...options };
+  return config;
+}
+
+describe('suite', () => {
+  function helper() {
+    return 1;
+  }
+  helper();
+});
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "config.ts");
+    let lines = |name: &str| -> Vec<usize> {
+      analyzer
+        .find_local_references(&file_path, name)
+        .expect("Should not error")
+        .iter()
+        .map(|r| r.line)
+        .collect()
+    };
+
+    // Only the top-level `config`; the one local to build() is a different symbol
+    assert_eq!(lines("config"), vec![4]);
+    // Names without a module-scope binding still resolve
+    assert_eq!(lines("helper"), vec![16]);
+  }
+
+  #[test]
+  fn test_build_import_index_records_local_symbol() {
+    let (_tmp, analyzer) = analyzer_over_files(&[
+      ("src/utils.ts", "export const data = 1;\n"),
+      (
+        "src/consumer.ts",
+        "import { data as shared } from './utils';\n\nexport function read(data: number) {\n  return data;\n}\n\nexport const value = shared;\n",
+      ),
+    ]);
+
+    let importers = analyzer
+      .import_index
+      .get(&(PathBuf::from("src/utils.ts"), "data".to_string()))
+      .expect("data should be imported");
+    assert_eq!(importers.len(), 1);
+    let importer = &importers[0];
+    assert_eq!(importer.local_name, "shared");
+    let symbol_id = importer
+      .local_symbol
+      .expect("static imports bind a module-scope symbol");
+
+    let refs = analyzer
+      .find_symbol_references(&importer.importing_file, &[symbol_id])
+      .expect("Should not error");
+    assert_eq!(refs.iter().map(|r| r.line).collect::<Vec<_>>(), vec![7]);
+  }
+
+  #[test]
+  fn test_references_flag_type_positions() {
+    let source = r#"import * as models from './models';
+import { Store } from './store';
+
+export function load(store: Store): models.User {
+  return new Store().load() as models.User;
+}
+
+export const kind = models.kind;
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "consumer.ts");
+
+    let store_refs: Vec<(usize, bool)> = analyzer
+      .find_local_references(&file_path, "Store")
+      .expect("Should not error")
+      .iter()
+      .map(|r| (r.line, r.is_type_only))
+      .collect();
+    assert_eq!(store_refs, vec![(4, true), (5, false)]);
+
+    let type_only = |member: &str| -> Vec<bool> {
+      analyzer
+        .find_namespace_member_access(&file_path, "models", member)
+        .expect("Should not error")
+        .iter()
+        .map(|r| r.is_type_only)
+        .collect()
+    };
+    assert_eq!(type_only("User"), vec![true, true]);
+    assert_eq!(type_only("kind"), vec![false]);
+  }
+
+  #[test]
+  fn test_find_exported_symbols_using_type_only() {
+    let (_tmp, analyzer) = analyzer_over_files(&[(
+      "src/store.ts",
+      "const store = { items: [] };\n\nexport const snapshot = (value: typeof store) => value;\nexport const items = () => store.items;\n",
+    )]);
+    let file_path = PathBuf::from("src/store.ts");
+
+    let exported = |include_type_only: bool| {
+      let mut exported = analyzer
+        .find_exported_symbols_using(&file_path, "store", include_type_only)
+        .expect("Should not error");
+      exported.sort();
+      exported
+    };
+    assert_eq!(
+      exported(true),
+      vec!["items".to_string(), "snapshot".to_string()]
+    );
+    assert_eq!(exported(false), vec!["items".to_string()]);
+  }
+
+  #[test]
+  fn test_find_member_at_line_and_dependents() {
+    let source = r#"export class ApiClient {
+  static base = '/api';
+
+  constructor() {
+    this.cache = new Map();
+  }
+
+  getUser(id) {
+    return this.request(`/users/${id}`);
+  }
+
+  listOrders() {
+    return this.request('/orders');
+  }
+
+  request(path) {
+    return fetch(ApiClient.base + path);
+  }
+}
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "client.ts");
+    let member_at = |line: usize| {
+      analyzer
+        .find_member_at_line(&file_path, line, 0)
+        .expect("Should not error")
+    };
+
+    assert_eq!(member_at(9), Some("ApiClient#getUser".to_string()));
+    assert_eq!(member_at(13), Some("ApiClient#listOrders".to_string()));
+    assert_eq!(member_at(2), Some("ApiClient#base".to_string()));
+    // Constructor and class header changes affect the whole class
+    assert_eq!(member_at(5), None);
+    assert_eq!(member_at(1), None);
+
+    let dependents = |member: &str| {
+      let mut dependents = analyzer
+        .find_class_member_dependents(&file_path, "ApiClient", member)
+        .expect("Should not error");
+      dependents.sort();
+      dependents
+    };
+    assert_eq!(
+      dependents("request"),
+      vec![
+        "ApiClient#getUser".to_string(),
+        "ApiClient#listOrders".to_string()
+      ]
+    );
+    assert_eq!(dependents("base"), vec!["ApiClient#request".to_string()]);
+    assert_eq!(dependents("cache"), vec!["ApiClient".to_string()]);
+    assert!(dependents("getUser").is_empty());
+  }
+
+  #[test]
+  fn test_find_member_at_line_objects_and_enums() {
+    let source = r#"export const ROUTES = {
+  billing: '/billing',
+  settings: {
+    profile: '/settings/profile',
+  },
+  ...LEGACY_ROUTES,
+} as const;
+
+export enum Permission {
+  Read = 1,
+  Write,
+  ReadWrite = Read | Write,
+  Admin = 8,
+}
+
+export const counter = {
+  count: 0,
+  increment() {
+    this.count++;
+  },
+};
+
+export function build() {
+  const local = { key: 1 };
+  return local;
+}
+"#;
+    let (analyzer, file_path) = create_analyzer_with_file(source, "constants.ts");
+    let member_at = |line: usize| {
+      analyzer
+        .find_member_at_line(&file_path, line, 0)
+        .expect("Should not error")
+    };
+
+    assert_eq!(member_at(2), Some("ROUTES.billing".to_string()));
+    assert_eq!(member_at(4), Some("ROUTES.settings".to_string()));
+    assert_eq!(member_at(10), Some("Permission.Read".to_string()));
+    assert_eq!(member_at(13), Some("Permission.Admin".to_string()));
+    // Spreads, headers, objects using `this` and non-top-level objects
+    assert_eq!(member_at(6), None);
+    assert_eq!(member_at(1), None);
+    assert_eq!(member_at(17), None);
+    assert_eq!(member_at(24), None);
+
+    let dependents = |member: &str| {
+      analyzer
+        .find_enum_member_dependents(&file_path, "Permission", member)
+        .expect("Should not error")
+    };
+    // Write is auto-incremented from Read; ReadWrite names both
+    assert_eq!(
+      dependents("Read"),
+      vec![
+        "Permission.Write".to_string(),
+        "Permission.ReadWrite".to_string()
+      ]
+    );
+    assert_eq!(
+      dependents("Write"),
+      vec!["Permission.ReadWrite".to_string()]
+    );
+    assert!(dependents("Admin").is_empty());
+  }
+
+  #[test]
+  fn test_find_property_usage() {
+    let (_tmp, analyzer) = analyzer_over_files(&[
+      (
+        "src/constants.ts",
+        "export const ROUTES = { billing: '/billing', settings: '/settings' };\n\nexport enum Permission {\n  Read = 1,\n  Write = 2,\n}\n",
+      ),
+      (
+        "src/consumer.ts",
+        "import { ROUTES, Permission } from './constants';\n\nexport const billing = ROUTES.billing;\nexport const { settings } = ROUTES;\nexport const keys = Object.keys(ROUTES);\nexport const canRead = (p: Permission.Read) => p === Permission.Read;\n",
+      ),
+    ]);
+
+    let consumer = PathBuf::from("src/consumer.ts");
+    let usage_lines = |object: &str, property: &str| {
+      let mut lines: Vec<usize> = analyzer
+        .find_local_references(&consumer, object)
+        .expect("Should not error")
+        .iter()
+        .flat_map(|r| {
+          analyzer
+            .find_property_usage(r, object, property)
+            .expect("Should not error")
+        })
+        .map(|r| r.line)
+        .collect();
+      lines.sort();
+      lines.dedup();
+      lines
+    };
+
+    // Line 5 passes the whole object along, so it reads every property
+    assert_eq!(usage_lines("ROUTES", "billing"), vec![3, 5]);
+    assert_eq!(usage_lines("ROUTES", "settings"), vec![4, 5]);
+    assert_eq!(usage_lines("ROUTES", "unused"), vec![5]);
+    // Enum members are read in both type and value position
+    assert_eq!(usage_lines("Permission", "Read"), vec![6]);
+    assert!(usage_lines("Permission", "Write").is_empty());
+  }
+
+  #[test]
+  fn test_find_class_member_usage() {
+    let (_tmp, analyzer) = analyzer_over_files(&[
+      (
+        "src/client.ts",
+        "export class ApiClient {\n  getUser() {}\n  listOrders() {}\n}\n",
+      ),
+      (
+        "src/consumer.ts",
+        "import { ApiClient } from './client';\n\nexport const orders = () => new ApiClient().listOrders();\n\nexport function user() {\n  const client = new ApiClient();\n  return client.getUser();\n}\n\nexport class Admin extends ApiClient {}\n",
+      ),
+    ]);
+
+    let consumer = PathBuf::from("src/consumer.ts");
+    let class_refs = analyzer
+      .find_local_references(&consumer, "ApiClient")
+      .expect("Should not error");
+    assert_eq!(class_refs.len(), 3);
+
+    let usage_lines = |member: &str| {
+      let mut lines: Vec<usize> = class_refs
+        .iter()
+        .flat_map(|r| {
+          analyzer
+            .find_class_member_usage(r, "ApiClient", member)
+            .expect("Should not error")
+        })
+        .map(|r| r.line)
+        .collect();
+      lines.sort();
+      lines
+    };
+
+    // Line 10 subclasses ApiClient, so it reaches every member
+    assert_eq!(usage_lines("getUser"), vec![7, 10]);
+    assert_eq!(usage_lines("listOrders"), vec![3, 10]);
+    assert_eq!(usage_lines("unused"), vec![10]);
+  }
+
   /// Helper to create an analyzer with a single parsed file
   fn create_analyzer_with_file(source: &str, file_name: &str) -> (WorkspaceAnalyzer, PathBuf) {
     let cwd = Path::new(".");
@@ -2235,6 +3757,7 @@ type Props = ui.ButtonProps;
         source: source.to_string(),
         allocator,
         semantic,
+        template: None,
       },
     );
 
@@ -2292,6 +3815,18 @@ type Props = ui.ButtonProps;
     assert!(result.is_empty());
   }
 
+  #[test]
+  fn test_find_node_at_line_namespace_reexport() {
+    let source = "export * as utils from './utils';\n";
+    let (analyzer, file_path) = create_analyzer_with_file(source, "barrel.ts");
+
+    // The namespace binding is the changed symbol.
+    let result = analyzer
+      .find_node_at_line(&file_path, 1, 0)
+      .expect("Should not error");
+    assert_eq!(result, vec!["utils".to_string()]);
+  }
+
   #[test]
   fn test_find_node_at_line_inline_export_still_works() {
     // Ensure the fix didn't break inline exports like `export const X = ...`
@@ -2326,6 +3861,7 @@ type Props = ui.ButtonProps;
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     };
 
     let profiler = Arc::new(Profiler::new(false));
@@ -2435,6 +3971,25 @@ type Props = ui.ButtonProps;
     );
   }
 
+  #[test]
+  fn test_build_reexport_index_namespace_reexport() {
+    let (_tmp, analyzer) = analyzer_over_files(&[
+      ("src/utils.ts", "export const formatDate = 1;\n"),
+      ("src/index.ts", "export * as utils from './utils';\n"),
+    ]);
+
+    let entries = analyzer
+      .reexport_index
+      .get(Path::new("src/utils.ts"))
+      .expect("utils.ts should be re-exported");
+    assert_eq!(entries.len(), 1);
+    let (file, export) = &entries[0];
+    assert_eq!(file, Path::new("src/index.ts"));
+    assert_eq!(export.exported_name, "utils");
+    assert!(export.is_namespace);
+    assert_eq!(export.re_export_from.as_deref(), Some("./utils"));
+  }
+
   #[test]
   fn test_build_reexport_index_keys_match_analyzer_paths() {
     // The index keys must be the same workspace-relative paths used everywhere else
@@ -2525,6 +4080,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "lib-b".to_string(),
@@ -2533,6 +4089,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app".to_string(),
@@ -2541,6 +4098,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -2562,7 +4120,7 @@ export function run() {
 
     let mut importer_files: Vec<PathBuf> = helper_a_importers
       .iter()
-      .map(|(file, _local_name, _from_module, _is_dynamic)| file.clone())
+      .map(|importer| importer.importing_file.clone())
       .collect();
     importer_files.sort();
     assert_eq!(
@@ -2579,8 +4137,8 @@ export function run() {
       .get(&helper_b_key)
       .unwrap_or_else(|| panic!("Expected import index entry for {:?}", helper_b_key));
     assert_eq!(helper_b_importers.len(), 1);
-    assert_eq!(helper_b_importers[0].0, app_main);
-    assert_eq!(helper_b_importers[0].1, "helperB");
+    assert_eq!(helper_b_importers[0].importing_file, app_main);
+    assert_eq!(helper_b_importers[0].local_name, "helperB");
 
     // CONST_A is never imported anywhere, so it must not appear in the index.
     let const_a_key = (lib_a_index, "CONST_A".to_string());
@@ -0,0 +1,352 @@
+//! Angular template usages of components, directives and pipes.
+//!
+//! Templates consume declarations through selectors (`<app-user-card>`,
+//! `<button appTooltip>`) and pipe names (`| currency`) rather than imports,
+//! so an NgModule-declared component used in another component's template
+//! has no import edge to follow. The `@Component` / `@Directive` / `@Pipe`
+//! metadata of every parsed file is collected, inline `template`s and
+//! `templateUrl` files are scanned, and each match links the declaration to
+//! the class owning the template. Matching is workspace-wide: NgModule and
+//! standalone `imports` scoping is not modelled.
+
+use super::analyzer::FileSemanticData;
+use super::static_string;
+use crate::utils::offset_to_line_col;
+use oxc_ast::ast::{Class, Declaration, Expression, ObjectPropertyKind, Statement};
+use rustc_hash::FxHashMap;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// A template reference to a declaration: the template's owning file and the
+/// line of its component class
+pub type TemplateUsage = (PathBuf, usize);
+
+/// (declaring file, class name) -> template usages
+pub type TemplateUsageIndex = FxHashMap<(PathBuf, String), Vec<TemplateUsage>>;
+
+/// A `@Component`, `@Directive` or `@Pipe` class
+#[derive(Debug)]
+struct Declared {
+  file: PathBuf,
+  class_name: String,
+  selectors: Vec<Selector>,
+  pipe_name: Option<String>,
+}
+
+/// One compound selector of a comma-separated selector list
+#[derive(Debug, PartialEq)]
+struct Selector {
+  /// Element name, if the selector names one
+  element: Option<String>,
+  /// Attribute names the element must carry
+  attributes: Vec<String>,
+}
+
+/// A component template and where its class is declared
+struct Template {
+  file: PathBuf,
+  line: usize,
+  source: String,
+}
+
+/// Link every declaration used in a template to the template's component
+pub fn build_template_usages(
+  cwd: &Path,
+  files: &HashMap<PathBuf, FileSemanticData>,
+) -> TemplateUsageIndex {
+  let mut declared = Vec::new();
+  let mut templates = Vec::new();
+  for (file, data) in files {
+    let source = data.source();
+    if !(source.contains("@Component") || source.contains("@Directive") || source.contains("@Pipe"))
+    {
+      continue;
+    }
+    for class in top_level_classes(data.semantic().nodes().program()) {
+      collect_class(cwd, file, source, class, &mut declared, &mut templates);
+    }
+  }
+  if declared.is_empty() || templates.is_empty() {
+    return TemplateUsageIndex::default();
+  }
+
+  let mut index = TemplateUsageIndex::default();
+  for template in &templates {
+    let elements = template_elements(&template.source);
+    let pipes = template_pipes(&template.source);
+    for declaration in &declared {
+      let uses_selector = declaration.selectors.iter().any(|selector| {
+        elements
+          .iter()
+          .any(|(tag, attributes)| selector.matches(tag, attributes))
+      });
+      let uses_pipe = declaration
+        .pipe_name
+        .as_ref()
+        .is_some_and(|name| pipes.contains(name));
+      if (uses_selector || uses_pipe) && declaration.file != template.file {
+        debug!(
+          "Template of {:?} uses {} from {:?}",
+          template.file, declaration.class_name, declaration.file
+        );
+        index
+          .entry((declaration.file.clone(), declaration.class_name.clone()))
+          .or_default()
+          .push((template.file.clone(), template.line));
+      }
+    }
+  }
+  for usages in index.values_mut() {
+    usages.sort();
+    usages.dedup();
+  }
+  index
+}
+
+fn top_level_classes<'a>(program: &'a oxc_ast::ast::Program<'a>) -> Vec<&'a Class<'a>> {
+  program
+    .body
+    .iter()
+    .filter_map(|statement| match statement {
+      Statement::ClassDeclaration(class) => Some(class.as_ref()),
+      Statement::ExportNamedDeclaration(export) => match &export.declaration {
+        Some(Declaration::ClassDeclaration(class)) => Some(class.as_ref()),
+        _ => None,
+      },
+      Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+        oxc_ast::ast::ExportDefaultDeclarationKind::ClassDeclaration(class) => Some(class.as_ref()),
+        _ => None,
+      },
+      _ => None,
+    })
+    .collect()
+}
+
+/// Record the Angular metadata of `class`, if it has any
+fn collect_class(
+  cwd: &Path,
+  file: &Path,
+  source: &str,
+  class: &Class,
+  declared: &mut Vec<Declared>,
+  templates: &mut Vec<Template>,
+) {
+  let Some(id) = &class.id else {
+    return;
+  };
+  for decorator in &class.decorators {
+    let Expression::CallExpression(call) = &decorator.expression else {
+      continue;
+    };
+    let Expression::Identifier(callee) = &call.callee else {
+      continue;
+    };
+    let kind = callee.name.as_str();
+    if !matches!(kind, "Component" | "Directive" | "Pipe") {
+      continue;
+    }
+    let Some(Expression::ObjectExpression(metadata)) = call
+      .arguments
+      .first()
+      .and_then(|argument| argument.as_expression())
+    else {
+      continue;
+    };
+    let property = |name: &str| {
+      metadata
+        .properties
+        .iter()
+        .find_map(|property| match property {
+          ObjectPropertyKind::ObjectProperty(p) if p.key.static_name().as_deref() == Some(name) => {
+            static_string(&p.value)
+          }
+          _ => None,
+        })
+    };
+
+    declared.push(Declared {
+      file: file.to_path_buf(),
+      class_name: id.name.to_string(),
+      selectors: property("selector")
+        .map(|selector| parse_selector(&selector))
+        .unwrap_or_default(),
+      pipe_name: if kind == "Pipe" {
+        property("name")
+      } else {
+        None
+      },
+    });
+
+    if kind == "Component" {
+      let template = property("template").or_else(|| {
+        let url = property("templateUrl")?;
+        let path = cwd.join(file).parent()?.join(url);
+        fs::read_to_string(path).ok()
+      });
+      if let Some(template) = template {
+        let (line, _) = offset_to_line_col(source, id.span.start as usize);
+        templates.push(Template {
+          file: file.to_path_buf(),
+          line,
+          source: template,
+        });
+      }
+    }
+  }
+}
+
+/// Parse `app-card, button[appTooltip]` into compound selectors. `:not(…)`
+/// and class parts are ignored; a part with only those matches nothing.
+fn parse_selector(selector: &str) -> Vec<Selector> {
+  selector
+    .split(',')
+    .filter_map(|part| {
+      let part = match part.find(":not(") {
+        Some(i) => &part[..i],
+        None => part,
+      }
+      .trim();
+      let element_end = part.find(['[', '.', ':']).unwrap_or(part.len());
+      let element = Some(part[..element_end].to_string()).filter(|element| !element.is_empty());
+      let attributes: Vec<String> = part[element_end..]
+        .split('[')
+        .skip(1)
+        .filter_map(|attribute| {
+          let name = attribute.split([']', '=']).next()?.trim();
+          Some(name.to_string()).filter(|name| !name.is_empty())
+        })
+        .collect();
+      if element.is_none() && attributes.is_empty() {
+        return None;
+      }
+      Some(Selector {
+        element,
+        attributes,
+      })
+    })
+    .collect()
+}
+
+impl Selector {
+  fn matches(&self, tag: &str, attributes: &[String]) -> bool {
+    self.element.as_deref().is_none_or(|element| element == tag)
+      && self
+        .attributes
+        .iter()
+        .all(|attribute| attributes.contains(attribute))
+  }
+}
+
+/// Tag names and attribute names of every element in `template`. Binding
+/// syntax is stripped from attribute names: `[appTooltip]`, `(click)`,
+/// `[(ngModel)]` and `*ngIf` become `appTooltip`, `click`, `ngModel`, `ngIf`.
+fn template_elements(template: &str) -> Vec<(String, Vec<String>)> {
+  let bytes = template.as_bytes();
+  let mut elements = Vec::new();
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] != b'<' || !bytes.get(i + 1).is_some_and(|b| b.is_ascii_alphabetic()) {
+      i += 1;
+      continue;
+    }
+    i += 1;
+    let tag_start = i;
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+      i += 1;
+    }
+    let tag = template[tag_start..i].to_string();
+
+    let mut attributes = Vec::new();
+    while i < bytes.len() && bytes[i] != b'>' {
+      match bytes[i] {
+        b'"' | b'\'' => {
+          let quote = bytes[i];
+          i += 1;
+          while i < bytes.len() && bytes[i] != quote {
+            i += 1;
+          }
+          i += 1;
+        }
+        b if b.is_ascii_whitespace() || b == b'/' || b == b'=' => i += 1,
+        _ => {
+          let name_start = i;
+          while i < bytes.len()
+            && !bytes[i].is_ascii_whitespace()
+            && !matches!(bytes[i], b'=' | b'>' | b'/' | b'"' | b'\'')
+          {
+            i += 1;
+          }
+          let name =
+            template[name_start..i].trim_matches(|c| matches!(c, '[' | ']' | '(' | ')' | '*'));
+          if !name.is_empty() {
+            attributes.push(name.to_string());
+          }
+        }
+      }
+    }
+    elements.push((tag, attributes));
+  }
+  elements
+}
+
+/// Names following a pipe operator (`value | currency:'EUR'`)
+fn template_pipes(template: &str) -> Vec<String> {
+  let bytes = template.as_bytes();
+  let mut pipes = Vec::new();
+  for (i, _) in template.match_indices('|') {
+    if bytes.get(i + 1) == Some(&b'|') || (i > 0 && bytes[i - 1] == b'|') {
+      continue;
+    }
+    let rest = template[i + 1..].trim_start();
+    let name: String = rest
+      .chars()
+      .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '$')
+      .collect();
+    if !name.is_empty() && !pipes.contains(&name) {
+      pipes.push(name);
+    }
+  }
+  pipes
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_selector() {
+    assert_eq!(
+      parse_selector("app-card, button[appTooltip], .legacy, [mat-button]:not(a)"),
+      [
+        Selector {
+          element: Some("app-card".to_string()),
+          attributes: vec![],
+        },
+        Selector {
+          element: Some("button".to_string()),
+          attributes: vec!["appTooltip".to_string()],
+        },
+        Selector {
+          element: None,
+          attributes: vec!["mat-button".to_string()],
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_template_elements_and_pipes() {
+    let template = r#"
+<app-user-card [user]="user" (select)="open($event)"></app-user-card>
+<button appTooltip *ngIf="count > 1 || admin" [(ngModel)]="name">{{ price | currency:'EUR' }}</button>
+"#;
+    let elements = template_elements(template);
+    assert_eq!(elements[0].0, "app-user-card");
+    assert_eq!(elements[0].1, ["user", "select"]);
+    assert_eq!(elements[1].0, "button");
+    assert_eq!(elements[1].1, ["appTooltip", "ngIf", "ngModel"]);
+    assert_eq!(template_pipes(template), ["currency"]);
+  }
+}
@@ -0,0 +1,317 @@
+//! Module Federation remotes and exposed modules.
+//!
+//! A host imports a remote's exposed module through a specifier like
+//! `shop/Button`, which looks external to the resolver. The federation configs
+//! of the workspace projects — `module-federation.config.*` (as generated by
+//! Nx) and `ModuleFederationPlugin` options in `webpack.config.*` /
+//! `rspack.config.*` — say which remote `shop` is and which file it exposes as
+//! `./Button`, so those specifiers resolve to workspace files like any other
+//! import.
+
+use super::{static_string, WorkspaceAnalyzer};
+use crate::types::Project;
+use oxc_ast::ast::{ArrayExpressionElement, Expression, ObjectExpression, ObjectPropertyKind};
+use oxc_ast_visit::{walk, Visit};
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tracing::debug;
+
+/// Config files that may declare a federation container, probed in each
+/// project root
+const CONFIG_FILES: &[&str] = &[
+  "module-federation.config.ts",
+  "module-federation.config.js",
+  "module-federation.config.mjs",
+  "module-federation.config.cjs",
+  "webpack.config.ts",
+  "webpack.config.js",
+  "rspack.config.ts",
+  "rspack.config.js",
+];
+
+/// The federation containers of the workspace
+#[derive(Debug, Default)]
+pub struct ModuleFederation {
+  /// Remote name -> exposed key (`./Button`) -> workspace-relative file
+  exposes: FxHashMap<String, FxHashMap<String, PathBuf>>,
+  /// Host project root -> remote alias -> remote name, longest root first
+  hosts: Vec<(PathBuf, FxHashMap<String, String>)>,
+}
+
+/// `name`, `exposes` and `remotes` of one federation config
+#[derive(Debug, Default)]
+struct ContainerConfig {
+  name: Option<String>,
+  exposes: Vec<(String, String)>,
+  remotes: Vec<(String, String)>,
+}
+
+impl ModuleFederation {
+  /// Read the federation configs in the roots of `projects`
+  pub fn load(cwd: &Path, projects: &[Project]) -> Self {
+    let mut federation = Self::default();
+    for project in projects {
+      let Some((file, source)) = CONFIG_FILES.iter().find_map(|name| {
+        let file = project.root.join(name);
+        let source = fs::read_to_string(cwd.join(&file)).ok()?;
+        Some((file, source))
+      }) else {
+        continue;
+      };
+      let Some(config) = read_config(&file, source) else {
+        continue;
+      };
+
+      let context = cwd.join(&project.root);
+      let exposes: FxHashMap<String, PathBuf> = config
+        .exposes
+        .into_iter()
+        .filter_map(|(key, target)| {
+          // `./src/…` is relative to the config; Nx writes workspace-relative
+          // `apps/shop/src/…` paths
+          let resolved = if target.starts_with('.') {
+            resolve_exposed(cwd, &context, &target)
+          } else {
+            let target = format!("./{}", target);
+            resolve_exposed(cwd, cwd, &target).or_else(|| resolve_exposed(cwd, &context, &target))
+          };
+          Some((key, resolved?))
+        })
+        .collect();
+      if !exposes.is_empty() {
+        let name = config.name.unwrap_or_else(|| project.name.clone());
+        debug!(
+          "Federation remote '{}' exposes {} modules",
+          name,
+          exposes.len()
+        );
+        federation.exposes.insert(name, exposes);
+      }
+      if !config.remotes.is_empty() {
+        federation
+          .hosts
+          .push((project.root.clone(), config.remotes.into_iter().collect()));
+      }
+    }
+    federation
+      .hosts
+      .sort_by_key(|(root, _)| std::cmp::Reverse(root.components().count()));
+    federation
+  }
+
+  /// Whether no project exposes modules
+  pub fn is_empty(&self) -> bool {
+    self.exposes.is_empty()
+  }
+
+  /// The exposed file a host's `specifier` (`remote/Exposed`) loads, when the
+  /// project of `from_file` declares the remote
+  pub fn resolve(&self, from_file: &Path, specifier: &str) -> Option<PathBuf> {
+    if self.is_empty() {
+      return None;
+    }
+    let (alias, exposed) = split_remote_specifier(specifier);
+    let (_, remotes) = self
+      .hosts
+      .iter()
+      .find(|(root, _)| from_file.starts_with(root))?;
+    let remote = remotes.get(alias)?;
+    let key = if exposed.is_empty() {
+      ".".to_string()
+    } else {
+      format!("./{}", exposed)
+    };
+    self.exposes.get(remote)?.get(&key).cloned()
+  }
+}
+
+/// An exposed module path, which usually names the file with its extension
+fn resolve_exposed(cwd: &Path, context: &Path, target: &str) -> Option<PathBuf> {
+  let path = context.join(target);
+  if path.is_file() {
+    let relative = path.strip_prefix(cwd).ok()?;
+    return Some(
+      relative
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect(),
+    );
+  }
+  super::simple_resolve_relative(cwd, context, target)
+}
+
+/// `remote/Exposed` -> (`remote`, `Exposed`); scoped remotes keep their scope
+fn split_remote_specifier(specifier: &str) -> (&str, &str) {
+  let scope_end = if specifier.starts_with('@') {
+    specifier.find('/').map_or(0, |i| i + 1)
+  } else {
+    0
+  };
+  match specifier[scope_end..].find('/') {
+    Some(i) => (&specifier[..scope_end + i], &specifier[scope_end + i + 1..]),
+    None => (specifier, ""),
+  }
+}
+
+/// The first object literal in `file` with `exposes` or `remotes`
+fn read_config(file: &Path, source: String) -> Option<ContainerConfig> {
+  let data = WorkspaceAnalyzer::parse_source(file, source).ok()?;
+  let mut finder = ConfigFinder { config: None };
+  finder.visit_program(data.semantic().nodes().program());
+  finder.config
+}
+
+struct ConfigFinder {
+  config: Option<ContainerConfig>,
+}
+
+impl<'a> Visit<'a> for ConfigFinder {
+  fn visit_object_expression(&mut self, object: &ObjectExpression<'a>) {
+    if self.config.is_some() {
+      return;
+    }
+    let mut config = ContainerConfig::default();
+    let mut is_container = false;
+    for property in &object.properties {
+      let ObjectPropertyKind::ObjectProperty(property) = property else {
+        continue;
+      };
+      match property.key.static_name().as_deref() {
+        Some("name") => config.name = static_string(&property.value),
+        Some("exposes") => {
+          is_container = true;
+          config.exposes = exposed_modules(&property.value);
+        }
+        Some("remotes") => {
+          is_container = true;
+          config.remotes = remote_aliases(&property.value);
+        }
+        _ => {}
+      }
+    }
+    if is_container {
+      self.config = Some(config);
+    } else {
+      walk::walk_object_expression(self, object);
+    }
+  }
+}
+
+/// `{ './Button': './src/button.tsx' }`, also with `{ import: … }` values
+fn exposed_modules(expression: &Expression) -> Vec<(String, String)> {
+  let Expression::ObjectExpression(object) = expression.get_inner_expression() else {
+    return vec![];
+  };
+  object
+    .properties
+    .iter()
+    .filter_map(|property| {
+      let ObjectPropertyKind::ObjectProperty(property) = property else {
+        return None;
+      };
+      let key = property.key.static_name()?.to_string();
+      let target = match property.value.get_inner_expression() {
+        Expression::ObjectExpression(options) => {
+          options.properties.iter().find_map(|option| match option {
+            ObjectPropertyKind::ObjectProperty(option)
+              if option.key.static_name().as_deref() == Some("import") =>
+            {
+              static_string(&option.value)
+            }
+            _ => None,
+          })
+        }
+        value => static_string(value),
+      }?;
+      Some((key, target))
+    })
+    .collect()
+}
+
+/// Alias -> remote name from `['shop', ['cart', url]]` (Nx) or
+/// `{ shop: 'shop@http://…' }` (webpack)
+fn remote_aliases(expression: &Expression) -> Vec<(String, String)> {
+  match expression.get_inner_expression() {
+    Expression::ArrayExpression(array) => array
+      .elements
+      .iter()
+      .filter_map(|element| match element {
+        ArrayExpressionElement::ArrayExpression(tuple) => {
+          static_string(tuple.elements.first()?.as_expression()?)
+        }
+        element => static_string(element.as_expression()?),
+      })
+      .map(|name| (name.clone(), name))
+      .collect(),
+    Expression::ObjectExpression(object) => object
+      .properties
+      .iter()
+      .filter_map(|property| {
+        let ObjectPropertyKind::ObjectProperty(property) = property else {
+          return None;
+        };
+        let alias = property.key.static_name()?.to_string();
+        let name = static_string(&property.value)
+          .and_then(|location| location.split_once('@').map(|(name, _)| name.to_string()))
+          .filter(|name| !name.is_empty())
+          .unwrap_or_else(|| alias.clone());
+        Some((alias, name))
+      })
+      .collect(),
+    _ => vec![],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_read_config() {
+    let remote = r#"
+import { ModuleFederationConfig } from '@nx/module-federation';
+
+const config: ModuleFederationConfig = {
+  name: 'shop',
+  exposes: {
+    './Button': './src/app/button.tsx',
+    './Cart': { import: './src/app/cart' },
+  },
+};
+export default config;
+"#;
+    let config = read_config(Path::new("module-federation.config.ts"), remote.to_string()).unwrap();
+    assert_eq!(config.name.as_deref(), Some("shop"));
+    assert_eq!(
+      config.exposes,
+      [
+        ("./Button".to_string(), "./src/app/button.tsx".to_string()),
+        ("./Cart".to_string(), "./src/app/cart".to_string()),
+      ]
+    );
+
+    let host = r#"
+module.exports = {
+  plugins: [
+    new ModuleFederationPlugin({
+      name: 'shell',
+      remotes: { store: 'shop@http://localhost:4201/remoteEntry.js' },
+    }),
+  ],
+};
+"#;
+    let config = read_config(Path::new("webpack.config.js"), host.to_string()).unwrap();
+    assert_eq!(config.remotes, [("store".to_string(), "shop".to_string())]);
+  }
+
+  #[test]
+  fn test_split_remote_specifier() {
+    assert_eq!(split_remote_specifier("shop/Button"), ("shop", "Button"));
+    assert_eq!(
+      split_remote_specifier("@org/shop/ui/Button"),
+      ("@org/shop", "ui/Button")
+    );
+    assert_eq!(split_remote_specifier("shop"), ("shop", ""));
+  }
+}
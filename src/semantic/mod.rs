@@ -1,17 +1,49 @@
 pub mod analyzer;
+pub mod angular;
 pub mod assets;
+pub mod federation;
 pub mod reference_finder;
 mod resolve_options;
+pub mod sfc;
 
 use std::path::{Path, PathBuf};
 
-pub use analyzer::WorkspaceAnalyzer;
+pub use analyzer::{ImportIndexValue, WorkspaceAnalyzer};
 pub use assets::AssetReferenceFinder;
+pub use federation::ModuleFederation;
 pub use reference_finder::ReferenceFinder;
 pub(crate) use resolve_options::create_resolve_options;
 pub(crate) use resolve_options::is_workspace_specifier;
 pub(crate) use resolve_options::parse_tsconfig_path_prefixes;
 
+/// Resolve an import/export specifier of `from_file` to a workspace-relative
+/// path; see [`WorkspaceAnalyzer::resolve_workspace_specifier`]. Usable without
+/// an analyzer, for callers that only parse a handful of files.
+pub(crate) fn resolve_workspace_specifier(
+  resolver: &oxc_resolver::Resolver,
+  cwd: &Path,
+  projects: &[crate::types::Project],
+  tsconfig_path_prefixes: &[String],
+  from_file: &Path,
+  specifier: &str,
+) -> Option<PathBuf> {
+  let from_path = cwd.join(from_file);
+  let context = from_path.parent()?;
+
+  if !is_workspace_specifier(specifier, projects, tsconfig_path_prefixes) {
+    return None;
+  }
+
+  match resolver.resolve(context, specifier) {
+    Ok(resolution) => resolution
+      .path()
+      .strip_prefix(cwd)
+      .ok()
+      .map(|p| p.to_path_buf()),
+    Err(_) => simple_resolve_relative(cwd, context, specifier),
+  }
+}
+
 /// Shared fallback resolution for relative imports when oxc_resolver fails.
 /// Handles .js/.jsx/.mjs/.cjs → TypeScript-equivalent remapping and standard
 /// extension probing. Mirrors the `extensions` / `extension_alias` config in
@@ -110,3 +142,18 @@ pub(crate) fn simple_resolve_relative(
 
   None
 }
+
+/// The value of a string literal, or of a template literal without
+/// expressions, seen through TypeScript `as` / `satisfies` wrappers
+pub(crate) fn static_string(expression: &oxc_ast::ast::Expression) -> Option<String> {
+  use oxc_ast::ast::Expression;
+  match expression.get_inner_expression() {
+    Expression::StringLiteral(literal) => Some(literal.value.to_string()),
+    Expression::TemplateLiteral(template) if template.expressions.is_empty() => template
+      .quasis
+      .first()
+      .and_then(|quasi| quasi.value.cooked.as_ref())
+      .map(|cooked| cooked.to_string()),
+    _ => None,
+  }
+}
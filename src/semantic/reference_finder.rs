@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::profiler::Profiler;
-use crate::semantic::WorkspaceAnalyzer;
+use crate::semantic::{ImportIndexValue, WorkspaceAnalyzer};
 use crate::types::Reference;
 use oxc_resolver::Resolver;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -10,6 +10,17 @@ use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, warn};
 
+/// References reached through an import. Everything reached through an
+/// `import type` only reads types, whatever position it sits in.
+fn through_import(mut refs: Vec<Reference>, is_type_only: bool) -> Vec<Reference> {
+  if is_type_only {
+    for reference in &mut refs {
+      reference.is_type_only = true;
+    }
+  }
+  refs
+}
+
 /// Cross-file reference finder
 pub struct ReferenceFinder<'a> {
   analyzer: &'a WorkspaceAnalyzer,
@@ -53,6 +64,7 @@ impl<'a> ReferenceFinder<'a> {
               file_path: importing_file.clone(),
               line: 0,
               column: 0,
+              is_type_only: import.is_type_only,
             });
             break; // Only add each file once
           }
@@ -99,19 +111,31 @@ impl<'a> ReferenceFinder<'a> {
 
     // Use the import index to find direct imports of this symbol
     if let Some(importers) = self.analyzer.import_index.get(&key) {
-      for (importing_file, local_name, _from_module, _is_dynamic) in importers {
+      for ImportIndexValue {
+        importing_file,
+        local_name,
+        local_symbol,
+        is_type_only,
+        ..
+      } in importers
+      {
         debug!(
           "Found import of '{}' in {:?} as '{}'",
           symbol_name, importing_file, local_name
         );
 
-        // Find all references to the local name in the importing file
-        match self
-          .analyzer
-          .find_local_references(importing_file, local_name)
-        {
+        // Find all references to the importing binding in the importing file
+        let local_refs = match local_symbol {
+          Some(symbol_id) => self
+            .analyzer
+            .find_symbol_references(importing_file, &[*symbol_id]),
+          None => self
+            .analyzer
+            .find_local_references(importing_file, local_name),
+        };
+        match local_refs {
           Ok(local_refs) => {
-            all_refs.extend(local_refs);
+            all_refs.extend(through_import(local_refs, *is_type_only));
           }
           Err(e) => {
             warn!("Error finding local references: {}", e);
@@ -139,10 +163,51 @@ impl<'a> ReferenceFinder<'a> {
       }
     }
 
+    // Angular templates use components, directives and pipes by selector or
+    // name; the reference is the class owning the template
+    if let Some(usages) = self.analyzer.template_usages.get(&key) {
+      for (template_owner, line) in usages {
+        debug!(
+          "Found template usage of '{}' in {:?}",
+          symbol_name, template_owner
+        );
+        all_refs.push(Reference {
+          file_path: template_owner.clone(),
+          line: *line,
+          column: 0,
+          is_type_only: false,
+        });
+      }
+    }
+
     // Also check for namespace imports (import * as foo)
     let namespace_key = (current_file.to_path_buf(), "*".to_string());
     if let Some(importers) = self.analyzer.import_index.get(&namespace_key) {
-      for (importing_file, local_name, _from_module, is_dynamic) in importers {
+      for ImportIndexValue {
+        importing_file,
+        local_name,
+        from_module,
+        is_dynamic,
+        is_module_url,
+        is_type_only,
+        ..
+      } in importers
+      {
+        if *is_module_url {
+          // `new Worker(new URL('./worker.ts', import.meta.url))`: the worker module
+          // is loaded as a whole, so every symbol in it reaches the `new URL` site.
+          debug!(
+            "Found module URL reference to {:?} in {:?} (symbol '{}')",
+            current_file, importing_file, symbol_name
+          );
+          all_refs.extend(
+            self
+              .analyzer
+              .find_module_url_references(importing_file, from_module)?,
+          );
+          continue;
+        }
+
         debug!(
           "Found {} namespace import in {:?} as '{}' (checking for {}.{})",
           if *is_dynamic { "dynamic" } else { "static" },
@@ -154,11 +219,15 @@ impl<'a> ReferenceFinder<'a> {
 
         // For namespace imports, we need to find references to namespace.symbol specifically
         // (e.g., utils.formatDate, not just any reference to utils)
-        match self
-          .analyzer
-          .find_namespace_member_access(importing_file, local_name, symbol_name)
-        {
+        match self.namespace_member_refs(
+          importing_file,
+          local_name,
+          from_module,
+          *is_dynamic,
+          symbol_name,
+        ) {
           Ok(member_refs) => {
+            let member_refs = through_import(member_refs, *is_type_only);
             if !member_refs.is_empty() {
               // Found actual references to namespace.symbol - these files are definitely affected
               debug!(
@@ -177,7 +246,8 @@ impl<'a> ReferenceFinder<'a> {
               //
               // This prevents React.lazy(() => import('./SomePage')) from cascading all
               // exports when a deep dependency of SomePage changes. The lazy boundary
-              // acts as an isolation point — only explicit member access propagates.
+              // acts as an isolation point — only the `default` export it renders, or
+              // explicit member access, propagates.
               debug!(
                 "No local references to dynamic namespace '{}' for symbol '{}' in {:?} — \
                  specifier is a static string literal, treating like static namespace import (no cascade)",
@@ -207,6 +277,11 @@ impl<'a> ReferenceFinder<'a> {
           continue;
         }
 
+        // A namespace re-export binds the whole module, not a symbol of it
+        if export.is_namespace {
+          continue;
+        }
+
         // If this is a re-export from elsewhere, follow it
         if let Some(ref from_module) = export.re_export_from {
           if let Some(resolved) = self.resolve_import(current_file, from_module) {
@@ -245,8 +320,21 @@ impl<'a> ReferenceFinder<'a> {
     }
 
     for (reexporting_file, export) in reexporters.into_iter().flatten() {
-      // Handle wildcard re-exports: export * from '...'
-      if export.exported_name == "*" {
+      if export.is_namespace {
+        // Namespace re-export: export * as ns from '...'
+        debug!(
+          "Found barrel file {:?} re-exporting {:?} as namespace '{}'",
+          reexporting_file, current_file, export.exported_name
+        );
+        self.find_namespace_reexport_refs(
+          &export.exported_name,
+          symbol_name,
+          reexporting_file,
+          all_refs,
+          visited,
+        )?;
+      } else if export.exported_name == "*" {
+        // Handle wildcard re-exports: export * from '...'
         debug!(
           "Found barrel file {:?} with wildcard re-export from {:?}",
           reexporting_file, current_file
@@ -274,6 +362,159 @@ impl<'a> ReferenceFinder<'a> {
     Ok(())
   }
 
+  /// Find references to `member` reached through the namespace `namespace`
+  /// exported by `barrel` (`export * as namespace from './declaring-file'`).
+  ///
+  /// Consumers importing the namespace only depend on `member` where they
+  /// access it (`namespace.member`), so this uses member-access lookup instead
+  /// of treating every use of the namespace binding as a reference. The
+  /// namespace binding itself is followed through further barrels: named and
+  /// wildcard re-exports keep it a namespace, while nesting it inside another
+  /// namespace (`outer.namespace`) or importing the barrel as a namespace
+  /// (`lib.namespace`) is matched conservatively on the namespace access alone.
+  fn find_namespace_reexport_refs(
+    &self,
+    namespace: &str,
+    member: &str,
+    barrel: &Path,
+    all_refs: &mut Vec<Reference>,
+    visited: &mut FxHashSet<(PathBuf, String)>,
+  ) -> Result<()> {
+    let member_key = (barrel.to_path_buf(), format!("{}.{}", namespace, member));
+    if !visited.insert(member_key) {
+      return Ok(());
+    }
+
+    // import { namespace } from 'barrel'
+    let key = (barrel.to_path_buf(), namespace.to_string());
+    if let Some(importers) = self.analyzer.import_index.get(&key) {
+      for ImportIndexValue {
+        importing_file,
+        local_name,
+        is_type_only,
+        ..
+      } in importers
+      {
+        debug!(
+          "Found import of namespace '{}' in {:?} as '{}' (checking for {}.{})",
+          namespace, importing_file, local_name, local_name, member
+        );
+        all_refs.extend(through_import(
+          self
+            .analyzer
+            .find_namespace_member_access(importing_file, local_name, member)?,
+          *is_type_only,
+        ));
+
+        if self.is_re_exported(importing_file, local_name) {
+          self.find_namespace_reexport_refs(
+            local_name,
+            member,
+            importing_file,
+            all_refs,
+            visited,
+          )?;
+        }
+      }
+    }
+
+    // import * as lib from 'barrel' (lib.namespace.member)
+    let namespace_key = (barrel.to_path_buf(), "*".to_string());
+    if let Some(importers) = self.analyzer.import_index.get(&namespace_key) {
+      for ImportIndexValue {
+        importing_file,
+        local_name,
+        from_module,
+        is_dynamic,
+        is_module_url,
+        is_type_only,
+        ..
+      } in importers
+      {
+        if *is_module_url {
+          all_refs.extend(
+            self
+              .analyzer
+              .find_module_url_references(importing_file, from_module)?,
+          );
+          continue;
+        }
+        all_refs.extend(through_import(
+          self.namespace_member_refs(
+            importing_file,
+            local_name,
+            from_module,
+            *is_dynamic,
+            namespace,
+          )?,
+          *is_type_only,
+        ));
+      }
+    }
+
+    let reexporters = self
+      .analyzer
+      .reexport_index
+      .get(Self::normalize_path(&self.cwd, barrel));
+    for (reexporting_file, export) in reexporters.into_iter().flatten() {
+      if export.is_namespace {
+        // export * as outer from 'barrel' (outer.namespace.member)
+        self.find_namespace_reexport_refs(
+          &export.exported_name,
+          namespace,
+          reexporting_file,
+          all_refs,
+          visited,
+        )?;
+      } else if export.exported_name == "*" {
+        self.find_namespace_reexport_refs(
+          namespace,
+          member,
+          reexporting_file,
+          all_refs,
+          visited,
+        )?;
+      } else if export
+        .local_name
+        .as_deref()
+        .unwrap_or(&export.exported_name)
+        == namespace
+      {
+        self.find_namespace_reexport_refs(
+          &export.exported_name,
+          member,
+          reexporting_file,
+          all_refs,
+          visited,
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// References to `member` of a namespace import. Static namespace imports are
+  /// looked up through their binding; dynamic `import()` expressions have none,
+  /// so they are located by specifier.
+  fn namespace_member_refs(
+    &self,
+    importing_file: &Path,
+    local_name: &str,
+    from_module: &str,
+    is_dynamic: bool,
+    member: &str,
+  ) -> Result<Vec<Reference>> {
+    if is_dynamic {
+      self
+        .analyzer
+        .find_dynamic_import_member_access(importing_file, from_module, member)
+    } else {
+      self
+        .analyzer
+        .find_namespace_member_access(importing_file, local_name, member)
+    }
+  }
+
   /// Resolve an import specifier to a file path (with caching)
   fn resolve_import(&self, from_file: &Path, specifier: &str) -> Option<PathBuf> {
     let start = if self.profiler.is_enabled() {
@@ -297,6 +538,14 @@ impl<'a> ReferenceFinder<'a> {
       }
     }
 
+    if let Some(exposed) = self.analyzer.federation.resolve(from_file, specifier) {
+      self
+        .resolution_cache
+        .borrow_mut()
+        .insert(cache_key, Some(exposed.clone()));
+      return Some(exposed);
+    }
+
     if !super::is_workspace_specifier(
       specifier,
       &self.analyzer.projects,
@@ -354,8 +603,9 @@ impl<'a> ReferenceFinder<'a> {
   fn is_re_exported(&self, file: &Path, symbol_name: &str) -> bool {
     if let Some(exports) = self.analyzer.exports.get(file) {
       exports.iter().any(|export| {
-        export.local_name.as_deref() == Some(symbol_name)
-          || (export.exported_name == symbol_name && export.local_name.is_none())
+        !export.is_namespace
+          && (export.local_name.as_deref() == Some(symbol_name)
+            || (export.exported_name == symbol_name && export.local_name.is_none()))
       })
     } else {
       false
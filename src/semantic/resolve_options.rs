@@ -355,6 +355,7 @@ mod tests {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let opts = create_resolve_options(cwd, &projects);
@@ -383,6 +384,7 @@ mod tests {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let opts = create_resolve_options(cwd, &projects);
@@ -416,6 +418,7 @@ mod tests {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let opts = create_resolve_options(cwd, &projects);
@@ -447,6 +450,7 @@ mod tests {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     // Must not panic — is_dir() on non-existent path returns false
@@ -470,6 +474,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       })
       .collect()
   }
@@ -0,0 +1,336 @@
+//! Vue, Svelte and Astro single-file components.
+//!
+//! A component's code lives in `<script>` blocks (and, for Astro, the `---`
+//! frontmatter), surrounded by markup. Everything outside those blocks is
+//! blanked to spaces before parsing, keeping every newline and byte offset, so
+//! spans and line numbers of the parsed script are those of the component file
+//! itself. The blanked-out markup is kept as a [`Template`]: a changed line in
+//! it is a change to the component's default export, and a script binding
+//! named in it is referenced from there.
+
+use oxc_span::SourceType;
+use std::ops::Range;
+use std::path::Path;
+
+/// Extensions of single-file components
+pub const SFC_EXTENSIONS: &[&str] = &["vue", "svelte", "astro"];
+
+/// The markup of a single-file component
+#[derive(Debug)]
+pub struct Template {
+  /// The component source with script and style blocks blanked out
+  markup: String,
+  /// Byte ranges of the script blocks
+  scripts: Vec<Range<usize>>,
+}
+
+impl Template {
+  /// Whether the source `range` (such as a line without its line break) lies
+  /// at least partly inside a script block
+  pub fn is_script(&self, range: Range<usize>) -> bool {
+    self.scripts.iter().any(|script| {
+      script.contains(&range.start) || (script.start < range.end && range.start < script.end)
+    })
+  }
+
+  /// Offset of the first place the markup names the script binding `name`:
+  /// as an expression identifier (`{{ format(price) }}`, `{count}`) or as a
+  /// component tag, also in kebab-case (`<UserCard>`, `<user-card>`)
+  pub fn find_usage(&self, name: &str) -> Option<usize> {
+    let kebab = kebab_case(name);
+    std::iter::once(name)
+      .chain(kebab.as_deref())
+      .filter_map(|name| find_word(&self.markup, name))
+      .min()
+  }
+}
+
+/// Split a single-file component into its parseable script and its markup.
+/// Returns `None` when `path` is not a single-file component.
+pub fn extract(path: &Path, source: &str) -> Option<(String, SourceType, Template)> {
+  let extension = path.extension()?.to_str()?;
+  if !SFC_EXTENSIONS.contains(&extension) {
+    return None;
+  }
+
+  let mut scripts = Vec::new();
+  let mut styles = Vec::new();
+  let mut lang = None;
+  let mut body_start = 0;
+  if extension == "astro" {
+    if let Some(frontmatter) = astro_frontmatter(source) {
+      body_start = frontmatter.end;
+      scripts.push(frontmatter);
+      lang = Some("ts".to_string());
+    }
+  }
+  for block in blocks(source, body_start) {
+    if block.tag == "style" {
+      styles.push(block.content);
+    } else if block.is_javascript() {
+      if lang.is_none() {
+        lang = block.attribute("lang");
+      }
+      scripts.push(block.content);
+    }
+  }
+
+  let source_type = match lang.as_deref() {
+    Some("ts" | "typescript") => SourceType::ts(),
+    Some("tsx") => SourceType::tsx(),
+    Some("jsx") => SourceType::jsx(),
+    _ if extension == "astro" => SourceType::ts(),
+    _ => SourceType::mjs(),
+  };
+  let script = blank(source, |offset| {
+    !scripts.iter().any(|script| script.contains(&offset))
+  });
+  let markup = blank(source, |offset| {
+    scripts
+      .iter()
+      .chain(&styles)
+      .any(|block| block.contains(&offset))
+  });
+  Some((script, source_type, Template { markup, scripts }))
+}
+
+/// A `<script>` or `<style>` element
+struct Block<'a> {
+  tag: &'a str,
+  attributes: &'a str,
+  content: Range<usize>,
+}
+
+impl Block<'_> {
+  /// Value of the attribute `name`, if present
+  fn attribute(&self, name: &str) -> Option<String> {
+    let mut rest = self.attributes;
+    while let Some(i) = rest.find(name) {
+      let after = rest[i + name.len()..].trim_start();
+      let is_name_start = rest[..i]
+        .chars()
+        .next_back()
+        .is_none_or(|c| c.is_whitespace());
+      if let Some(value) = after.strip_prefix('=').filter(|_| is_name_start) {
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+          Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+          _ => value.split(|c: char| c.is_whitespace() || c == '>').next(),
+        };
+        return value.map(str::to_string);
+      }
+      rest = &rest[i + name.len()..];
+    }
+    None
+  }
+
+  /// Scripts are JavaScript unless their `type` names a data format
+  fn is_javascript(&self) -> bool {
+    self.tag == "script"
+      && self.attribute("type").is_none_or(|kind| {
+        matches!(
+          kind.as_str(),
+          "module" | "text/javascript" | "application/javascript" | "text/typescript" | "ts"
+        )
+      })
+  }
+}
+
+/// Top-level `<script>` and `<style>` elements from `start` on, skipping HTML
+/// comments
+fn blocks(source: &str, start: usize) -> Vec<Block<'_>> {
+  let mut blocks = Vec::new();
+  let mut i = start;
+  while let Some(found) = source[i..].find('<') {
+    i += found;
+    let rest = &source[i..];
+    if rest.starts_with("<!--") {
+      match rest.find("-->") {
+        Some(end) => i += end + 3,
+        None => break,
+      }
+      continue;
+    }
+    let Some(tag) = ["script", "style"].into_iter().find(|tag| {
+      rest[1..].starts_with(tag)
+        && rest[1 + tag.len()..]
+          .chars()
+          .next()
+          .is_some_and(|c| c.is_whitespace() || c == '>')
+    }) else {
+      i += 1;
+      continue;
+    };
+    let Some(open_end) = tag_end(source, i + 1 + tag.len()) else {
+      break;
+    };
+    let attributes = &source[i + 1 + tag.len()..open_end];
+    let content_start = open_end + 1;
+    let closing = format!("</{}", tag);
+    let content_end = source[content_start..]
+      .find(&closing)
+      .map_or(source.len(), |end| content_start + end);
+    if !attributes.trim_end().ends_with('/') {
+      blocks.push(Block {
+        tag,
+        attributes,
+        content: content_start..content_end,
+      });
+    }
+    i = content_end;
+  }
+  blocks
+}
+
+/// Offset of the `>` closing a start tag, skipping quoted attribute values
+fn tag_end(source: &str, from: usize) -> Option<usize> {
+  let mut quote = None;
+  for (i, c) in source[from..].char_indices() {
+    match (quote, c) {
+      (Some(q), c) if c == q => quote = None,
+      (Some(_), _) => {}
+      (None, '"' | '\'') => quote = Some(c),
+      (None, '>') => return Some(from + i),
+      _ => {}
+    }
+  }
+  None
+}
+
+/// The code between the opening and closing `---` fences of an Astro file
+fn astro_frontmatter(source: &str) -> Option<Range<usize>> {
+  let leading = source.len() - source.trim_start().len();
+  let rest = source[leading..].strip_prefix("---")?;
+  let start = source.len() - rest.len();
+  let mut lines = rest.split_inclusive('\n');
+  let mut offset = start + lines.next()?.len();
+  for line in lines {
+    if line.trim_end() == "---" {
+      return Some(start..offset);
+    }
+    offset += line.len();
+  }
+  None
+}
+
+/// `source` with every character at an offset matching `hidden` replaced by
+/// spaces (one per byte), keeping line breaks
+fn blank(source: &str, hidden: impl Fn(usize) -> bool) -> String {
+  let mut blanked = String::with_capacity(source.len());
+  for (offset, c) in source.char_indices() {
+    if matches!(c, '\n' | '\r') || !hidden(offset) {
+      blanked.push(c);
+    } else {
+      blanked.extend(std::iter::repeat_n(' ', c.len_utf8()));
+    }
+  }
+  blanked
+}
+
+/// First occurrence of `word` in `text` not inside a longer identifier
+fn find_word(text: &str, word: &str) -> Option<usize> {
+  let is_word_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '-');
+  text.match_indices(word).map(|(i, _)| i).find(|&i| {
+    !text[..i].chars().next_back().is_some_and(is_word_char)
+      && !text[i + word.len()..]
+        .chars()
+        .next()
+        .is_some_and(is_word_char)
+  })
+}
+
+/// `UserCard` -> `user-card`; `None` for names that do not start uppercase
+fn kebab_case(name: &str) -> Option<String> {
+  if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+    return None;
+  }
+  let mut kebab = String::with_capacity(name.len() + 4);
+  for (i, c) in name.chars().enumerate() {
+    if c.is_ascii_uppercase() {
+      if i > 0 {
+        kebab.push('-');
+      }
+      kebab.push(c.to_ascii_lowercase());
+    } else {
+      kebab.push(c);
+    }
+  }
+  Some(kebab)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_extract_vue_keeps_line_offsets() {
+    let source = r#"<template>
+  <UserCard :name="name" />
+  <p>{{ formatPrice(total) }}</p>
+</template>
+
+<script setup lang="ts">
+import UserCard from './UserCard.vue';
+import { formatPrice } from '../lib/price';
+
+const name: string = 'Ada';
+</script>
+
+<style scoped>
+p { color: red; }
+</style>
+"#;
+    let (script, source_type, template) = extract(Path::new("Page.vue"), source).unwrap();
+    assert!(source_type.is_typescript());
+    assert_eq!(script.len(), source.len());
+    let lines: Vec<&str> = script.lines().collect();
+    assert_eq!(lines.len(), source.lines().count());
+    assert_eq!(lines[6], "import UserCard from './UserCard.vue';");
+    assert!(lines[1].trim().is_empty());
+    assert!(lines[13].trim().is_empty());
+
+    let line = |n: usize| {
+      let start: usize = source.lines().take(n).map(|line| line.len() + 1).sum();
+      start..start + source.lines().nth(n).unwrap().len()
+    };
+    assert!(template.is_script(line(6)));
+    assert!(template.is_script(line(8)));
+    assert!(!template.is_script(line(2)));
+    assert!(!template.is_script(line(5)));
+    assert!(!template.is_script(line(10)));
+    assert_eq!(template.find_usage("UserCard"), source.find("UserCard"));
+    assert_eq!(
+      template.find_usage("formatPrice"),
+      source.find("formatPrice")
+    );
+    assert_eq!(
+      template.find_usage("name"),
+      source.find(":name").map(|i| i + 1)
+    );
+    assert_eq!(template.find_usage("total"), source.find("total"));
+    assert_eq!(template.find_usage("Page"), None);
+    assert_eq!(template.find_usage("color"), None);
+  }
+
+  #[test]
+  fn test_extract_svelte_and_astro() {
+    let svelte = "<script context=\"module\">\n  export const prerender = true;\n</script>\n<script>\n  import Card from './card.svelte';\n</script>\n<!-- <script>ignored</script> -->\n<card-list />\n";
+    let (script, source_type, template) = extract(Path::new("Page.svelte"), svelte).unwrap();
+    assert!(!source_type.is_typescript());
+    assert!(script.contains("export const prerender = true;"));
+    assert!(script.contains("import Card from './card.svelte';"));
+    assert!(!script.contains("ignored"));
+    assert_eq!(template.find_usage("Card"), None);
+
+    let astro = "---\nimport Layout from '../layouts/Layout.astro';\nconst title = 'Home';\n---\n<Layout title={title}>\n  <script>\n    import '../scripts/menu';\n  </script>\n</Layout>\n";
+    let (script, source_type, template) = extract(Path::new("index.astro"), astro).unwrap();
+    assert!(source_type.is_typescript());
+    assert!(script.starts_with("   \nimport Layout"));
+    assert!(script.contains("import '../scripts/menu';"));
+    assert!(!script.contains("<Layout"));
+    assert_eq!(template.find_usage("Layout"), astro.find("Layout title"));
+    assert_eq!(template.find_usage("title"), astro.find("title={"));
+
+    assert!(extract(Path::new("index.ts"), "export {}").is_none());
+  }
+}
@@ -0,0 +1,656 @@
+//! Storybook stories affected by a change (`domino affected-stories`).
+//!
+//! Story files (CSF, `*.stories.*` by default) are found the way
+//! `affected-tests` finds test files: changed story files and those that
+//! transitively import a changed symbol. Every story a file exports is then
+//! listed by the ID Storybook gives it — the sanitized `title` (or `id`) of
+//! the default export, `--`, and the sanitized start-cased export name.
+//!
+//! Files without a static `title` are autotitled the way Storybook 7+ does it:
+//! from their path below the `directory` of the `stories` entry in
+//! `.storybook/main.*` that covers them, after its `titlePrefix`. Files that
+//! no entry covers are reported as untitled.
+
+use crate::affected_tests;
+use crate::error::{DominoError, Result};
+use crate::profiler::Profiler;
+use crate::semantic::analyzer::FileSemanticData;
+use crate::semantic::{static_string, WorkspaceAnalyzer};
+use crate::types::{
+  AffectedStories, AffectedStory, Export, Project, StoriesFormat, TrueAffectedConfig,
+};
+use crate::utils::MATCH_OPTIONS;
+use glob::Pattern;
+use oxc_ast::ast::{
+  ArrayExpressionElement, BindingPatternKind, Declaration, ExportDefaultDeclarationKind,
+  Expression, ObjectExpression, ObjectPropertyKind, Program, Statement,
+};
+use oxc_ast_visit::{walk, Visit};
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Files Storybook loads as CSF stories by default
+pub const STORY_FILE_PATTERNS: &[&str] = &["**/*.stories.*"];
+
+/// Storybook main config files, probed in `.storybook/` of the workspace and
+/// of every project
+const MAIN_CONFIG_FILES: &[&str] = &["main.ts", "main.js", "main.mjs", "main.cjs", "main.mts"];
+
+/// Characters Storybook replaces with `-` when building story IDs
+const ID_SEPARATORS: &str = " ’–—―′¿'`~!@#$%^&*()_|+-=?;:\",.<>{}[]\\/";
+
+/// An entry of the `stories` array of a Storybook main config
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoriesSpecifier {
+  /// Directory the entry's glob starts from, relative to the workspace root
+  pub directory: PathBuf,
+  /// `titlePrefix` of the entry, prepended to every title below `directory`
+  pub title_prefix: String,
+}
+
+/// Find the stories of the story files matching `patterns` (globs relative to
+/// the workspace root) that are changed or transitively import a changed
+/// symbol
+pub fn find_affected_stories(
+  config: TrueAffectedConfig,
+  patterns: &[String],
+  profiler: Arc<Profiler>,
+) -> Result<AffectedStories> {
+  let cwd = config.cwd.clone();
+  let projects = config.projects.clone();
+  let patterns = patterns
+    .iter()
+    .map(|pattern| {
+      Pattern::new(pattern)
+        .map_err(|e| DominoError::Other(format!("Invalid story pattern '{}': {}", pattern, e)))
+    })
+    .collect::<Result<Vec<_>>>()?;
+  let analyzer = WorkspaceAnalyzer::new(projects.clone(), &cwd, profiler.clone())?;
+  let files = affected_tests::find_affected_matching(
+    config,
+    Some(&analyzer),
+    |file| {
+      patterns
+        .iter()
+        .any(|pattern| pattern.matches_path_with(file, MATCH_OPTIONS))
+    },
+    profiler,
+  )?;
+
+  let specifiers = storybook_specifiers(&cwd, &projects);
+  debug!("Found {} Storybook stories entries", specifiers.len());
+
+  let mut stories = Vec::new();
+  let mut untitled = Vec::new();
+  for file in &files.files {
+    let specifier = specifiers
+      .iter()
+      .find(|specifier| file.starts_with(&specifier.directory));
+    let file_stories = match analyzer.files.get(file) {
+      Some(data) => {
+        let exports = analyzer.exports.get(file).map_or(&[][..], Vec::as_slice);
+        read_stories(file, data, exports, specifier)
+      }
+      // Story files outside every project are not indexed by the analyzer
+      None => {
+        let source = match fs::read_to_string(cwd.join(file)) {
+          Ok(source) => source,
+          Err(e) => {
+            warn!("Failed to read story file {:?}: {}", file, e);
+            continue;
+          }
+        };
+        let data = WorkspaceAnalyzer::parse_source(file, source)?;
+        let exports = WorkspaceAnalyzer::extract_exports(data.semantic().nodes().program());
+        read_stories(file, &data, &exports, specifier)
+      }
+    };
+    match file_stories {
+      Some(file_stories) => stories.extend(file_stories),
+      None => {
+        warn!(
+          "{:?} has no static title and no Storybook stories entry covers it; its stories are not listed",
+          file
+        );
+        untitled.push(file.clone());
+      }
+    }
+  }
+  debug!(
+    "{} affected stories in {} story files",
+    stories.len(),
+    files.files.len()
+  );
+
+  Ok(AffectedStories {
+    stories,
+    untitled,
+    global: files.global,
+  })
+}
+
+/// The stories a CSF file exports, in source order, titled by the default
+/// export's `title` or else by `specifier`, the Storybook `stories` entry
+/// covering the file. `data` and `exports` are the file's parse and its
+/// exports as the analyzer extracted them. `None` when the file has no static
+/// title and no specifier to derive one from.
+pub fn read_stories(
+  file: &Path,
+  data: &FileSemanticData,
+  exports: &[Export],
+  specifier: Option<&StoriesSpecifier>,
+) -> Option<Vec<AffectedStory>> {
+  let program = data.semantic().nodes().program();
+  let locals = local_initializers(program);
+
+  let Some(meta) = program.body.iter().find_map(|statement| match statement {
+    Statement::ExportDefaultDeclaration(export) => default_meta(&export.declaration, &locals),
+    _ => None,
+  }) else {
+    debug!("{:?} has no CSF default export", file);
+    return Some(vec![]);
+  };
+  let title = match (string_property(meta, "title"), specifier) {
+    (Some(title), Some(specifier)) => join_title(&specifier.title_prefix, &title),
+    (Some(title), None) => title,
+    (None, Some(specifier)) => autotitle(file, specifier),
+    (None, None) => return None,
+  };
+  let component_id = sanitize(&string_property(meta, "id").unwrap_or_else(|| title.clone()));
+  let include = string_list_property(meta, "includeStories");
+  let exclude = string_list_property(meta, "excludeStories").unwrap_or_default();
+  let story_names = assigned_story_names(program);
+
+  let stories = story_exports(exports)
+    .filter(|export| export != "__namedExportsOrder")
+    .filter(|export| {
+      include
+        .as_ref()
+        .is_none_or(|include| include.contains(export))
+    })
+    .filter(|export| !exclude.contains(export))
+    .map(|export| {
+      let name = story_names
+        .get(export.as_str())
+        .cloned()
+        .or_else(|| {
+          locals
+            .get(export.as_str())
+            .and_then(|init| object_of(init))
+            .and_then(|story| string_property(story, "name"))
+        })
+        .unwrap_or_else(|| start_case(&export));
+      AffectedStory {
+        id: format!("{}--{}", component_id, sanitize(&start_case(&export))),
+        title: title.clone(),
+        name,
+        file: file.to_path_buf(),
+      }
+    })
+    .collect();
+  Some(stories)
+}
+
+/// Storybook's autotitle: the path of `file` below the specifier's directory,
+/// without the extension, and without a last segment that is `index` or
+/// repeats its directory (`button/button.stories.tsx` is `button`)
+pub fn autotitle(file: &Path, specifier: &StoriesSpecifier) -> String {
+  let relative = file.strip_prefix(&specifier.directory).unwrap_or(file);
+  let mut parts: Vec<String> = relative
+    .components()
+    .filter_map(|component| component.as_os_str().to_str().map(str::to_string))
+    .collect();
+  if let Some(last) = parts.last_mut() {
+    if let Some(dot) = last.find('.').filter(|&dot| dot > 0) {
+      last.truncate(dot);
+    }
+  }
+  let redundant = match parts.as_slice() {
+    [.., previous, last] => last == previous || last.eq_ignore_ascii_case("index"),
+    [last] => last.eq_ignore_ascii_case("index"),
+    [] => false,
+  };
+  if redundant {
+    parts.pop();
+  }
+  join_title(&specifier.title_prefix, &parts.join("/"))
+}
+
+fn join_title(prefix: &str, title: &str) -> String {
+  prefix
+    .split('/')
+    .chain(title.split('/'))
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// The `stories` entries of the Storybook main configs of the workspace and
+/// its projects, deepest directory first
+pub fn storybook_specifiers(cwd: &Path, projects: &[Project]) -> Vec<StoriesSpecifier> {
+  let roots = std::iter::once(PathBuf::new()).chain(projects.iter().map(|p| p.root.clone()));
+  let mut specifiers: Vec<StoriesSpecifier> = Vec::new();
+  for root in roots {
+    let config_dir = root.join(".storybook");
+    let Some((file, source)) = MAIN_CONFIG_FILES.iter().find_map(|name| {
+      let file = config_dir.join(name);
+      let source = fs::read_to_string(cwd.join(&file)).ok()?;
+      Some((file, source))
+    }) else {
+      continue;
+    };
+    let Ok(data) = WorkspaceAnalyzer::parse_source(&file, source) else {
+      continue;
+    };
+    let mut finder = StoriesFinder { entries: None };
+    finder.visit_program(data.semantic().nodes().program());
+    for (directory, title_prefix) in finder.entries.unwrap_or_default() {
+      let specifier = StoriesSpecifier {
+        directory: normalize(&config_dir.join(directory)),
+        title_prefix,
+      };
+      if !specifiers.contains(&specifier) {
+        specifiers.push(specifier);
+      }
+    }
+  }
+  specifiers.sort_by_key(|specifier| std::cmp::Reverse(specifier.directory.components().count()));
+  specifiers
+}
+
+/// Finds the first `stories` array and reads its entries as (directory
+/// relative to the config directory, title prefix)
+struct StoriesFinder {
+  entries: Option<Vec<(String, String)>>,
+}
+
+impl<'a> Visit<'a> for StoriesFinder {
+  fn visit_object_expression(&mut self, object: &ObjectExpression<'a>) {
+    if self.entries.is_some() {
+      return;
+    }
+    if let Some(Expression::ArrayExpression(array)) =
+      property(object, "stories").map(|stories| stories.get_inner_expression())
+    {
+      self.entries = Some(
+        array
+          .elements
+          .iter()
+          .filter_map(|element| match element {
+            ArrayExpressionElement::ObjectExpression(entry) => Some((
+              string_property(entry, "directory")?,
+              string_property(entry, "titlePrefix").unwrap_or_default(),
+            )),
+            element => Some((
+              glob_directory(&static_string(element.as_expression()?)?),
+              String::new(),
+            )),
+          })
+          .collect(),
+      );
+      return;
+    }
+    walk::walk_object_expression(self, object);
+  }
+}
+
+/// The leading directories of a glob that contain no glob syntax:
+/// `../src/**/*.stories.tsx` -> `../src`
+fn glob_directory(glob: &str) -> String {
+  let segments: Vec<&str> = glob.split('/').collect();
+  segments[..segments.len() - 1]
+    .iter()
+    .take_while(|segment| !segment.contains(['*', '?', '[', '{', '(', '!']))
+    .copied()
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Resolve `.` and `..` components lexically
+fn normalize(path: &Path) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        normalized.pop();
+      }
+      component => normalized.push(component),
+    }
+  }
+  normalized
+}
+
+/// Top-level `const`/`let`/`var` initializers by binding name
+fn local_initializers<'a>(program: &'a Program<'a>) -> FxHashMap<&'a str, &'a Expression<'a>> {
+  let declarations = program.body.iter().filter_map(|statement| match statement {
+    Statement::VariableDeclaration(declaration) => Some(declaration.as_ref()),
+    Statement::ExportNamedDeclaration(export) => match &export.declaration {
+      Some(Declaration::VariableDeclaration(declaration)) => Some(declaration.as_ref()),
+      _ => None,
+    },
+    _ => None,
+  });
+  declarations
+    .flat_map(|declaration| declaration.declarations.iter())
+    .filter_map(|declarator| match (&declarator.id.kind, &declarator.init) {
+      (BindingPatternKind::BindingIdentifier(id), Some(init)) => Some((id.name.as_str(), init)),
+      _ => None,
+    })
+    .collect()
+}
+
+/// The meta object of `export default { … }` or `export default meta`
+fn default_meta<'a>(
+  declaration: &'a ExportDefaultDeclarationKind<'a>,
+  locals: &FxHashMap<&str, &'a Expression<'a>>,
+) -> Option<&'a ObjectExpression<'a>> {
+  match declaration.as_expression()?.get_inner_expression() {
+    Expression::ObjectExpression(object) => Some(object),
+    Expression::Identifier(id) => object_of(locals.get(id.name.as_str())?),
+    _ => None,
+  }
+}
+
+fn object_of<'a>(expression: &'a Expression<'a>) -> Option<&'a ObjectExpression<'a>> {
+  match expression.get_inner_expression() {
+    Expression::ObjectExpression(object) => Some(object),
+    _ => None,
+  }
+}
+
+fn property<'a>(object: &'a ObjectExpression<'a>, name: &str) -> Option<&'a Expression<'a>> {
+  object
+    .properties
+    .iter()
+    .find_map(|property| match property {
+      ObjectPropertyKind::ObjectProperty(p) if p.key.static_name().as_deref() == Some(name) => {
+        Some(&p.value)
+      }
+      _ => None,
+    })
+}
+
+fn string_property(object: &ObjectExpression, name: &str) -> Option<String> {
+  static_string(property(object, name)?)
+}
+
+/// A property holding an array of strings; regex filters are not evaluated
+fn string_list_property(object: &ObjectExpression, name: &str) -> Option<Vec<String>> {
+  match property(object, name)?.get_inner_expression() {
+    Expression::ArrayExpression(array) => Some(
+      array
+        .elements
+        .iter()
+        .filter_map(|element| static_string(element.as_expression()?))
+        .collect(),
+    ),
+    _ => None,
+  }
+}
+
+/// Names of the exports that can be stories: neither the default export (the
+/// meta) nor `export *`, whose names live in another module
+fn story_exports(exports: &[Export]) -> impl Iterator<Item = String> + '_ {
+  exports
+    .iter()
+    .filter(|export| export.exported_name != "default" && export.exported_name != "*")
+    .filter(|export| !export.is_namespace)
+    .map(|export| export.exported_name.clone())
+}
+
+/// CSF 2 display names: `Primary.storyName = 'Primary button'`
+fn assigned_story_names<'a>(program: &'a Program<'a>) -> FxHashMap<&'a str, String> {
+  program
+    .body
+    .iter()
+    .filter_map(|statement| {
+      let Statement::ExpressionStatement(statement) = statement else {
+        return None;
+      };
+      let Expression::AssignmentExpression(assignment) = &statement.expression else {
+        return None;
+      };
+      let member = assignment.left.as_member_expression()?;
+      if member.static_property_name()? != "storyName" {
+        return None;
+      }
+      let Expression::Identifier(story) = member.object() else {
+        return None;
+      };
+      Some((story.name.as_str(), static_string(&assignment.right)?))
+    })
+    .collect()
+}
+
+/// Storybook's `storyNameFromExport`: lodash `startCase` of the export name
+pub fn start_case(export: &str) -> String {
+  let chars: Vec<char> = export.chars().collect();
+  let mut words: Vec<String> = Vec::new();
+  let mut word = String::new();
+  for (i, &c) in chars.iter().enumerate() {
+    if !c.is_alphanumeric() {
+      if !word.is_empty() {
+        words.push(std::mem::take(&mut word));
+      }
+      continue;
+    }
+    if let Some(&prev) = word.chars().last().as_ref() {
+      let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+      let boundary = (prev.is_lowercase() && c.is_uppercase())
+        || (prev.is_uppercase() && c.is_uppercase() && next_is_lower)
+        || (prev.is_numeric() != c.is_numeric());
+      if boundary {
+        words.push(std::mem::take(&mut word));
+      }
+    }
+    word.push(c);
+  }
+  if !word.is_empty() {
+    words.push(word);
+  }
+  words
+    .iter()
+    .map(|word| {
+      let mut chars = word.chars();
+      chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default()
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Storybook's ID sanitizer: lowercase, separators to `-`, no repeated or
+/// surrounding dashes
+pub fn sanitize(value: &str) -> String {
+  let mut id = String::new();
+  for c in value.to_lowercase().chars() {
+    let c = if ID_SEPARATORS.contains(c) { '-' } else { c };
+    if c == '-' && (id.is_empty() || id.ends_with('-')) {
+      continue;
+    }
+    id.push(c);
+  }
+  id.trim_end_matches('-').to_string()
+}
+
+/// Story filters for a visual regression tool, or the JSON / text listing.
+/// Filter formats print one argument per line and are empty when there is
+/// nothing to snapshot.
+pub fn format_stories(stories: &AffectedStories, format: StoriesFormat) -> String {
+  match format {
+    StoriesFormat::Json => serde_json::to_string(stories).unwrap(),
+    StoriesFormat::Text | StoriesFormat::Ids => stories
+      .stories
+      .iter()
+      .map(|story| story.id.as_str())
+      .collect::<Vec<_>>()
+      .join("\n"),
+    _ if stories.stories.is_empty() => String::new(),
+    // Chromatic matches `Title/Story Name` globs
+    StoriesFormat::Chromatic => stories
+      .stories
+      .iter()
+      .map(|story| format!("--only-story-names={}/{}", story.title, story.name))
+      .collect::<Vec<_>>()
+      .join("\n"),
+    // Loki matches the regex against `Title Story Name`
+    StoriesFormat::Loki => format!(
+      "--storiesFilter=^({})$",
+      stories
+        .stories
+        .iter()
+        .map(|story| regex::escape(&format!("{} {}", story.title, story.name)))
+        .collect::<Vec<_>>()
+        .join("|")
+    ),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::PathBuf;
+
+  fn stories_of(
+    file: &Path,
+    source: &str,
+    specifier: Option<&StoriesSpecifier>,
+  ) -> Option<Vec<AffectedStory>> {
+    let data = WorkspaceAnalyzer::parse_source(file, source.to_string()).unwrap();
+    let exports = WorkspaceAnalyzer::extract_exports(data.semantic().nodes().program());
+    read_stories(file, &data, &exports, specifier)
+  }
+
+  #[test]
+  fn test_read_stories() {
+    let source = r#"
+import type { Meta, StoryObj } from '@storybook/react';
+import { Button } from './button';
+
+const meta = {
+  title: 'Design System/Button',
+  component: Button,
+  excludeStories: ['buttonData'],
+} satisfies Meta<typeof Button>;
+export default meta;
+
+export const buttonData = { label: 'Click' };
+export const Primary: StoryObj<typeof meta> = { args: { primary: true } };
+export const WithIcon2: StoryObj<typeof meta> = { name: 'With an icon' };
+export function HTMLLabel() { return null; }
+HTMLLabel.storyName = 'HTML label';
+export const __namedExportsOrder = ['Primary', 'WithIcon2', 'HTMLLabel'];
+"#;
+    let stories = stories_of(Path::new("button.stories.tsx"), source, None).unwrap();
+    let stories: Vec<(&str, &str)> = stories
+      .iter()
+      .map(|story| (story.id.as_str(), story.name.as_str()))
+      .collect();
+    assert_eq!(
+      stories,
+      [
+        ("design-system-button--primary", "Primary"),
+        ("design-system-button--with-icon-2", "With an icon"),
+        ("design-system-button--html-label", "HTML label"),
+      ]
+    );
+
+    let autotitled = "export default { component: Button };\nexport const Primary = {};\n";
+    let file = Path::new("libs/ui/src/button/button.stories.tsx");
+    assert!(stories_of(file, autotitled, None).is_none());
+    let specifier = StoriesSpecifier {
+      directory: PathBuf::from("libs/ui/src"),
+      title_prefix: "UI".to_string(),
+    };
+    let stories = stories_of(file, autotitled, Some(&specifier)).unwrap();
+    assert_eq!(stories[0].id, "ui-button--primary");
+    assert_eq!(stories[0].title, "UI/button");
+
+    // Stories are the file's exports as the analyzer extracts them
+    let aliased = r#"
+export default { title: 'Card' };
+const Base = {};
+export { Base as Outlined };
+export * from './shared';
+"#;
+    let stories = stories_of(Path::new("card.stories.tsx"), aliased, None).unwrap();
+    let ids: Vec<&str> = stories.iter().map(|story| story.id.as_str()).collect();
+    assert_eq!(ids, ["card--outlined"]);
+  }
+
+  #[test]
+  fn test_autotitle() {
+    let specifier = |directory: &str, title_prefix: &str| StoriesSpecifier {
+      directory: PathBuf::from(directory),
+      title_prefix: title_prefix.to_string(),
+    };
+    let title = |file: &str, specifier: &StoriesSpecifier| autotitle(Path::new(file), specifier);
+
+    let src = specifier("libs/ui/src", "");
+    assert_eq!(
+      title("libs/ui/src/components/Button.stories.tsx", &src),
+      "components/Button"
+    );
+    assert_eq!(
+      title("libs/ui/src/Button/Button.stories.tsx", &src),
+      "Button"
+    );
+    assert_eq!(title("libs/ui/src/forms/index.stories.mdx", &src), "forms");
+    assert_eq!(
+      title(
+        "libs/ui/src/forms/Input.stories.tsx",
+        &specifier("libs/ui/src", "Design/")
+      ),
+      "Design/forms/Input"
+    );
+    assert_eq!(join_title("Design", "/Button/"), "Design/Button");
+
+    assert_eq!(glob_directory("../src/**/*.stories.@(ts|tsx)"), "../src");
+    assert_eq!(glob_directory("../stories/Intro.mdx"), "../stories");
+    assert_eq!(glob_directory("**/*.stories.tsx"), "");
+    assert_eq!(
+      normalize(Path::new("libs/ui/.storybook/../src")),
+      PathBuf::from("libs/ui/src")
+    );
+  }
+
+  #[test]
+  fn test_format_stories() {
+    let story = |title: &str, name: &str, id: &str| AffectedStory {
+      id: id.to_string(),
+      title: title.to_string(),
+      name: name.to_string(),
+      file: PathBuf::from("libs/ui/src/button.stories.tsx"),
+    };
+    let stories = AffectedStories {
+      stories: vec![
+        story("UI/Button", "Primary", "ui-button--primary"),
+        story("UI/Button", "With Icon", "ui-button--with-icon"),
+      ],
+      untitled: vec![],
+      global: false,
+    };
+    assert_eq!(
+      format_stories(&stories, StoriesFormat::Ids),
+      "ui-button--primary\nui-button--with-icon"
+    );
+    assert_eq!(
+      format_stories(&stories, StoriesFormat::Chromatic),
+      "--only-story-names=UI/Button/Primary\n--only-story-names=UI/Button/With Icon"
+    );
+    assert_eq!(
+      format_stories(&stories, StoriesFormat::Loki),
+      r"--storiesFilter=^(UI/Button Primary|UI/Button With Icon)$"
+    );
+    assert_eq!(
+      format_stories(&AffectedStories::default(), StoriesFormat::Loki),
+      ""
+    );
+  }
+}
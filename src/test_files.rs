@@ -0,0 +1,273 @@
+//! Test-only vs production classification of files and affected projects.
+//!
+//! A file is a test file when it matches one of the test globs (the defaults
+//! plus a root package.json `domino.testPatterns` field), when the owning
+//! project's Nx `production` named input leaves it out, or when the owning
+//! project's tsconfig excludes it. An affected project is `test-only` when
+//! every cause recorded for it runs through test files, and `production` as
+//! soon as one cause does not.
+
+use crate::error::{DominoError, Result};
+use crate::named_inputs::{NxInputs, TargetInputs};
+use crate::types::{AffectCause, AffectedScope, Project};
+use crate::utils::{domino_setting, ProjectIndex, MATCH_OPTIONS};
+use glob::Pattern;
+use rustc_hash::FxHashMap;
+use std::path::Path;
+use tracing::debug;
+
+/// Files that only tests, stories and mocks load
+pub const DEFAULT_TEST_PATTERNS: &[&str] = &[
+  "**/*.spec.*",
+  "**/*.test.*",
+  "**/__tests__/**",
+  "**/__mocks__/**",
+  "**/*.stories.*",
+  "**/test-setup.*",
+];
+
+/// Decides whether workspace files are test files
+pub struct TestFiles {
+  patterns: Vec<Pattern>,
+  index: ProjectIndex,
+  /// Each project's Nx `production` input, where one is defined
+  production: FxHashMap<String, TargetInputs>,
+}
+
+impl TestFiles {
+  /// Build the classifier for `projects`, with `extra_patterns` (globs
+  /// relative to `cwd`) on top of the defaults and `domino.testPatterns`
+  pub fn new(cwd: &Path, projects: &[Project], extra_patterns: &[String]) -> Result<Self> {
+    let configured: Vec<String> = domino_setting(cwd, "testPatterns").unwrap_or_default();
+    let patterns = DEFAULT_TEST_PATTERNS
+      .iter()
+      .map(|pattern| pattern.to_string())
+      .chain(configured)
+      .chain(extra_patterns.iter().cloned())
+      .map(|pattern| {
+        Pattern::new(&pattern)
+          .map_err(|e| DominoError::Other(format!("Invalid test pattern '{}': {}", pattern, e)))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(Self::with_patterns(cwd, projects, patterns))
+  }
+
+  /// Classifier using only [`DEFAULT_TEST_PATTERNS`], for callers that must
+  /// not fail on an invalid configured glob
+  pub fn with_default_patterns(cwd: &Path, projects: &[Project]) -> Self {
+    let patterns = DEFAULT_TEST_PATTERNS
+      .iter()
+      .filter_map(|pattern| Pattern::new(pattern).ok())
+      .collect();
+    Self::with_patterns(cwd, projects, patterns)
+  }
+
+  fn with_patterns(cwd: &Path, projects: &[Project], patterns: Vec<Pattern>) -> Self {
+    let production = NxInputs::load(cwd, projects)
+      .map(|inputs| {
+        projects
+          .iter()
+          .map(|project| {
+            (
+              project.name.clone(),
+              inputs.named_input(&project.name, "production"),
+            )
+          })
+          .filter(|(_, production)| !production.files.is_empty())
+          .collect()
+      })
+      .unwrap_or_default();
+
+    Self {
+      patterns,
+      index: ProjectIndex::new(projects, cwd),
+      production,
+    }
+  }
+
+  /// Whether the workspace-relative `file` matches one of the test globs
+  pub fn matches_pattern(&self, file: &Path) -> bool {
+    self
+      .patterns
+      .iter()
+      .any(|pattern| pattern.matches_path_with(file, MATCH_OPTIONS))
+  }
+
+  /// Whether the workspace-relative `file` only matters to tests
+  pub fn is_test_file(&self, file: &Path) -> bool {
+    if self.matches_pattern(file) {
+      return true;
+    }
+
+    let owners = self.index.get_owning_packages_by_path(file);
+    if owners.is_empty() {
+      return false;
+    }
+    let outside_production = owners.iter().all(|owner| {
+      self
+        .production
+        .get(owner)
+        .is_some_and(|production| !production.matches(file))
+    });
+    // Files every owning tsconfig excludes are not compiled into the project
+    outside_production || self.index.get_package_names_by_path(file).is_empty()
+  }
+
+  /// Label every project in `causes` `production` or `test-only`.
+  ///
+  /// Implicit dependencies take the label of the project they depend on, so
+  /// they are resolved after every other cause.
+  pub fn classify(
+    &self,
+    causes: &FxHashMap<String, Vec<AffectCause>>,
+  ) -> FxHashMap<String, AffectedScope> {
+    let mut scopes: FxHashMap<String, AffectedScope> = causes
+      .iter()
+      .map(|(project, project_causes)| {
+        let production = project_causes
+          .iter()
+          .any(|cause| self.is_production_cause(cause));
+        (project.clone(), scope(production))
+      })
+      .collect();
+
+    let mut changed = true;
+    while changed {
+      changed = false;
+      for (project, project_causes) in causes {
+        if scopes[project] == AffectedScope::Production {
+          continue;
+        }
+        let through_production = project_causes.iter().any(|cause| {
+          matches!(cause, AffectCause::ImplicitDependency { depends_on }
+            if scopes.get(depends_on) == Some(&AffectedScope::Production))
+        });
+        if through_production {
+          debug!(
+            "{} is affected in production through an implicit dependency",
+            project
+          );
+          scopes.insert(project.clone(), AffectedScope::Production);
+          changed = true;
+        }
+      }
+    }
+    scopes
+  }
+
+  fn is_production_cause(&self, cause: &AffectCause) -> bool {
+    match cause {
+      AffectCause::DirectChange { file, .. } => !self.is_test_file(file),
+      AffectCause::ImportedSymbol {
+        via_file,
+        source_file,
+        ..
+      } => !self.is_test_file(via_file) && !self.is_test_file(source_file),
+      AffectCause::ReExported {
+        through_file,
+        source_file,
+        ..
+      } => !self.is_test_file(through_file) && !self.is_test_file(source_file),
+      AffectCause::AssetChange { referenced_in, .. } => !self.is_test_file(referenced_in),
+      AffectCause::LockfileChange { importing_file, .. } => !self.is_test_file(importing_file),
+      AffectCause::ImplicitDependency { .. } => false,
+      AffectCause::GlobalInvalidation { .. } => true,
+    }
+  }
+}
+
+fn scope(production: bool) -> AffectedScope {
+  if production {
+    AffectedScope::Production
+  } else {
+    AffectedScope::TestOnly
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+  use std::path::PathBuf;
+
+  fn project(name: &str, root: &str) -> Project {
+    Project {
+      name: name.to_string(),
+      root: PathBuf::from(root),
+      source_root: PathBuf::from(root).join("src"),
+      ts_config: None,
+      implicit_dependencies: vec![],
+      targets: vec![],
+      tags: vec![],
+    }
+  }
+
+  #[test]
+  fn test_is_test_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    fs::write(
+      root.join("nx.json"),
+      r#"{ "namedInputs": { "production": ["{projectRoot}/src/**/*", "!{projectRoot}/src/testing/**"] } }"#,
+    )
+    .unwrap();
+    fs::write(
+      root.join("package.json"),
+      r#"{ "name": "root", "domino": { "testPatterns": ["**/fixtures/**"] } }"#,
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("libs/ui")).unwrap();
+    fs::write(root.join("libs/ui/project.json"), r#"{ "name": "ui" }"#).unwrap();
+
+    let test_files = TestFiles::new(root, &[project("ui", "libs/ui")], &[]).unwrap();
+    assert!(test_files.is_test_file(Path::new("libs/ui/src/button.spec.tsx")));
+    assert!(test_files.is_test_file(Path::new("libs/ui/src/button.stories.tsx")));
+    assert!(test_files.is_test_file(Path::new("libs/ui/src/fixtures/user.ts")));
+    assert!(test_files.is_test_file(Path::new("libs/ui/src/testing/render.ts")));
+    assert!(!test_files.is_test_file(Path::new("libs/ui/src/button.tsx")));
+    assert!(!test_files.is_test_file(Path::new("tools/build.js")));
+  }
+
+  #[test]
+  fn test_classify() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let projects = [project("ui", "libs/ui"), project("web", "apps/web")];
+    let test_files = TestFiles::new(dir.path(), &projects, &[]).unwrap();
+    let direct = |file: &str| AffectCause::DirectChange {
+      file: PathBuf::from(file),
+      symbol: None,
+      line: 1,
+    };
+    let imported = |via_file: &str| AffectCause::ImportedSymbol {
+      source_project: "ui".to_string(),
+      symbol: "button".to_string(),
+      via_file: PathBuf::from(via_file),
+      source_file: PathBuf::from("libs/ui/src/button.ts"),
+      type_only: false,
+    };
+    let implicit = |depends_on: &str| AffectCause::ImplicitDependency {
+      depends_on: depends_on.to_string(),
+    };
+
+    let causes: FxHashMap<String, Vec<AffectCause>> = [
+      ("ui", vec![direct("libs/ui/src/button.spec.ts")]),
+      (
+        "web",
+        vec![imported("apps/web/src/page.test.tsx"), implicit("shared")],
+      ),
+      ("shared", vec![direct("libs/shared/src/index.ts")]),
+      ("web-e2e", vec![implicit("web")]),
+      ("ui-e2e", vec![implicit("ui")]),
+    ]
+    .into_iter()
+    .map(|(name, causes)| (name.to_string(), causes))
+    .collect();
+
+    let scopes = test_files.classify(&causes);
+    assert_eq!(scopes["ui"], AffectedScope::TestOnly);
+    assert_eq!(scopes["shared"], AffectedScope::Production);
+    assert_eq!(scopes["web"], AffectedScope::Production);
+    assert_eq!(scopes["web-e2e"], AffectedScope::Production);
+    assert_eq!(scopes["ui-e2e"], AffectedScope::TestOnly);
+  }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -43,6 +44,115 @@ impl FromStr for LockfileStrategy {
   }
 }
 
+/// Which dependency edges propagate a change to consumers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Propagation {
+  /// Follow every edge, including type-only ones (what type-checking needs)
+  #[default]
+  All,
+  /// Skip type-only edges (`import type`, type-position usage): only changes
+  /// that reach emitted JavaScript propagate
+  Runtime,
+}
+
+impl fmt::Display for Propagation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Propagation::All => write!(f, "all"),
+      Propagation::Runtime => write!(f, "runtime"),
+    }
+  }
+}
+
+impl FromStr for Propagation {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "all" => Ok(Propagation::All),
+      "runtime" => Ok(Propagation::Runtime),
+      _ => Err(format!(
+        "Invalid propagation mode '{}'. Expected: all, runtime",
+        s
+      )),
+    }
+  }
+}
+
+/// Whether an affected project is affected through production code, or only
+/// through test files (specs, stories, test helpers)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AffectedScope {
+  /// At least one cause runs through non-test files
+  Production,
+  /// Every cause runs through test files
+  TestOnly,
+}
+
+impl fmt::Display for AffectedScope {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      AffectedScope::Production => write!(f, "production"),
+      AffectedScope::TestOnly => write!(f, "test-only"),
+    }
+  }
+}
+
+impl FromStr for AffectedScope {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "production" => Ok(AffectedScope::Production),
+      "test-only" => Ok(AffectedScope::TestOnly),
+      _ => Err(format!(
+        "Invalid scope '{}'. Expected: production, test-only",
+        s
+      )),
+    }
+  }
+}
+
+/// Whether implementation-only changes propagate across project boundaries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum BoundaryMode {
+  /// Any change to an imported symbol affects consuming projects
+  #[default]
+  Full,
+  /// Consuming projects are only affected when the exported declaration's
+  /// signature differs between base and head
+  Signature,
+}
+
+impl fmt::Display for BoundaryMode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BoundaryMode::Full => write!(f, "full"),
+      BoundaryMode::Signature => write!(f, "signature"),
+    }
+  }
+}
+
+impl FromStr for BoundaryMode {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "full" => Ok(BoundaryMode::Full),
+      "signature" => Ok(BoundaryMode::Signature),
+      _ => Err(format!(
+        "Invalid boundary mode '{}'. Expected: full, signature",
+        s
+      )),
+    }
+  }
+}
+
 /// A project in the workspace
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -56,8 +166,10 @@ pub struct Project {
   pub ts_config: Option<PathBuf>,
   /// Implicit dependencies (projects that should be marked affected when this one changes)
   pub implicit_dependencies: Vec<String>,
-  /// Available targets (Nx only)
+  /// Available targets: project.json `targets`, or package.json `scripts`
   pub targets: Vec<String>,
+  /// Tags from project.json `tags`, or a package.json `domino.tags` field
+  pub tags: Vec<String>,
 }
 
 /// A file with changed lines
@@ -87,6 +199,9 @@ pub struct Reference {
   /// Column number (0-indexed)
   #[allow(dead_code)]
   pub column: usize,
+  /// Whether the reference only reaches the symbol's type: it sits in a type
+  /// position (`x: Foo`, `typeof foo`) or goes through a type-only import
+  pub is_type_only: bool,
 }
 
 /// A reference to a non-source asset in a source file
@@ -117,12 +232,17 @@ pub struct Import {
   #[allow(dead_code)]
   pub resolved_file: Option<PathBuf>,
   /// Whether this is a type-only import
-  #[allow(dead_code)]
   pub is_type_only: bool,
   /// Whether this import comes from a dynamic import() expression.
   /// Dynamic imports with string literal specifiers are treated like static
   /// namespace imports — only explicit member access propagates changes.
   pub is_dynamic: bool,
+  /// Whether this import comes from a `new URL('./worker.ts', import.meta.url)`
+  /// module reference (Web Workers, SharedWorkers, service workers). Bundlers
+  /// emit the referenced module as its own entry point and load it as a whole,
+  /// so any change inside it affects the referencing site — there is no member
+  /// access to narrow on.
+  pub is_module_url: bool,
 }
 
 /// Export information
@@ -134,6 +254,10 @@ pub struct Export {
   pub local_name: Option<String>,
   /// If this is a re-export, the module it's re-exported from
   pub re_export_from: Option<String>,
+  /// Whether this is a namespace re-export (`export * as ns from './utils'`).
+  /// `exported_name` is then the namespace binding, and every export of the
+  /// source module is reachable only as a member of it (`ns.formatDate`).
+  pub is_namespace: bool,
 }
 
 /// Configuration for the true affected algorithm
@@ -149,6 +273,13 @@ pub struct TrueAffectedConfig {
   pub projects: Vec<Project>,
   /// Lockfile change detection strategy
   pub lockfile_strategy: LockfileStrategy,
+  /// Trace changes to individual class, object literal and enum members
+  /// instead of whole declarations
+  pub member_precision: bool,
+  /// Whether type-only edges propagate changes
+  pub propagation: Propagation,
+  /// Whether implementation-only changes cross project boundaries
+  pub boundary_mode: BoundaryMode,
 }
 
 /// Result of the true affected analysis
@@ -161,6 +292,20 @@ pub struct AffectedResult {
   pub report: Option<AffectedReport>,
 }
 
+/// A target of an affected project, displayed as the `project:target` pair
+/// task runners accept (`nx run web:build`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProjectTarget {
+  pub project: String,
+  pub target: String,
+}
+
+impl fmt::Display for ProjectTarget {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}", self.project, self.target)
+  }
+}
+
 /// Detailed report of affected projects with causality information
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -215,6 +360,8 @@ pub struct AffectedProjectInfo {
   pub name: String,
   /// Reasons why this project is affected
   pub causes: Vec<AffectCause>,
+  /// Whether any cause runs through production (non-test) files
+  pub scope: AffectedScope,
 }
 
 /// Reason why a project is affected
@@ -242,6 +389,10 @@ pub enum AffectCause {
     via_file: PathBuf,
     /// Original file where symbol was changed
     source_file: PathBuf,
+    /// The import is only used in type positions, so the dependency
+    /// disappears from emitted JavaScript
+    #[serde(default)]
+    type_only: bool,
   },
   /// Re-exported a changed symbol
   #[serde(rename = "re_exported")]
@@ -289,6 +440,507 @@ pub enum AffectCause {
   },
 }
 
+/// Kind of an exported declaration in a project's public API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiKind {
+  Function,
+  Class,
+  Interface,
+  Type,
+  Enum,
+  Namespace,
+  Variable,
+  /// Forwarded from a module outside the workspace (or one that failed to
+  /// resolve), so only the specifier is known
+  ReExport,
+}
+
+impl fmt::Display for ApiKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ApiKind::Function => write!(f, "function"),
+      ApiKind::Class => write!(f, "class"),
+      ApiKind::Interface => write!(f, "interface"),
+      ApiKind::Type => write!(f, "type"),
+      ApiKind::Enum => write!(f, "enum"),
+      ApiKind::Namespace => write!(f, "namespace"),
+      ApiKind::Variable => write!(f, "variable"),
+      ApiKind::ReExport => write!(f, "re-export"),
+    }
+  }
+}
+
+/// A single export of a project entry point
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiEntry {
+  /// Name the symbol is exported under (`default` for the default export)
+  pub name: String,
+  pub kind: ApiKind,
+  /// Declaration text without bodies, initializers or comments, with
+  /// whitespace normalized so formatting-only edits compare equal
+  pub signature: String,
+}
+
+/// An export present on both sides whose kind or signature changed
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiChange {
+  pub name: String,
+  /// Kind at head
+  pub kind: ApiKind,
+  /// Signature at the base revision
+  pub base: String,
+  /// Signature at head
+  pub head: String,
+}
+
+/// Public API differences of one project entry point
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectApiDiff {
+  /// Project name
+  pub project: String,
+  /// Entry point the API was read from (relative to workspace root)
+  pub entry_point: PathBuf,
+  pub added: Vec<ApiEntry>,
+  pub removed: Vec<ApiEntry>,
+  pub changed: Vec<ApiChange>,
+}
+
+impl ProjectApiDiff {
+  /// Whether the entry point's API is unchanged
+  pub fn is_empty(&self) -> bool {
+    self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+  }
+}
+
+/// Semver bump suggested for a publishable package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpType {
+  Patch,
+  Minor,
+  Major,
+}
+
+impl fmt::Display for BumpType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BumpType::Patch => write!(f, "patch"),
+      BumpType::Minor => write!(f, "minor"),
+      BumpType::Major => write!(f, "major"),
+    }
+  }
+}
+
+/// A publishable workspace package affected by the diff
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageBump {
+  /// npm package name from package.json
+  pub package: String,
+  /// Workspace project name
+  pub project: String,
+  pub bump: BumpType,
+  /// Why the package changed, most significant first
+  pub reasons: Vec<String>,
+}
+
+/// A strongly connected component of the import graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportCycle {
+  /// Files or projects in the cycle, sorted
+  pub members: Vec<String>,
+  /// One shortest import path through the cycle, starting and ending at the
+  /// first member
+  pub path: Vec<String>,
+  /// For project cycles, an importing file and the file it imports for each
+  /// hop of `path`
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub via: Vec<(String, String)>,
+}
+
+/// Import cycles at file and project granularity
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CycleReport {
+  /// Cycles between files through runtime imports and re-exports
+  pub files: Vec<ImportCycle>,
+  /// Cycles between projects through any static import
+  pub projects: Vec<ImportCycle>,
+}
+
+impl CycleReport {
+  /// Whether no cycle was found
+  pub fn is_empty(&self) -> bool {
+    self.files.is_empty() && self.projects.is_empty()
+  }
+}
+
+/// An export that nothing in the workspace imports
+#[derive(Debug, Clone, Serialize)]
+pub struct UnusedExport {
+  /// Project owning the file
+  pub project: String,
+  /// File declaring the export (relative to workspace root)
+  pub file: PathBuf,
+  /// Exported name (`default` for default exports)
+  pub name: String,
+  /// Line of the export statement (1-indexed)
+  pub line: usize,
+}
+
+/// How one project depends on another: Nx's `static`, `dynamic` and
+/// `implicit`, plus `type-only` for dependencies that only exist for the
+/// type checker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyType {
+  Static,
+  Dynamic,
+  TypeOnly,
+  Implicit,
+}
+
+impl fmt::Display for DependencyType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DependencyType::Static => write!(f, "static"),
+      DependencyType::Dynamic => write!(f, "dynamic"),
+      DependencyType::TypeOnly => write!(f, "type-only"),
+      DependencyType::Implicit => write!(f, "implicit"),
+    }
+  }
+}
+
+/// A project-to-project edge of the [`ProjectGraph`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectDependency {
+  pub source: String,
+  pub target: String,
+  #[serde(rename = "type")]
+  pub dependency_type: DependencyType,
+}
+
+/// A project node of the [`ProjectGraph`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectGraphNode {
+  pub name: String,
+  /// `app`, `lib` or `e2e`
+  #[serde(rename = "type")]
+  pub project_type: String,
+  pub data: ProjectGraphNodeData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGraphNodeData {
+  pub root: PathBuf,
+  pub source_root: PathBuf,
+}
+
+/// Project dependency graph in the shape of Nx's `nx graph --file` output
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectGraph {
+  pub nodes: BTreeMap<String, ProjectGraphNode>,
+  /// Outgoing edges of every project, keyed by source project
+  pub dependencies: BTreeMap<String, Vec<ProjectDependency>>,
+}
+
+/// A workspace package dependency whose package.json declaration disagrees
+/// with the imports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyDrift {
+  /// Depending project
+  pub project: String,
+  /// npm name of the workspace package depended on
+  pub dependency: String,
+}
+
+/// The project graph derived from source imports, with the package.json
+/// declarations it contradicts
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGraphReport {
+  pub graph: ProjectGraph,
+  /// Imported workspace packages missing from the importer's package.json
+  pub undeclared_dependencies: Vec<DependencyDrift>,
+  /// Workspace packages in a package.json that the project never imports
+  pub unused_dependencies: Vec<DependencyDrift>,
+}
+
+/// An Nx `depConstraints` entry: what projects tagged `source_tag` may import
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepConstraint {
+  /// Tag (or `*`-glob) selecting the constrained projects
+  pub source_tag: String,
+  /// The imported project must carry at least one of these tags
+  #[serde(default)]
+  pub only_depend_on_libs_with_tags: Vec<String>,
+  /// The imported project must carry none of these tags
+  #[serde(default)]
+  pub not_depend_on_libs_with_tags: Vec<String>,
+}
+
+/// An import crossing a module boundary its project's tags forbid
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BoundaryViolation {
+  /// Importing file (relative to workspace root)
+  pub file: PathBuf,
+  /// Line of the import (1-indexed)
+  pub line: usize,
+  /// Module specifier as written
+  pub specifier: String,
+  /// Importing project
+  pub project: String,
+  /// Imported project
+  pub dependency: String,
+  /// Which constraint the import breaks
+  pub message: String,
+}
+
+/// Output format of `domino project-graph`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+  #[default]
+  Text,
+  /// Nx project graph JSON
+  Json,
+  /// Graphviz DOT
+  Dot,
+}
+
+impl fmt::Display for GraphFormat {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      GraphFormat::Text => write!(f, "text"),
+      GraphFormat::Json => write!(f, "json"),
+      GraphFormat::Dot => write!(f, "dot"),
+    }
+  }
+}
+
+impl FromStr for GraphFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "text" => Ok(GraphFormat::Text),
+      "json" => Ok(GraphFormat::Json),
+      "dot" => Ok(GraphFormat::Dot),
+      _ => Err(format!(
+        "Invalid graph format '{}'. Expected: text, json, dot",
+        s
+      )),
+    }
+  }
+}
+
+/// Files reached by a change (see `core::find_affected_files`)
+#[derive(Debug, Clone, Default)]
+pub struct AffectedFiles {
+  /// Changed files and every file referencing a changed symbol, sorted
+  pub files: Vec<PathBuf>,
+  /// The changed files themselves, sorted
+  pub changed: Vec<PathBuf>,
+  /// Changed global inputs, which affect every file
+  pub global_triggers: Vec<GlobalTrigger>,
+}
+
+/// Test files that can observe a change
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedTests {
+  /// Test files, relative to the workspace root, sorted
+  pub files: Vec<PathBuf>,
+  /// A global input changed, so `files` lists every test file
+  pub global: bool,
+}
+
+/// Output format of `domino affected-tests`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestsFormat {
+  #[default]
+  Text,
+  Json,
+  /// A `--testPathPattern` argument for Jest
+  Jest,
+  /// File filters for `vitest run`
+  Vitest,
+  /// File filters for `playwright test`, including component tests
+  Playwright,
+}
+
+impl fmt::Display for TestsFormat {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TestsFormat::Text => write!(f, "text"),
+      TestsFormat::Json => write!(f, "json"),
+      TestsFormat::Jest => write!(f, "jest"),
+      TestsFormat::Vitest => write!(f, "vitest"),
+      TestsFormat::Playwright => write!(f, "playwright"),
+    }
+  }
+}
+
+impl FromStr for TestsFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "text" => Ok(TestsFormat::Text),
+      "json" => Ok(TestsFormat::Json),
+      "jest" => Ok(TestsFormat::Jest),
+      "vitest" => Ok(TestsFormat::Vitest),
+      "playwright" => Ok(TestsFormat::Playwright),
+      _ => Err(format!(
+        "Invalid tests format '{}'. Expected: text, json, jest, vitest, playwright",
+        s
+      )),
+    }
+  }
+}
+
+/// How a chunk is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChunkKind {
+  /// Loaded on startup from an entry point
+  Entry,
+  /// Loaded on demand through a dynamic `import()`
+  Lazy,
+  /// Loaded as a worker through `new URL(…, import.meta.url)`
+  Worker,
+}
+
+impl fmt::Display for ChunkKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ChunkKind::Entry => write!(f, "entry"),
+      ChunkKind::Lazy => write!(f, "lazy"),
+      ChunkKind::Worker => write!(f, "worker"),
+    }
+  }
+}
+
+/// A bundle chunk containing files reached by a change
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedChunk {
+  /// Project owning the chunk root
+  pub project: String,
+  pub kind: ChunkKind,
+  /// Entry point or lazily loaded module the chunk starts from
+  pub root: PathBuf,
+  /// Reached files in the chunk, sorted; empty after a global change
+  pub files: Vec<PathBuf>,
+}
+
+/// Entry point and lazy chunks that can behave differently after a change
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedChunks {
+  /// Chunks sorted by project, kind and root
+  pub chunks: Vec<AffectedChunk>,
+  /// Some entry point chunk, loaded on startup, is affected
+  pub initial: bool,
+  /// A global input changed, so `chunks` lists every chunk
+  pub global: bool,
+}
+
+/// A file-system route whose module, or a module rendered around it, is affected
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedRoute {
+  /// Project serving the route
+  pub project: String,
+  /// URL path in the router's notation, e.g. `/billing/[id]` or `/billing/$id`
+  pub route: String,
+  /// Route module, relative to the workspace root
+  pub file: PathBuf,
+}
+
+/// Routes that can behave differently after a change
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedRoutes {
+  /// Routes sorted by project and path
+  pub routes: Vec<AffectedRoute>,
+  /// A global input changed, so `routes` lists every route
+  pub global: bool,
+}
+
+/// A Storybook story exported by an affected story file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedStory {
+  /// Story ID, e.g. `design-system-button--primary`
+  pub id: String,
+  /// Title of the story file's default export
+  pub title: String,
+  /// Display name of the story
+  pub name: String,
+  /// Story file, relative to the workspace root
+  pub file: PathBuf,
+}
+
+/// Storybook stories that can render differently after a change
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedStories {
+  /// Stories in story file order
+  pub stories: Vec<AffectedStory>,
+  /// Reached story files whose stories are missing from `stories`: they have
+  /// no static title, and no Storybook `stories` entry covers them to derive
+  /// one
+  pub untitled: Vec<PathBuf>,
+  /// A global input changed, so `stories` lists every story
+  pub global: bool,
+}
+
+/// Output format of `domino affected-stories`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StoriesFormat {
+  #[default]
+  Text,
+  Json,
+  /// Story IDs, one per line
+  Ids,
+  /// `--only-story-names` arguments for Chromatic, one per line
+  Chromatic,
+  /// A `--storiesFilter` argument for Loki
+  Loki,
+}
+
+impl fmt::Display for StoriesFormat {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      StoriesFormat::Text => write!(f, "text"),
+      StoriesFormat::Json => write!(f, "json"),
+      StoriesFormat::Ids => write!(f, "ids"),
+      StoriesFormat::Chromatic => write!(f, "chromatic"),
+      StoriesFormat::Loki => write!(f, "loki"),
+    }
+  }
+}
+
+impl FromStr for StoriesFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.to_lowercase().as_str() {
+      "text" => Ok(StoriesFormat::Text),
+      "json" => Ok(StoriesFormat::Json),
+      "ids" => Ok(StoriesFormat::Ids),
+      "chromatic" => Ok(StoriesFormat::Chromatic),
+      "loki" => Ok(StoriesFormat::Loki),
+      _ => Err(format!(
+        "Invalid stories format '{}'. Expected: text, json, ids, chromatic, loki",
+        s
+      )),
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -349,4 +1001,28 @@ mod tests {
   fn test_lockfile_strategy_default() {
     assert_eq!(LockfileStrategy::default(), LockfileStrategy::Direct);
   }
+
+  #[test]
+  fn test_propagation_from_str() {
+    assert_eq!("all".parse::<Propagation>().unwrap(), Propagation::All);
+    assert_eq!(
+      "Runtime".parse::<Propagation>().unwrap(),
+      Propagation::Runtime
+    );
+    assert!("types".parse::<Propagation>().is_err());
+    assert_eq!(Propagation::default(), Propagation::All);
+    assert_eq!(Propagation::Runtime.to_string(), "runtime");
+  }
+
+  #[test]
+  fn test_boundary_mode_from_str() {
+    assert_eq!("full".parse::<BoundaryMode>().unwrap(), BoundaryMode::Full);
+    assert_eq!(
+      "Signature".parse::<BoundaryMode>().unwrap(),
+      BoundaryMode::Signature
+    );
+    assert!("api".parse::<BoundaryMode>().is_err());
+    assert_eq!(BoundaryMode::default(), BoundaryMode::Full);
+    assert_eq!(BoundaryMode::Signature.to_string(), "signature");
+  }
 }
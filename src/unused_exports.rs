@@ -0,0 +1,302 @@
+//! Unused export detection (`domino unused-exports`).
+//!
+//! An export is used when the import index has an importer for it, when a
+//! namespace or dynamic import of its file reads it as a member (or lets the
+//! namespace escape), when its file is loaded whole through
+//! `new URL(…, import.meta.url)`, or when a file re-exporting it has that
+//! re-export used in turn. Exports of entry points — package entry files and
+//! files matching the entry globs (framework pages, stories, tool configs) —
+//! are consumed from outside the workspace graph and always count as used.
+
+use crate::api_diff;
+use crate::error::{DominoError, Result};
+use crate::profiler::Profiler;
+use crate::semantic::WorkspaceAnalyzer;
+use crate::types::{Project, UnusedExport};
+use crate::utils::{offset_to_line_col, ProjectIndex, MATCH_OPTIONS};
+use glob::Pattern;
+use oxc_ast::ast::{Declaration, Program, Statement};
+use oxc_span::GetSpan;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Files whose exports are read by frameworks and tools rather than imported:
+/// Next.js `pages/` and `app/` route files, Remix routes, Storybook stories
+/// and `*.config.*` files
+pub const DEFAULT_ENTRY_PATTERNS: &[&str] = &[
+  "**/pages/**",
+  "**/app/**/page.*",
+  "**/app/**/layout.*",
+  "**/app/**/template.*",
+  "**/app/**/route.*",
+  "**/app/**/loading.*",
+  "**/app/**/error.*",
+  "**/app/**/not-found.*",
+  "**/app/routes/**",
+  "**/middleware.*",
+  "**/*.stories.*",
+  "**/*.config.*",
+];
+
+/// Find the exports nothing in the workspace uses, sorted by file and line.
+///
+/// `entry_patterns` are globs relative to `cwd` whose files may have unused
+/// exports, in addition to each project's package entry point.
+pub fn find_unused_exports(
+  cwd: &Path,
+  projects: Vec<Project>,
+  entry_patterns: &[String],
+  profiler: Arc<Profiler>,
+) -> Result<Vec<UnusedExport>> {
+  let patterns = entry_patterns
+    .iter()
+    .map(|pattern| {
+      Pattern::new(pattern)
+        .map_err(|e| DominoError::Other(format!("Invalid entry pattern '{}': {}", pattern, e)))
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let analyzer = WorkspaceAnalyzer::new(projects.clone(), cwd, profiler)?;
+  let mut entries: FxHashSet<PathBuf> = projects
+    .iter()
+    .filter_map(|project| api_diff::find_entry_point(&analyzer, cwd, project))
+    .collect();
+  entries.extend(
+    analyzer
+      .files
+      .keys()
+      .filter(|file| {
+        patterns
+          .iter()
+          .any(|pattern| pattern.matches_path_with(file, MATCH_OPTIONS))
+      })
+      .cloned(),
+  );
+  debug!("{} entry files may keep unused exports", entries.len());
+
+  let index = ProjectIndex::new(&projects, cwd);
+  let mut usage = ExportUsage {
+    analyzer: &analyzer,
+    entries: &entries,
+    used: FxHashMap::default(),
+    in_progress: FxHashSet::default(),
+  };
+
+  let mut unused = Vec::new();
+  for (file, exports) in &analyzer.exports {
+    if entries.contains(file) {
+      continue;
+    }
+    for export in exports {
+      // `export * from` forwards names rather than declaring one
+      if export.exported_name == "*" || usage.is_used(file, &export.exported_name) {
+        continue;
+      }
+      let line = analyzer
+        .files
+        .get(file)
+        .and_then(|data| {
+          let program = data.semantic().nodes().program();
+          export_offset(program, &export.exported_name)
+            .map(|offset| offset_to_line_col(data.source(), offset).0)
+        })
+        .unwrap_or(1);
+      unused.push(UnusedExport {
+        project: index
+          .get_owning_packages_by_path(file)
+          .into_iter()
+          .next()
+          .unwrap_or_default(),
+        file: file.clone(),
+        name: export.exported_name.clone(),
+        line,
+      });
+    }
+  }
+
+  unused.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+  Ok(unused)
+}
+
+/// Memoized "is this export used" queries over the analyzer's indexes
+struct ExportUsage<'a> {
+  analyzer: &'a WorkspaceAnalyzer,
+  entries: &'a FxHashSet<PathBuf>,
+  used: FxHashMap<(PathBuf, String), bool>,
+  /// Exports being resolved, so re-export cycles end instead of recursing
+  in_progress: FxHashSet<(PathBuf, String)>,
+}
+
+impl ExportUsage<'_> {
+  fn is_used(&mut self, file: &Path, name: &str) -> bool {
+    let key = (file.to_path_buf(), name.to_string());
+    if let Some(&used) = self.used.get(&key) {
+      return used;
+    }
+    if !self.in_progress.insert(key.clone()) {
+      return false;
+    }
+    let used = self.check(file, name);
+    self.in_progress.remove(&key);
+    // A negative answer reached inside a re-export cycle may only hold for
+    // the query that entered the cycle, so only cache it at the top level
+    if used || self.in_progress.is_empty() {
+      self.used.insert(key, used);
+    }
+    used
+  }
+
+  fn check(&mut self, file: &Path, name: &str) -> bool {
+    let analyzer = self.analyzer;
+    if self.entries.contains(file) {
+      return true;
+    }
+
+    let named_key = (file.to_path_buf(), name.to_string());
+    if analyzer
+      .import_index
+      .get(&named_key)
+      .is_some_and(|importers| !importers.is_empty())
+    {
+      return true;
+    }
+
+    let namespace_key = (file.to_path_buf(), "*".to_string());
+    for importer in analyzer
+      .import_index
+      .get(&namespace_key)
+      .into_iter()
+      .flatten()
+    {
+      if importer.is_module_url {
+        return true;
+      }
+      let access = if importer.is_dynamic {
+        analyzer.find_dynamic_import_member_access(
+          &importer.importing_file,
+          &importer.from_module,
+          name,
+        )
+      } else {
+        analyzer.find_namespace_member_access(&importer.importing_file, &importer.local_name, name)
+      };
+      match access {
+        Ok(references) if references.is_empty() => {}
+        Ok(_) => return true,
+        Err(e) => {
+          debug!(
+            "Treating {}#{} as used: member access lookup in {:?} failed: {}",
+            file.display(),
+            name,
+            importer.importing_file,
+            e
+          );
+          return true;
+        }
+      }
+    }
+
+    for (reexporting_file, export) in analyzer.reexport_index.get(file).into_iter().flatten() {
+      let forwarded = if export.is_namespace {
+        Some(export.exported_name.as_str())
+      } else if export.exported_name == "*" {
+        // `export *` does not forward the default export
+        (name != "default").then_some(name)
+      } else if export
+        .local_name
+        .as_deref()
+        .unwrap_or(&export.exported_name)
+        == name
+      {
+        Some(export.exported_name.as_str())
+      } else {
+        None
+      };
+      if forwarded.is_some_and(|forwarded| self.is_used(reexporting_file, forwarded)) {
+        return true;
+      }
+    }
+
+    false
+  }
+}
+
+/// Offset of the export statement that exports `name`
+fn export_offset(program: &Program, name: &str) -> Option<usize> {
+  program.body.iter().find_map(|statement| {
+    let exports_name = match statement {
+      Statement::ExportDefaultDeclaration(_) => name == "default",
+      Statement::ExportAllDeclaration(export) => export
+        .exported
+        .as_ref()
+        .is_some_and(|exported| exported.name() == name),
+      Statement::ExportNamedDeclaration(export) => {
+        export
+          .specifiers
+          .iter()
+          .any(|specifier| specifier.exported.name() == name)
+          || export
+            .declaration
+            .as_ref()
+            .is_some_and(|declaration| declares(declaration, name))
+      }
+      _ => false,
+    };
+    exports_name.then(|| statement.span().start as usize)
+  })
+}
+
+fn declares(declaration: &Declaration, name: &str) -> bool {
+  match declaration {
+    Declaration::VariableDeclaration(variables) => {
+      variables.declarations.iter().any(|declarator| {
+        declarator
+          .id
+          .get_identifier_name()
+          .is_some_and(|id| id == name)
+      })
+    }
+    Declaration::FunctionDeclaration(function) => {
+      function.id.as_ref().is_some_and(|id| id.name == name)
+    }
+    Declaration::ClassDeclaration(class) => class.id.as_ref().is_some_and(|id| id.name == name),
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_entry_patterns() {
+    let is_entry = |path: &str| {
+      DEFAULT_ENTRY_PATTERNS.iter().any(|pattern| {
+        Pattern::new(pattern)
+          .unwrap()
+          .matches_path_with(Path::new(path), MATCH_OPTIONS)
+      })
+    };
+    assert!(is_entry("apps/web/pages/index.tsx"));
+    assert!(is_entry("apps/web/app/page.tsx"));
+    assert!(is_entry("apps/web/app/settings/layout.tsx"));
+    assert!(is_entry("libs/ui/src/button.stories.tsx"));
+    assert!(is_entry("apps/web/next.config.js"));
+    assert!(!is_entry("libs/ui/src/button.tsx"));
+    assert!(!is_entry("apps/web/app/components/header.tsx"));
+  }
+
+  #[test]
+  fn test_export_offset() {
+    let source = "const a = 1;\nexport { a as b };\nexport function c() {}\nexport default c;\n";
+    let data = WorkspaceAnalyzer::parse_source(Path::new("x.ts"), source.to_string()).unwrap();
+    let program = data.semantic().nodes().program();
+    let line = |name| export_offset(program, name).map(|o| offset_to_line_col(source, o).0);
+    assert_eq!(line("b"), Some(2));
+    assert_eq!(line("c"), Some(3));
+    assert_eq!(line("default"), Some(4));
+    assert_eq!(line("a"), None);
+  }
+}
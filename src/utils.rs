@@ -1,8 +1,11 @@
+use crate::semantic::sfc::SFC_EXTENSIONS;
 use crate::tsconfig::TsconfigExcludes;
-use crate::types::Project;
+use crate::types::{Project, ProjectTarget};
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::de::DeserializeOwned;
+use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// Extensions considered as source files (analyzed by Oxc parser).
 ///
@@ -12,6 +15,14 @@ use tracing::debug;
 /// (which report as `mts`/`cts`) without needing separate entries.
 const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
 
+/// Options for matching workspace-relative paths against configured globs:
+/// `*` stays within a path segment and only `**` crosses directories
+pub(crate) const MATCH_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+  case_sensitive: true,
+  require_literal_separator: true,
+  require_literal_leading_dot: false,
+};
+
 /// Check if a file is a source file (TypeScript/JavaScript)
 /// These are files that can be parsed by the Oxc parser
 pub fn is_source_file(path: &Path) -> bool {
@@ -22,6 +33,17 @@ pub fn is_source_file(path: &Path) -> bool {
     .unwrap_or(false)
 }
 
+/// Check if a file is analyzed by the workspace analyzer: a source file, or a
+/// Vue, Svelte or Astro component whose script blocks are parsed (see
+/// [`crate::semantic::sfc`])
+pub fn is_analyzable_file(path: &Path) -> bool {
+  is_source_file(path)
+    || path
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .is_some_and(|ext| SFC_EXTENSIONS.contains(&ext))
+}
+
 /// Pre-built index from sourceRoot (and project root) to project names for
 /// O(unique_roots) lookups instead of O(total_projects) on every call.
 ///
@@ -223,10 +245,179 @@ pub fn offset_to_line_col(source: &str, offset: usize) -> (usize, usize) {
   (line, col)
 }
 
+/// The `project:target` pairs for each project in `names` that defines one of
+/// `targets`, in the order of `names` and then of `targets`
+pub fn select_targets(
+  names: &[String],
+  projects: &[Project],
+  targets: &[String],
+) -> Vec<ProjectTarget> {
+  let defined: FxHashMap<&str, &[String]> = projects
+    .iter()
+    .map(|project| (project.name.as_str(), project.targets.as_slice()))
+    .collect();
+
+  names
+    .iter()
+    .flat_map(|name| {
+      let own = defined.get(name.as_str()).copied().unwrap_or_default();
+      targets
+        .iter()
+        .filter(move |target| own.contains(target))
+        .map(move |target| ProjectTarget {
+          project: name.clone(),
+          target: target.clone(),
+        })
+    })
+    .collect()
+}
+
+/// Whether `tag` matches a tag pattern: `*` matches any tag, and a pattern
+/// containing `*` is a glob (`scope:*`)
+pub fn tag_matches(pattern: &str, tag: &str) -> bool {
+  if pattern == "*" || pattern == tag {
+    return true;
+  }
+  pattern.contains('*') && glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches(tag))
+}
+
+/// Keep the projects in `names` that carry a tag matching one of `tags`
+/// (when given) and none matching `exclude_tags`
+pub fn filter_by_tags(
+  names: Vec<String>,
+  projects: &[Project],
+  tags: &[String],
+  exclude_tags: &[String],
+) -> Vec<String> {
+  if tags.is_empty() && exclude_tags.is_empty() {
+    return names;
+  }
+  let project_tags: FxHashMap<&str, &[String]> = projects
+    .iter()
+    .map(|project| (project.name.as_str(), project.tags.as_slice()))
+    .collect();
+  let has_any = |project_tags: &[String], patterns: &[String]| {
+    patterns
+      .iter()
+      .any(|pattern| project_tags.iter().any(|tag| tag_matches(pattern, tag)))
+  };
+
+  names
+    .into_iter()
+    .filter(|name| {
+      let own = project_tags.get(name.as_str()).copied().unwrap_or_default();
+      (tags.is_empty() || has_any(own, tags)) && !has_any(own, exclude_tags)
+    })
+    .collect()
+}
+
+/// `domino.<key>` of the root package.json. `None` when the file or the key
+/// is missing; a package.json or setting that does not parse is logged and
+/// ignored.
+pub(crate) fn domino_setting<T: DeserializeOwned>(cwd: &Path, key: &str) -> Option<T> {
+  let content = fs::read_to_string(cwd.join("package.json")).ok()?;
+  let json: serde_json::Value = match serde_json::from_str(&content) {
+    Ok(json) => json,
+    Err(e) => {
+      warn!(
+        "Ignoring the domino settings of an unparsable package.json: {}",
+        e
+      );
+      return None;
+    }
+  };
+  let value = json.get("domino")?.get(key)?;
+  match T::deserialize(value) {
+    Ok(setting) => Some(setting),
+    Err(e) => {
+      warn!("Ignoring invalid domino.{} in package.json: {}", key, e);
+      None
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_tag_matches() {
+    assert!(tag_matches("*", "scope:billing"));
+    assert!(tag_matches("scope:billing", "scope:billing"));
+    assert!(tag_matches("scope:*", "scope:shared"));
+    assert!(!tag_matches("scope:*", "type:lib"));
+    assert!(!tag_matches("scope:billing", "scope:shared"));
+  }
+
+  #[test]
+  fn test_filter_by_tags() {
+    let tagged = |name: &str, tags: &[&str]| Project {
+      name: name.to_string(),
+      root: PathBuf::from(name),
+      source_root: PathBuf::from(name),
+      ts_config: None,
+      implicit_dependencies: vec![],
+      targets: vec![],
+      tags: tags.iter().map(|t| t.to_string()).collect(),
+    };
+    let projects = vec![
+      tagged("web", &["type:app", "scope:web"]),
+      tagged("web-e2e", &["type:e2e", "scope:web"]),
+      tagged("ui", &["type:lib", "scope:shared"]),
+      tagged("legacy", &[]),
+    ];
+    let names: Vec<String> = projects.iter().map(|p| p.name.clone()).collect();
+    let strings = |tags: &[&str]| -> Vec<String> { tags.iter().map(|t| t.to_string()).collect() };
+
+    assert_eq!(
+      filter_by_tags(names.clone(), &projects, &strings(&["type:app"]), &[]),
+      ["web"]
+    );
+    assert_eq!(
+      filter_by_tags(
+        names.clone(),
+        &projects,
+        &strings(&["scope:*"]),
+        &strings(&["type:e2e"])
+      ),
+      ["web", "ui"]
+    );
+    assert_eq!(
+      filter_by_tags(names.clone(), &projects, &[], &strings(&["type:e2e"])),
+      ["web", "ui", "legacy"]
+    );
+    assert_eq!(filter_by_tags(names.clone(), &projects, &[], &[]), names);
+  }
+
+  #[test]
+  fn test_select_targets() {
+    let with_targets = |name: &str, targets: &[&str]| Project {
+      name: name.to_string(),
+      root: PathBuf::from(name),
+      source_root: PathBuf::from(name),
+      ts_config: None,
+      implicit_dependencies: vec![],
+      targets: targets.iter().map(|t| t.to_string()).collect(),
+      tags: vec![],
+    };
+    let projects = vec![
+      with_targets("web", &["test", "build", "serve"]),
+      with_targets("ui", &["lint", "test"]),
+      with_targets("docs", &[]),
+    ];
+    let names = ["web".to_string(), "ui".to_string(), "docs".to_string()];
+    let pairs: Vec<String> = select_targets(
+      &names,
+      &projects,
+      &["build".to_string(), "test".to_string()],
+    )
+    .iter()
+    .map(|pair| pair.to_string())
+    .collect();
+
+    assert_eq!(pairs, ["web:build", "web:test", "ui:test"]);
+  }
+
   #[test]
   fn test_is_source_file() {
     // Source files
@@ -252,6 +443,21 @@ mod tests {
     assert!(!is_source_file(Path::new("no-extension")));
   }
 
+  #[test]
+  fn test_is_analyzable_file() {
+    assert!(is_analyzable_file(Path::new("index.ts")));
+    assert!(is_analyzable_file(Path::new("utils.cjs")));
+
+    // Single-file components, parsed through their script blocks
+    assert!(is_analyzable_file(Path::new("UserCard.vue")));
+    assert!(is_analyzable_file(Path::new("Card.svelte")));
+    assert!(is_analyzable_file(Path::new("pages/index.astro")));
+    assert!(!is_source_file(Path::new("UserCard.vue")));
+
+    assert!(!is_analyzable_file(Path::new("styles.css")));
+    assert!(!is_analyzable_file(Path::new("template.html")));
+  }
+
   #[test]
   fn test_line_to_offset() {
     let source = "line1\nline2\nline3\n";
@@ -281,6 +487,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "nx".to_string(),
@@ -289,6 +496,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -319,6 +527,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app-desktop-mv3".to_string(),
@@ -327,6 +536,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "other-project".to_string(),
@@ -335,6 +545,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -374,6 +585,7 @@ mod tests {
       ts_config: Some(lib_dir.join("tsconfig.lib.json")),
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let index = ProjectIndex::new(&projects, cwd);
@@ -417,6 +629,7 @@ mod tests {
       ts_config: Some(lib_dir.join("tsconfig.lib.json")),
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let index = ProjectIndex::new(&projects, cwd);
@@ -470,6 +683,7 @@ mod tests {
       ts_config: Some(lib_dir.join("tsconfig.lib.json")),
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let index = ProjectIndex::new(&projects, cwd);
@@ -501,6 +715,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "my-lib".to_string(),
@@ -509,6 +724,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       // Project where root == sourceRoot (no fallback needed)
       Project {
@@ -518,6 +734,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -578,6 +795,7 @@ mod tests {
       ts_config: Some(lib_dir.join("tsconfig.lib.json")),
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     }];
 
     let index = ProjectIndex::new(&projects, cwd);
@@ -627,6 +845,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       // Nested child project with separate src dir
       Project {
@@ -636,6 +855,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -683,6 +903,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "my-lib".to_string(),
@@ -691,6 +912,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -738,6 +960,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "my-lib".to_string(),
@@ -746,6 +969,7 @@ mod tests {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ];
 
@@ -758,4 +982,24 @@ mod tests {
       "empty-path root must not over-attribute"
     );
   }
+
+  #[test]
+  fn test_domino_setting() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let setting = |key: &str| domino_setting::<Vec<String>>(tmp.path(), key);
+    assert_eq!(setting("testPatterns"), None);
+
+    std::fs::write(
+      tmp.path().join("package.json"),
+      r#"{ "domino": { "testPatterns": ["**/e2e/**"], "entryPoints": "src/main.ts" } }"#,
+    )
+    .unwrap();
+    assert_eq!(setting("testPatterns"), Some(vec!["**/e2e/**".to_string()]));
+    assert_eq!(setting("routes"), None);
+    // A setting of the wrong shape is ignored
+    assert_eq!(setting("entryPoints"), None);
+
+    std::fs::write(tmp.path().join("package.json"), "{ not json").unwrap();
+    assert_eq!(setting("testPatterns"), None);
+  }
 }
@@ -7,7 +7,8 @@ use crate::error::{DominoError, Result};
 use crate::types::Project;
 use ignore::overrides::OverrideBuilder;
 use ignore::WalkBuilder;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
 const STATIC_EXCLUDES: &[&str] = &["!node_modules/", "!dist/", "!__fixtures__/"];
 
@@ -42,6 +43,26 @@ pub(crate) fn build_walker(
   Ok(builder.build())
 }
 
+/// Every file of the workspace that git does not ignore, relative to `cwd`
+pub(crate) fn workspace_files(cwd: &Path) -> Result<Vec<PathBuf>> {
+  let mut files = Vec::new();
+  for entry in build_walker(cwd, &[], &[])? {
+    let entry = match entry {
+      Ok(entry) => entry,
+      Err(e) => {
+        warn!("Walk error while listing workspace files: {}", e);
+        continue;
+      }
+    };
+    if entry.file_type().is_some_and(|ft| ft.is_file()) {
+      if let Ok(relative) = entry.path().strip_prefix(cwd) {
+        files.push(relative.to_path_buf());
+      }
+    }
+  }
+  Ok(files)
+}
+
 /// Detect workspace type and discover projects
 pub fn discover_projects(cwd: &Path) -> Result<Vec<Project>> {
   // Try Nx first
@@ -16,6 +16,8 @@ struct NxProjectJson {
   #[serde(default)]
   implicit_dependencies: Vec<String>,
   targets: Option<HashMap<String, NxTarget>>,
+  #[serde(default)]
+  tags: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -157,6 +159,7 @@ fn parse_project_json(path: &Path, cwd: &Path) -> Result<Project> {
     ts_config,
     implicit_dependencies: nx_project.implicit_dependencies,
     targets,
+    tags: nx_project.tags,
   })
 }
 
@@ -251,6 +254,7 @@ fn get_workspace_json_projects(cwd: &Path) -> Result<Vec<Project>> {
         ts_config,
         implicit_dependencies: nx_project.implicit_dependencies,
         targets,
+        tags: nx_project.tags,
       });
     }
   }
@@ -1,3 +1,4 @@
+use super::workspaces;
 use crate::error::{DominoError, Result};
 use crate::types::Project;
 use serde::Deserialize;
@@ -72,6 +73,8 @@ pub fn get_projects(cwd: &Path) -> Result<Vec<Project>> {
 
     let source_root = Path::new(&rush_project.project_folder).to_path_buf();
 
+    let tags = workspaces::package_json_tags(&project_dir).unwrap_or_default();
+
     projects.push(Project {
       name,
       root: source_root.clone(),
@@ -79,6 +82,7 @@ pub fn get_projects(cwd: &Path) -> Result<Vec<Project>> {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags,
     });
   }
 
@@ -2,6 +2,7 @@ use crate::error::{DominoError, Result};
 use crate::types::Project;
 use glob::{MatchOptions, Pattern};
 use serde::Deserialize;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 use tracing::{debug, warn};
@@ -21,6 +22,27 @@ struct PnpmWorkspace {
 struct PackageJson {
   name: String,
   workspaces: Option<Vec<String>>,
+  #[serde(default)]
+  private: bool,
+  #[serde(default)]
+  dependencies: serde_json::Map<String, serde_json::Value>,
+  #[serde(default, rename = "devDependencies")]
+  dev_dependencies: serde_json::Map<String, serde_json::Value>,
+  #[serde(default, rename = "peerDependencies")]
+  peer_dependencies: serde_json::Map<String, serde_json::Value>,
+  #[serde(default, rename = "optionalDependencies")]
+  optional_dependencies: serde_json::Map<String, serde_json::Value>,
+  #[serde(default)]
+  scripts: serde_json::Map<String, serde_json::Value>,
+  #[serde(default)]
+  domino: DominoPackageConfig,
+}
+
+/// The `domino` field of a workspace package's package.json
+#[derive(Debug, Default, Deserialize)]
+struct DominoPackageConfig {
+  #[serde(default)]
+  tags: Vec<String>,
 }
 
 /// Check if the current directory is a generic workspace (npm/yarn/pnpm/bun)
@@ -119,10 +141,45 @@ pub fn get_workspace_patterns(cwd: &Path) -> Result<Vec<String>> {
   Ok(vec![])
 }
 
-fn parse_package_json(path: &Path, cwd: &Path) -> Result<Project> {
+fn read_package_json(path: &Path) -> Result<PackageJson> {
   let content = fs::read_to_string(path)?;
-  let pkg_json: PackageJson = serde_json::from_str(&content)
-    .map_err(|e| DominoError::Parse(format!("Failed to parse package.json: {}", e)))?;
+  serde_json::from_str(&content)
+    .map_err(|e| DominoError::Parse(format!("Failed to parse package.json: {}", e)))
+}
+
+/// npm name of the package in `project_dir`, when it has a package.json that
+/// is not marked `private` — i.e. the project can be published
+pub fn publishable_package_name(project_dir: &Path) -> Option<String> {
+  let pkg_json = read_package_json(&project_dir.join("package.json")).ok()?;
+  (!pkg_json.private).then_some(pkg_json.name)
+}
+
+/// npm name of the package in `project_dir` and the names of everything its
+/// package.json declares as a dependency of any kind (regular, dev, peer,
+/// optional)
+pub fn declared_dependencies(project_dir: &Path) -> Option<(String, BTreeSet<String>)> {
+  let pkg_json = read_package_json(&project_dir.join("package.json")).ok()?;
+  let dependencies = [
+    pkg_json.dependencies,
+    pkg_json.dev_dependencies,
+    pkg_json.peer_dependencies,
+    pkg_json.optional_dependencies,
+  ]
+  .into_iter()
+  .flat_map(|map| map.into_iter().map(|(name, _)| name))
+  .collect();
+  Some((pkg_json.name, dependencies))
+}
+
+/// `domino.tags` of the package.json in `project_dir`, if it has one
+pub fn package_json_tags(project_dir: &Path) -> Option<Vec<String>> {
+  read_package_json(&project_dir.join("package.json"))
+    .ok()
+    .map(|pkg_json| pkg_json.domino.tags)
+}
+
+fn parse_package_json(path: &Path, cwd: &Path) -> Result<Project> {
+  let pkg_json = read_package_json(path)?;
 
   let project_dir = path
     .parent()
@@ -139,7 +196,8 @@ fn parse_package_json(path: &Path, cwd: &Path) -> Result<Project> {
     source_root,
     ts_config: None,
     implicit_dependencies: vec![],
-    targets: vec![],
+    targets: pkg_json.scripts.keys().cloned().collect(),
+    tags: pkg_json.domino.tags,
   })
 }
 
@@ -359,4 +417,50 @@ mod tests {
     assert!(!by_name["@myorg/web"].source_root.is_absolute());
     assert!(!by_name["@myorg/shared"].source_root.is_absolute());
   }
+
+  #[test]
+  fn test_publishable_package_name_skips_private() {
+    let dir = create_workspace_fixture(&["packages/*"]);
+    let root = dir.path();
+
+    write_package_json(root, "packages/lib", "@myorg/lib");
+    fs::create_dir_all(root.join("packages/app")).unwrap();
+    fs::write(
+      root.join("packages/app/package.json"),
+      r#"{ "name": "@myorg/app", "private": true }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      publishable_package_name(&root.join("packages/lib")),
+      Some("@myorg/lib".to_string())
+    );
+    assert_eq!(publishable_package_name(&root.join("packages/app")), None);
+    assert_eq!(publishable_package_name(&root.join("packages/none")), None);
+  }
+
+  #[test]
+  fn test_declared_dependencies_merges_all_kinds() {
+    let dir = create_workspace_fixture(&["packages/*"]);
+    let root = dir.path();
+
+    fs::create_dir_all(root.join("packages/app")).unwrap();
+    fs::write(
+      root.join("packages/app/package.json"),
+      r#"{
+        "name": "@myorg/app",
+        "dependencies": { "@myorg/ui": "workspace:*" },
+        "devDependencies": { "vitest": "^1.0.0" },
+        "peerDependencies": { "react": "^18.0.0" }
+      }"#,
+    )
+    .unwrap();
+
+    let (name, dependencies) = declared_dependencies(&root.join("packages/app")).unwrap();
+    assert_eq!(name, "@myorg/app");
+    assert_eq!(
+      dependencies.into_iter().collect::<Vec<_>>(),
+      ["@myorg/ui", "react", "vitest"]
+    );
+  }
 }
@@ -1,9 +1,24 @@
 mod common;
 
+use domino::affected_tests::find_affected_tests;
+use domino::api_diff::find_api_changes;
+use domino::boundaries::find_boundary_violations;
+use domino::changeset::find_package_bumps;
+use domino::chunks::find_affected_chunks;
 use domino::core::{find_affected, find_affected_with_report};
+use domino::cycles::{find_cycles, find_new_cycles};
+use domino::named_inputs::{filter_targets_by_inputs, NxInputs};
 use domino::profiler::Profiler;
+use domino::project_graph::build_project_graph;
 use domino::report::generate_html_report;
-use domino::types::{LockfileStrategy, Project, TrueAffectedConfig};
+use domino::routes::find_affected_routes;
+use domino::stories::{find_affected_stories, STORY_FILE_PATTERNS};
+use domino::types::{
+  AffectCause, AffectedScope, BoundaryMode, ChunkKind, DependencyDrift, LockfileStrategy, Project,
+  Propagation, TrueAffectedConfig,
+};
+use domino::unused_exports::{find_unused_exports, DEFAULT_ENTRY_PATTERNS};
+use domino::utils::{filter_by_tags, select_targets, ProjectIndex};
 use domino::workspace;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -211,6 +226,7 @@ impl TestBranch {
           ts_config: Some(PathBuf::from("proj1/tsconfig.json")),
           implicit_dependencies: vec![],
           targets: vec![],
+          tags: vec![],
         },
         Project {
           name: "proj2".to_string(),
@@ -219,6 +235,7 @@ impl TestBranch {
           ts_config: Some(PathBuf::from("proj2/tsconfig.json")),
           implicit_dependencies: vec![],
           targets: vec![],
+          tags: vec![],
         },
         Project {
           name: "proj3".to_string(),
@@ -227,9 +244,13 @@ impl TestBranch {
           ts_config: Some(PathBuf::from("proj3/tsconfig.json")),
           implicit_dependencies: vec!["proj1".to_string()],
           targets: vec![],
+          tags: vec![],
         },
       ],
       lockfile_strategy: LockfileStrategy::None,
+      member_precision: false,
+      propagation: Propagation::All,
+      boundary_mode: BoundaryMode::Full,
     };
 
     // Create a profiler (disabled for tests)
@@ -455,6 +476,7 @@ export function anotherFn() {
         ts_config: Some(PathBuf::from("proj1/tsconfig.json")),
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj2".to_string(),
@@ -463,6 +485,7 @@ export function anotherFn() {
         ts_config: Some(PathBuf::from("proj2/tsconfig.json")),
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj3".to_string(),
@@ -471,9 +494,13 @@ export function anotherFn() {
         ts_config: Some(PathBuf::from("proj3/tsconfig.json")),
         implicit_dependencies: vec!["proj1".to_string()],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2029,6 +2056,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app".to_string(),
@@ -2037,9 +2065,13 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2136,6 +2168,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app".to_string(),
@@ -2144,9 +2177,13 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2262,6 +2299,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "@test/app".to_string(),
@@ -2270,9 +2308,13 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2392,6 +2434,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "my-app".to_string(),
@@ -2400,9 +2443,13 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2512,6 +2559,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj-b".to_string(),
@@ -2520,6 +2568,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj-c".to_string(),
@@ -2528,9 +2577,13 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2654,6 +2707,7 @@ export function Panel() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj-b".to_string(),
@@ -2662,6 +2716,7 @@ export function Panel() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj-c".to_string(),
@@ -2670,9 +2725,13 @@ export function Panel() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2781,6 +2840,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj-b".to_string(),
@@ -2789,6 +2849,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj-c".to_string(),
@@ -2797,9 +2858,13 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -2903,6 +2968,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app".to_string(),
@@ -2911,6 +2977,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "other".to_string(),
@@ -2919,9 +2986,13 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3025,6 +3096,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app".to_string(),
@@ -3033,6 +3105,7 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "other".to_string(),
@@ -3041,9 +3114,13 @@ export function main() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3129,6 +3206,7 @@ fn test_shared_source_root_all_projects_affected() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app-desktop-mv3".to_string(),
@@ -3137,9 +3215,13 @@ fn test_shared_source_root_all_projects_affected() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3262,6 +3344,7 @@ fn lockfile_projects() -> Vec<Project> {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     },
     Project {
       name: "proj-b".to_string(),
@@ -3270,6 +3353,7 @@ fn lockfile_projects() -> Vec<Project> {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     },
     Project {
       name: "proj-c".to_string(),
@@ -3278,6 +3362,7 @@ fn lockfile_projects() -> Vec<Project> {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     },
   ]
 }
@@ -3318,6 +3403,9 @@ fn test_lockfile_direct_strategy_detects_importing_project() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Direct,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3371,6 +3459,9 @@ fn test_lockfile_full_strategy_traces_reference_chain() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Full,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3429,6 +3520,9 @@ fn test_lockfile_none_strategy_ignores_lockfile_changes() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3478,6 +3572,9 @@ fn test_lockfile_transitive_dep_change_resolves_to_direct() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Direct,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3516,6 +3613,9 @@ fn test_lockfile_no_change_zero_impact() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Direct,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3650,6 +3750,7 @@ export const mockData: SharedType = { name: 'test' };
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "ui-widgets".to_string(),
@@ -3658,9 +3759,13 @@ export const mockData: SharedType = { name: 'test' };
         ts_config: Some(widgets_dir.join("tsconfig.lib.json")),
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3773,6 +3878,7 @@ export const mockData: SharedType = { name: 'test' };
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "ui-widgets".to_string(),
@@ -3781,9 +3887,13 @@ export const mockData: SharedType = { name: 'test' };
         ts_config: Some(widgets_dir.join("tsconfig.lib.json")),
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -3943,6 +4053,9 @@ impl TempNxRepo {
       head: None,
       projects,
       lockfile_strategy: LockfileStrategy::None,
+      member_precision: false,
+      propagation: Propagation::All,
+      boundary_mode: BoundaryMode::Full,
     };
 
     let profiler = Arc::new(Profiler::new(false));
@@ -3959,6 +4072,9 @@ impl TempNxRepo {
       head: None,
       projects,
       lockfile_strategy: LockfileStrategy::None,
+      member_precision: false,
+      propagation: Propagation::All,
+      boundary_mode: BoundaryMode::Full,
     };
 
     let profiler = Arc::new(Profiler::new(false));
@@ -4315,6 +4431,9 @@ fn test_workspace_root_project_not_over_attributed() {
     head: None,
     projects,
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -4388,6 +4507,9 @@ fn test_spec_file_change_affects_owning_project() {
     head: None,
     projects,
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -4436,6 +4558,7 @@ export function unusedFn() {
         ts_config: Some(PathBuf::from("proj1/tsconfig.json")),
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj2".to_string(),
@@ -4444,6 +4567,7 @@ export function unusedFn() {
         ts_config: Some(PathBuf::from("proj2/tsconfig.json")),
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "proj3".to_string(),
@@ -4452,9 +4576,13 @@ export function unusedFn() {
         ts_config: Some(PathBuf::from("proj3/tsconfig.json")),
         implicit_dependencies: vec!["proj1".to_string()],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -4534,6 +4662,7 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
       Project {
         name: "app".to_string(),
@@ -4542,9 +4671,13 @@ export function run() {
         ts_config: None,
         implicit_dependencies: vec![],
         targets: vec![],
+        tags: vec![],
       },
     ],
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   (tmp, root, config)
@@ -4694,6 +4827,9 @@ fn test_dependency_manifest_in_shared_globals_does_not_globally_invalidate() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Direct,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -4745,6 +4881,9 @@ fn test_non_manifest_shared_global_still_globally_invalidates() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Direct,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -4804,6 +4943,9 @@ fn test_manifest_only_change_without_lockfile_still_globally_invalidates() {
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::Direct,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -4870,6 +5012,9 @@ fn test_lockfile_in_shared_globals_with_none_strategy_still_globally_invalidates
     head: None,
     projects: lockfile_projects(),
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -5007,6 +5152,9 @@ export function run() {
     head: None,
     projects,
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   };
 
   let profiler = Arc::new(Profiler::new(false));
@@ -5161,6 +5309,7 @@ fn turbo_projects() -> Vec<Project> {
       ts_config: None,
       implicit_dependencies: vec![],
       targets: vec![],
+      tags: vec![],
     })
     .collect()
 }
@@ -5172,6 +5321,9 @@ fn turbo_config_for(root: &Path, projects: Vec<Project>, base: &str) -> TrueAffe
     head: None,
     projects,
     lockfile_strategy: LockfileStrategy::None,
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
   }
 }
 
@@ -5601,6 +5753,15 @@ fn scaffold_repo(files: &[(&str, &str)]) -> (TempDir, PathBuf) {
   (tmp, root)
 }
 
+/// tsconfig.base.json mapping each `(alias, entry file)` path alias
+fn tsconfig_paths(paths: &[(&str, &str)]) -> String {
+  let paths: serde_json::Map<String, serde_json::Value> = paths
+    .iter()
+    .map(|(alias, entry)| (alias.to_string(), serde_json::json!([entry])))
+    .collect();
+  serde_json::json!({ "compilerOptions": { "baseUrl": ".", "paths": paths } }).to_string()
+}
+
 fn barrel_project(name: &str, source_root: &str) -> Project {
   Project {
     name: name.to_string(),
@@ -5609,23 +5770,52 @@ fn barrel_project(name: &str, source_root: &str) -> Project {
     ts_config: None,
     implicit_dependencies: vec![],
     targets: vec![],
+    tags: vec![],
   }
 }
 
-fn affected_in(root: &std::path::Path, projects: Vec<Project>) -> Vec<String> {
-  let config = TrueAffectedConfig {
+/// One [`barrel_project`] per `(name, source root)`
+fn barrel_projects(projects: &[(&str, &str)]) -> Vec<Project> {
+  projects
+    .iter()
+    .map(|(name, source_root)| barrel_project(name, source_root))
+    .collect()
+}
+
+/// Default config comparing the repo at `root` against `main`. Tests change
+/// single options with struct update syntax.
+fn test_config(root: &std::path::Path, projects: Vec<Project>) -> TrueAffectedConfig {
+  TrueAffectedConfig {
     cwd: root.to_path_buf(),
     base: "main".to_string(),
     head: None,
     projects,
     lockfile_strategy: LockfileStrategy::None,
-  };
+    member_precision: false,
+    propagation: Propagation::All,
+    boundary_mode: BoundaryMode::Full,
+  }
+}
+
+/// [`test_config`] for the projects discovered in the repo at `root`
+fn discovered_config(root: &std::path::Path) -> TrueAffectedConfig {
+  test_config(
+    root,
+    workspace::discover_projects(root).expect("discover_projects failed"),
+  )
+}
+
+fn affected_with(config: TrueAffectedConfig) -> Vec<String> {
   let profiler = Arc::new(Profiler::new(false));
   find_affected(config, profiler)
     .expect("find_affected failed")
     .affected_projects
 }
 
+fn affected_in(root: &std::path::Path, projects: Vec<Project>) -> Vec<String> {
+  affected_with(test_config(root, projects))
+}
+
 /// (a) Single-hop barrel: symbol changed in utils.ts, re-exported through
 /// `libs/my-lib/src/index.ts`, consumed from the barrel by another project.
 #[test]
@@ -5855,3 +6045,2089 @@ fn test_barrel_consumer_of_other_symbol_not_affected() {
     affected
   );
 }
+
+// ---------------------------------------------------------------------------
+// `new URL('…', import.meta.url)` module references (Web Workers)
+//
+// A worker module is bundled as its own entry point and never imported, so the
+// only edge to it is the `new URL` expression handed to `new Worker(...)`.
+// ---------------------------------------------------------------------------
+
+fn scaffold_worker_repo() -> (TempDir, PathBuf, Vec<Project>) {
+  let (tmp, root) = scaffold_repo(&[
+    (
+      "libs/worker-lib/src/crunch.ts",
+      "export function crunch(n: number) {\n  return n * 2;\n}\n",
+    ),
+    (
+      "libs/worker-lib/src/worker.ts",
+      "import { crunch } from './crunch';\n\nself.onmessage = (event) => {\n  self.postMessage(crunch(event.data));\n};\n",
+    ),
+    (
+      "libs/worker-lib/src/index.ts",
+      "export function startWorker() {\n  return new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });\n}\n\nexport function unrelated() {\n  return 1;\n}\n",
+    ),
+    (
+      "apps/app/src/main.ts",
+      "import { startWorker } from '@scope/worker-lib';\n\nexport const worker = startWorker();\n",
+    ),
+    (
+      "apps/other/src/main.ts",
+      "import { unrelated } from '@scope/worker-lib';\n\nexport const value = unrelated();\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/worker-lib", "libs/worker-lib/src/index.ts")]),
+    ),
+  ]);
+  let projects = barrel_projects(&[
+    ("worker-lib", "libs/worker-lib/src"),
+    ("app", "apps/app/src"),
+    ("other", "apps/other/src"),
+  ]);
+  (tmp, root, projects)
+}
+
+#[test]
+fn test_worker_module_change_affects_spawning_file() {
+  let (_tmp, root, projects) = scaffold_worker_repo();
+
+  fs::write(
+    root.join("libs/worker-lib/src/worker.ts"),
+    "import { crunch } from './crunch';\n\nself.onmessage = (event) => {\n  self.postMessage(crunch(event.data) + 1);\n};\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change worker"]);
+
+  let affected = affected_in(&root, projects);
+
+  assert!(
+    affected.contains(&"app".to_string()),
+    "app calls startWorker(), which spawns the changed worker module. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"other".to_string()),
+    "other only uses an unrelated export. Got: {:?}",
+    affected
+  );
+}
+
+#[test]
+fn test_worker_dependency_change_affects_spawning_file() {
+  let (_tmp, root, projects) = scaffold_worker_repo();
+
+  fs::write(
+    root.join("libs/worker-lib/src/crunch.ts"),
+    "export function crunch(n: number) {\n  return n * 3;\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change crunch"]);
+
+  let affected = affected_in(&root, projects);
+
+  assert!(
+    affected.contains(&"app".to_string()),
+    "crunch() is imported by the worker, which startWorker() spawns. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"other".to_string()),
+    "other only uses an unrelated export. Got: {:?}",
+    affected
+  );
+}
+
+// ---------------------------------------------------------------------------
+// Namespace re-exports (`export * as ns from './utils'`)
+//
+// Consumers import the namespace binding from the barrel and reach the changed
+// symbol only through member access (`utils.formatDate`).
+// ---------------------------------------------------------------------------
+
+fn scaffold_namespace_reexport_repo() -> (TempDir, PathBuf, Vec<Project>) {
+  let (tmp, root) = scaffold_repo(&[
+    (
+      "libs/my-lib/src/utils.ts",
+      "export function formatDate(d: Date) {\n  return d.toISOString();\n}\n\nexport function slugify(s: string) {\n  return s.toLowerCase();\n}\n",
+    ),
+    (
+      "libs/my-lib/src/index.ts",
+      "export * as utils from './utils';\n",
+    ),
+    // Second-hop barrel forwards the namespace binding by name
+    (
+      "libs/public/src/index.ts",
+      "export { utils } from '@scope/my-lib';\n",
+    ),
+    (
+      "apps/dates/src/main.ts",
+      "import { utils } from '@scope/my-lib';\n\nexport const today = utils.formatDate(new Date());\n",
+    ),
+    (
+      "apps/slugs/src/main.ts",
+      "import { utils } from '@scope/my-lib';\n\nexport const slug = utils.slugify('Hello');\n",
+    ),
+    (
+      "apps/forwarded/src/main.ts",
+      "import { utils as u } from '@scope/public';\n\nexport const today = u.formatDate(new Date());\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[
+        ("@scope/my-lib", "libs/my-lib/src/index.ts"),
+        ("@scope/public", "libs/public/src/index.ts"),
+      ]),
+    ),
+  ]);
+  let projects = barrel_projects(&[
+    ("my-lib", "libs/my-lib/src"),
+    ("public", "libs/public/src"),
+    ("dates", "apps/dates/src"),
+    ("slugs", "apps/slugs/src"),
+    ("forwarded", "apps/forwarded/src"),
+  ]);
+  (tmp, root, projects)
+}
+
+#[test]
+fn test_namespace_reexport_member_access_affects_consumer() {
+  let (_tmp, root, projects) = scaffold_namespace_reexport_repo();
+
+  fs::write(
+    root.join("libs/my-lib/src/utils.ts"),
+    "export function formatDate(d: Date) {\n  return d.toUTCString();\n}\n\nexport function slugify(s: string) {\n  return s.toLowerCase();\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change formatDate"]);
+
+  let affected = affected_in(&root, projects);
+
+  assert!(
+    affected.contains(&"dates".to_string()),
+    "dates calls utils.formatDate. Got: {:?}",
+    affected
+  );
+  assert!(
+    affected.contains(&"forwarded".to_string()),
+    "forwarded calls formatDate through a second barrel. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"slugs".to_string()),
+    "slugs only uses utils.slugify. Got: {:?}",
+    affected
+  );
+}
+
+#[test]
+fn test_namespace_reexport_line_change_affects_all_namespace_consumers() {
+  let (_tmp, root, projects) = scaffold_namespace_reexport_repo();
+
+  fs::write(
+    root.join("libs/my-lib/src/dates.ts"),
+    "export * from './utils';\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("libs/my-lib/src/index.ts"),
+    "export * as utils from './dates';\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "repoint namespace"]);
+
+  let affected = affected_in(&root, projects);
+
+  for project in ["dates", "slugs", "forwarded"] {
+    assert!(
+      affected.contains(&project.to_string()),
+      "{} uses the re-pointed namespace binding. Got: {:?}",
+      project,
+      affected
+    );
+  }
+}
+
+// ---------------------------------------------------------------------------
+// Dynamic import usage: `.then()` callbacks, destructured `await import()` and
+// lazy loaders resolve to the exports they actually read.
+// ---------------------------------------------------------------------------
+
+fn scaffold_lazy_routes_repo() -> (TempDir, PathBuf, Vec<Project>) {
+  let (tmp, root) = scaffold_repo(&[
+    (
+      "libs/pages/src/index.ts",
+      "export function SettingsPage() {\n  return 'settings';\n}\n\nexport function ReportsPage() {\n  return 'reports';\n}\n\nexport default function HomePage() {\n  return 'home';\n}\n",
+    ),
+    (
+      "apps/settings/src/routes.ts",
+      "export const routes = [\n  { path: 'settings', loadComponent: () => import('@scope/pages').then((m) => m.SettingsPage) },\n];\n",
+    ),
+    (
+      "apps/reports/src/routes.ts",
+      "export async function loadReports() {\n  const { ReportsPage } = await import('@scope/pages');\n  return ReportsPage;\n}\n",
+    ),
+    (
+      "apps/home/src/routes.tsx",
+      "import { lazy } from 'react';\n\nexport const Home = lazy(() => import('@scope/pages'));\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/pages", "libs/pages/src/index.ts")]),
+    ),
+  ]);
+  let projects = barrel_projects(&[
+    ("pages", "libs/pages/src"),
+    ("settings", "apps/settings/src"),
+    ("reports", "apps/reports/src"),
+    ("home", "apps/home/src"),
+  ]);
+  (tmp, root, projects)
+}
+
+#[test]
+fn test_dynamic_import_then_callback_affects_route() {
+  let (_tmp, root, projects) = scaffold_lazy_routes_repo();
+
+  fs::write(
+    root.join("libs/pages/src/index.ts"),
+    "export function SettingsPage() {\n  return 'settings v2';\n}\n\nexport function ReportsPage() {\n  return 'reports';\n}\n\nexport default function HomePage() {\n  return 'home';\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change settings page"]);
+
+  let affected = affected_in(&root, projects);
+
+  assert!(
+    affected.contains(&"settings".to_string()),
+    "settings reads m.SettingsPage in a .then() callback. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"reports".to_string()),
+    "reports only destructures ReportsPage. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"home".to_string()),
+    "home only renders the default export. Got: {:?}",
+    affected
+  );
+}
+
+#[test]
+fn test_dynamic_import_destructuring_and_lazy_default_affect_routes() {
+  let (_tmp, root, projects) = scaffold_lazy_routes_repo();
+
+  fs::write(
+    root.join("libs/pages/src/index.ts"),
+    "export function SettingsPage() {\n  return 'settings';\n}\n\nexport function ReportsPage() {\n  return 'reports v2';\n}\n\nexport default function HomePage() {\n  return 'home v2';\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change reports and home pages"]);
+
+  let affected = affected_in(&root, projects);
+
+  assert!(
+    affected.contains(&"reports".to_string()),
+    "reports destructures ReportsPage from await import(). Got: {:?}",
+    affected
+  );
+  assert!(
+    affected.contains(&"home".to_string()),
+    "home lazily renders the default export. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"settings".to_string()),
+    "settings only reads SettingsPage. Got: {:?}",
+    affected
+  );
+}
+
+// ---------------------------------------------------------------------------
+// Scope-accurate local references: a nested binding that shadows a changed
+// top-level symbol must not make its enclosing export look affected.
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_shadowed_local_binding_does_not_propagate() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/store/src/index.ts",
+      "export const data = [1, 2];\n\nexport function load(fetchAll: () => number[]) {\n  const data = fetchAll();\n  return data;\n}\n",
+    ),
+    (
+      "apps/loader/src/main.ts",
+      "import { load } from '@scope/store';\n\nexport const items = load(() => []);\n",
+    ),
+    (
+      "apps/reader/src/main.ts",
+      "import { data } from '@scope/store';\n\nexport const first = data[0];\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/store", "libs/store/src/index.ts")]),
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/store/src/index.ts"),
+    "export const data = [1, 2, 3];\n\nexport function load(fetchAll: () => number[]) {\n  const data = fetchAll();\n  return data;\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change data"]);
+
+  let affected = affected_in(
+    &root,
+    barrel_projects(&[
+      ("store", "libs/store/src"),
+      ("loader", "apps/loader/src"),
+      ("reader", "apps/reader/src"),
+    ]),
+  );
+
+  assert!(
+    affected.contains(&"reader".to_string()),
+    "reader imports the changed data. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"loader".to_string()),
+    "load() only uses its own local `data`. Got: {:?}",
+    affected
+  );
+}
+
+fn scaffold_api_client_repo() -> (TempDir, PathBuf, Vec<Project>) {
+  let (tmp, root) = scaffold_repo(&[
+    (
+      "libs/api/src/index.ts",
+      "export class ApiClient {\n  constructor() {\n    this.base = '/api';\n  }\n\n  getUser(id: string) {\n    return fetch(`${this.base}/users/${id}`);\n  }\n\n  listOrders() {\n    return fetch(`${this.base}/orders`);\n  }\n}\n",
+    ),
+    (
+      "apps/orders/src/main.ts",
+      "import { ApiClient } from '@scope/api';\n\nexport const orders = () => new ApiClient().listOrders();\n",
+    ),
+    (
+      "apps/profile/src/main.ts",
+      "import { ApiClient } from '@scope/api';\n\nexport function loadUser(id: string) {\n  const client = new ApiClient();\n  return client.getUser(id);\n}\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/api", "libs/api/src/index.ts")]),
+    ),
+  ]);
+  let projects = barrel_projects(&[
+    ("api", "libs/api/src"),
+    ("orders", "apps/orders/src"),
+    ("profile", "apps/profile/src"),
+  ]);
+  (tmp, root, projects)
+}
+
+/// With member precision, editing one method only affects consumers that call
+/// it; without it, every consumer of the class is affected.
+#[test]
+fn test_member_precision_method_change_affects_only_callers() {
+  let (_tmp, root, projects) = scaffold_api_client_repo();
+
+  fs::write(
+    root.join("libs/api/src/index.ts"),
+    "export class ApiClient {\n  constructor() {\n    this.base = '/api';\n  }\n\n  getUser(id: string) {\n    return fetch(`${this.base}/v2/users/${id}`);\n  }\n\n  listOrders() {\n    return fetch(`${this.base}/orders`);\n  }\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change getUser"]);
+
+  let affected = affected_with(TrueAffectedConfig {
+    member_precision: true,
+    ..test_config(&root, projects.clone())
+  });
+  assert!(
+    affected.contains(&"profile".to_string()),
+    "profile calls getUser. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"orders".to_string()),
+    "orders only calls listOrders. Got: {:?}",
+    affected
+  );
+
+  let affected = affected_in(&root, projects);
+  assert!(
+    affected.contains(&"orders".to_string()) && affected.contains(&"profile".to_string()),
+    "Without member precision the whole class propagates. Got: {:?}",
+    affected
+  );
+}
+
+/// A constructor change reaches every instance, so all consumers are affected
+/// even with member precision.
+#[test]
+fn test_member_precision_constructor_change_affects_all_consumers() {
+  let (_tmp, root, projects) = scaffold_api_client_repo();
+
+  fs::write(
+    root.join("libs/api/src/index.ts"),
+    "export class ApiClient {\n  constructor() {\n    this.base = '/api/v2';\n  }\n\n  getUser(id: string) {\n    return fetch(`${this.base}/users/${id}`);\n  }\n\n  listOrders() {\n    return fetch(`${this.base}/orders`);\n  }\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change constructor"]);
+
+  let affected = affected_with(TrueAffectedConfig {
+    member_precision: true,
+    ..test_config(&root, projects)
+  });
+  assert!(
+    affected.contains(&"orders".to_string()) && affected.contains(&"profile".to_string()),
+    "A constructor change affects every consumer. Got: {:?}",
+    affected
+  );
+}
+
+/// With member precision, adding a key to a shared constant map only affects
+/// consumers that read that key or use the map as a whole.
+#[test]
+fn test_member_precision_object_property_change() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/config/src/index.ts",
+      "export const ROUTES = {\n  billing: '/billing',\n  settings: '/settings',\n} as const;\n",
+    ),
+    (
+      "apps/billing/src/main.ts",
+      "import { ROUTES } from '@scope/config';\n\nexport const link = ROUTES.billing;\n",
+    ),
+    (
+      "apps/settings/src/main.ts",
+      "import { ROUTES } from '@scope/config';\n\nconst { settings } = ROUTES;\n\nexport const link = settings;\n",
+    ),
+    (
+      "apps/sitemap/src/main.ts",
+      "import { ROUTES } from '@scope/config';\n\nexport const paths = Object.values(ROUTES);\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/config", "libs/config/src/index.ts")]),
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/config/src/index.ts"),
+    "export const ROUTES = {\n  billing: '/account/billing',\n  settings: '/settings',\n} as const;\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "move billing"]);
+
+  let affected = affected_with(TrueAffectedConfig {
+    member_precision: true,
+    ..test_config(
+      &root,
+      barrel_projects(&[
+        ("config", "libs/config/src"),
+        ("billing", "apps/billing/src"),
+        ("settings", "apps/settings/src"),
+        ("sitemap", "apps/sitemap/src"),
+      ]),
+    )
+  });
+
+  assert!(
+    affected.contains(&"billing".to_string()),
+    "billing reads ROUTES.billing. Got: {:?}",
+    affected
+  );
+  assert!(
+    affected.contains(&"sitemap".to_string()),
+    "sitemap uses the whole object. Got: {:?}",
+    affected
+  );
+  assert!(
+    !affected.contains(&"settings".to_string()),
+    "settings only destructures ROUTES.settings. Got: {:?}",
+    affected
+  );
+}
+
+/// Member precision only narrows class and object members: a plain local
+/// helper still reaches importers through an aliased or default export, and
+/// one used by a single method still only reaches that method's callers.
+#[test]
+fn test_member_precision_keeps_aliased_exports_of_local_helpers() {
+  let api = |path: &str| {
+    format!(
+      "function userPath(id: string) {{\n  return `{}/${{id}}`;\n}}\n\nexport class ApiClient {{\n  getUser(id: string) {{\n    return fetch(userPath(id));\n  }}\n\n  listOrders() {{\n    return fetch('/orders');\n  }}\n}}\n",
+      path
+    )
+  };
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/format/src/index.ts",
+      "function helper(value: string) {\n  return value.trim();\n}\n\nexport { helper as format };\n",
+    ),
+    (
+      "libs/money/src/index.ts",
+      "const toCents = (value: number) => Math.round(value * 100);\n\nexport default toCents;\n",
+    ),
+    ("libs/api/src/index.ts", &api("/users")),
+    (
+      "apps/web/src/main.ts",
+      "import { format } from '@scope/format';\n\nexport const title = format(' Home ');\n",
+    ),
+    (
+      "apps/shop/src/main.ts",
+      "import toCents from '@scope/money';\n\nexport const price = toCents(9.99);\n",
+    ),
+    (
+      "apps/profile/src/main.ts",
+      "import { ApiClient } from '@scope/api';\n\nexport const user = new ApiClient().getUser('1');\n",
+    ),
+    (
+      "apps/orders/src/main.ts",
+      "import { ApiClient } from '@scope/api';\n\nexport const orders = new ApiClient().listOrders();\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[
+        ("@scope/format", "libs/format/src/index.ts"),
+        ("@scope/money", "libs/money/src/index.ts"),
+        ("@scope/api", "libs/api/src/index.ts"),
+      ]),
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/format/src/index.ts"),
+    "function helper(value: string) {\n  return value.trim().toLowerCase();\n}\n\nexport { helper as format };\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("libs/money/src/index.ts"),
+    "const toCents = (value: number) => Math.floor(value * 100);\n\nexport default toCents;\n",
+  )
+  .unwrap();
+  fs::write(root.join("libs/api/src/index.ts"), api("/v2/users")).unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change helpers"]);
+
+  let affected = affected_with(TrueAffectedConfig {
+    member_precision: true,
+    ..test_config(
+      &root,
+      barrel_projects(&[
+        ("format", "libs/format/src"),
+        ("money", "libs/money/src"),
+        ("api", "libs/api/src"),
+        ("web", "apps/web/src"),
+        ("shop", "apps/shop/src"),
+        ("profile", "apps/profile/src"),
+        ("orders", "apps/orders/src"),
+      ]),
+    )
+  });
+  for project in ["web", "shop", "profile"] {
+    assert!(
+      affected.contains(&project.to_string()),
+      "{} reaches a changed helper. Got: {:?}",
+      project,
+      affected
+    );
+  }
+  assert!(
+    !affected.contains(&"orders".to_string()),
+    "orders only calls listOrders, which does not use userPath. Got: {:?}",
+    affected
+  );
+}
+
+/// `--propagation runtime` drops edges that only exist in the type graph:
+/// `import type`, and value imports used only in type positions.
+#[test]
+fn test_runtime_propagation_skips_type_only_edges() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/models/src/index.ts",
+      "export interface User {\n  id: string;\n}\n\nexport class Store {\n  load() {\n    return 1;\n  }\n}\n",
+    ),
+    (
+      "apps/types-only/src/main.ts",
+      "import type { User } from '@scope/models';\n\nexport const describe = (user: User) => user.id;\n",
+    ),
+    (
+      "apps/annotated/src/main.ts",
+      "import { Store } from '@scope/models';\n\nexport function use(store: Store) {\n  return store;\n}\n",
+    ),
+    (
+      "apps/runtime/src/main.ts",
+      "import { Store } from '@scope/models';\n\nexport const store = new Store();\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/models", "libs/models/src/index.ts")]),
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/models/src/index.ts"),
+    "export interface User {\n  id: number;\n}\n\nexport class Store {\n  load() {\n    return 2;\n  }\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change models"]);
+
+  let config = |propagation| TrueAffectedConfig {
+    propagation,
+    ..test_config(
+      &root,
+      barrel_projects(&[
+        ("models", "libs/models/src"),
+        ("types-only", "apps/types-only/src"),
+        ("annotated", "apps/annotated/src"),
+        ("runtime", "apps/runtime/src"),
+      ]),
+    )
+  };
+  let runtime = affected_with(config(Propagation::Runtime));
+  assert!(
+    runtime.contains(&"runtime".to_string()),
+    "runtime instantiates Store. Got: {:?}",
+    runtime
+  );
+  assert!(
+    !runtime.contains(&"types-only".to_string()) && !runtime.contains(&"annotated".to_string()),
+    "Type-only consumers must not be affected at runtime. Got: {:?}",
+    runtime
+  );
+
+  let all = find_affected_with_report(config(Propagation::All), Arc::new(Profiler::new(false)))
+    .expect("find_affected_with_report failed");
+  for project in ["types-only", "annotated", "runtime"] {
+    assert!(
+      all.affected_projects.contains(&project.to_string()),
+      "{} should be affected when type edges propagate. Got: {:?}",
+      project,
+      all.affected_projects
+    );
+  }
+
+  let report = all.report.expect("expected a report");
+  let imported_type_only = |name: &str| -> Vec<bool> {
+    report
+      .projects
+      .iter()
+      .filter(|project| project.name == name)
+      .flat_map(|project| &project.causes)
+      .filter_map(|cause| match cause {
+        AffectCause::ImportedSymbol { type_only, .. } => Some(*type_only),
+        _ => None,
+      })
+      .collect()
+  };
+  assert!(imported_type_only("types-only").iter().all(|t| *t));
+  assert!(imported_type_only("annotated").iter().all(|t| *t));
+  assert!(imported_type_only("runtime").iter().any(|t| !*t));
+  assert!(!imported_type_only("annotated").is_empty());
+}
+
+/// With `--boundary-mode signature`, an implementation-only change stays in
+/// its project, while a signature change crosses — including one that only
+/// reaches a declaration through a type it names.
+#[test]
+fn test_signature_boundary_mode_stops_implementation_changes() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/core/src/index.ts",
+      "export * from './format';\nexport * from './configure';\n",
+    ),
+    (
+      "libs/core/src/format.ts",
+      "export function format(value: number): string {\n  return String(value);\n}\n",
+    ),
+    (
+      "libs/core/src/configure.ts",
+      "export interface Options {\n  verbose: boolean;\n}\n\nexport function configure(options: Options): void {\n  console.log(options);\n}\n",
+    ),
+    (
+      "apps/formatter/src/main.ts",
+      "import { format } from '@scope/core';\n\nexport const label = format(1);\n",
+    ),
+    (
+      "apps/configurer/src/main.ts",
+      "import { configure } from '@scope/core';\n\nexport const setup = () => configure({ verbose: true });\n",
+    ),
+    (
+      "tsconfig.base.json",
+      &tsconfig_paths(&[("@scope/core", "libs/core/src/index.ts")]),
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/core/src/format.ts"),
+    "export function format(value: number): string {\n  return value.toFixed(2);\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "change format body"]);
+
+  let affected = |boundary_mode| {
+    affected_with(TrueAffectedConfig {
+      boundary_mode,
+      ..test_config(
+        &root,
+        barrel_projects(&[
+          ("core", "libs/core/src"),
+          ("formatter", "apps/formatter/src"),
+          ("configurer", "apps/configurer/src"),
+        ]),
+      )
+    })
+  };
+
+  let full = affected(BoundaryMode::Full);
+  assert!(
+    full.contains(&"formatter".to_string()),
+    "full mode follows every import. Got: {:?}",
+    full
+  );
+  let signature = affected(BoundaryMode::Signature);
+  assert_eq!(signature, vec!["core".to_string()]);
+
+  // Widening `Options` changes `configure(options: Options)` too
+  fs::write(
+    root.join("libs/core/src/configure.ts"),
+    "export interface Options {\n  verbose: boolean;\n  level: number;\n}\n\nexport function configure(options: Options): void {\n  console.log(options);\n}\n",
+  )
+  .unwrap();
+  git_in(&root, &["add", "."]);
+  git_in(&root, &["commit", "-m", "widen options"]);
+
+  let signature = affected(BoundaryMode::Signature);
+  assert!(
+    signature.contains(&"configurer".to_string()),
+    "configure's parameter type changed. Got: {:?}",
+    signature
+  );
+  assert!(
+    !signature.contains(&"formatter".to_string()),
+    "format only changed its body. Got: {:?}",
+    signature
+  );
+}
+
+/// `api-diff` reads a project's public API through its package.json entry
+/// point and barrel re-exports, and reports only signature-level changes.
+#[test]
+fn test_api_diff_reports_signature_changes() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/ui/package.json",
+      r#"{ "name": "@org/ui", "exports": { ".": { "types": "./src/index.ts", "default": "./dist/index.js" } } }"#,
+    ),
+    (
+      "libs/ui/src/index.ts",
+      "export * from './format';\nexport { Button as PrimaryButton } from './button';\nexport const legacy = 1;\n",
+    ),
+    (
+      "libs/ui/src/format.ts",
+      "export function format(value: number): string {\n  return String(value);\n}\n\nexport function trim(value: string): string {\n  return value.trim();\n}\n",
+    ),
+    (
+      "libs/ui/src/button.ts",
+      "export class Button {\n  label: string = '';\n  render(): string {\n    return this.label;\n  }\n}\n",
+    ),
+    ("apps/web/src/main.ts", "console.log('web');\n"),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/format.ts"),
+    "export function format(\n  value: number,\n  locale?: string,\n): string {\n  return String(value);\n}\n\n// implementation-only change\nexport function trim(value: string): string {\n  return value.trimEnd();\n}\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("libs/ui/src/index.ts"),
+    "export * from './format';\nexport { Button as PrimaryButton } from './button';\nexport type Size = 'sm' | 'lg';\n",
+  )
+  .unwrap();
+
+  let projects = vec![
+    Project {
+      name: "ui".to_string(),
+      root: PathBuf::from("libs/ui"),
+      source_root: PathBuf::from("libs/ui/src"),
+      ts_config: None,
+      implicit_dependencies: vec![],
+      targets: vec![],
+      tags: vec![],
+    },
+    barrel_project("web", "apps/web/src"),
+  ];
+
+  let diffs = find_api_changes(
+    &root,
+    "main",
+    None,
+    projects,
+    Arc::new(Profiler::new(false)),
+  )
+  .expect("find_api_changes failed");
+
+  assert_eq!(diffs.len(), 1, "only ui has an API change: {:?}", diffs);
+  let ui = &diffs[0];
+  assert_eq!(ui.project, "ui");
+  assert_eq!(ui.entry_point, PathBuf::from("libs/ui/src/index.ts"));
+
+  let names = |entries: &[domino::types::ApiEntry]| -> Vec<String> {
+    entries.iter().map(|e| e.name.clone()).collect()
+  };
+  assert_eq!(names(&ui.added), ["Size"]);
+  assert_eq!(names(&ui.removed), ["legacy"]);
+  assert_eq!(ui.changed.len(), 1, "changed: {:?}", ui.changed);
+  assert_eq!(ui.changed[0].name, "format");
+  assert_eq!(ui.changed[0].base, "function format(value: number): string");
+  assert_eq!(
+    ui.changed[0].head,
+    "function format(value: number, locale?: string): string"
+  );
+}
+
+/// `changeset` suggests bumps only for affected packages that can be
+/// published, graded by how their public API changed.
+#[test]
+fn test_changeset_bumps_publishable_packages() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "package.json",
+      r#"{ "name": "root", "private": true, "workspaces": ["packages/*"] }"#,
+    ),
+    (
+      "packages/core/package.json",
+      r#"{ "name": "@org/core", "main": "./src/index.ts" }"#,
+    ),
+    (
+      "packages/core/src/index.ts",
+      "export function parse(input: string): number {\n  return Number(input);\n}\n",
+    ),
+    (
+      "packages/ui/package.json",
+      r#"{ "name": "@org/ui", "main": "./src/index.ts" }"#,
+    ),
+    (
+      "packages/ui/src/index.ts",
+      "import { parse } from '@org/core';\n\nexport const width = (value: string) => parse(value);\nexport const legacy = 1;\n",
+    ),
+    (
+      "packages/app/package.json",
+      r#"{ "name": "@org/app", "private": true, "main": "./src/index.ts" }"#,
+    ),
+    (
+      "packages/app/src/index.ts",
+      "import { width } from '@org/ui';\n\nexport const main = () => width('1');\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("packages/core/src/index.ts"),
+    "export function parse(input: string): number {\n  return Number.parseFloat(input);\n}\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("packages/ui/src/index.ts"),
+    "import { parse } from '@org/core';\n\nexport const width = (value: string) => parse(value);\n",
+  )
+  .unwrap();
+
+  let bumps = find_package_bumps(discovered_config(&root), Arc::new(Profiler::new(false)))
+    .expect("find_package_bumps failed");
+
+  let summary: Vec<(String, String)> = bumps
+    .iter()
+    .map(|b| (b.package.clone(), b.bump.to_string()))
+    .collect();
+  assert_eq!(
+    summary,
+    [
+      ("@org/core".to_string(), "patch".to_string()),
+      ("@org/ui".to_string(), "major".to_string()),
+    ]
+  );
+  assert_eq!(bumps[0].reasons, ["internal changes"]);
+  assert_eq!(bumps[1].reasons[0], "removed export `legacy`");
+}
+
+/// Integration test: `domino cycles` reports file and project cycles, and
+/// `--fail-on-new` only keeps the ones missing at the base revision.
+#[test]
+fn test_cycles_reports_only_new_cycles_against_base() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/core/src/a.ts",
+      "import { b } from './b';\n\nexport const a = () => b();\n",
+    ),
+    (
+      "libs/core/src/b.ts",
+      "import { a } from './a';\n\nexport const b = () => 1;\nexport const callA = () => a();\n",
+    ),
+    ("libs/core/src/d.ts", "export const d = 4;\n"),
+    (
+      "libs/ui/src/index.ts",
+      "import { d } from '../../core/src/d';\n\nexport const ui = () => d;\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/core/src/c.ts"),
+    "import { ui } from '../../ui/src/index';\n\nexport const c = () => ui();\n",
+  )
+  .unwrap();
+
+  let projects = vec![
+    barrel_project("core", "libs/core/src"),
+    barrel_project("ui", "libs/ui/src"),
+  ];
+  let profiler = Arc::new(Profiler::new(false));
+
+  let all = find_cycles(&root, projects.clone(), profiler.clone()).expect("find_cycles failed");
+  assert_eq!(all.files.len(), 1);
+  assert_eq!(
+    all.files[0].path,
+    [
+      "libs/core/src/a.ts",
+      "libs/core/src/b.ts",
+      "libs/core/src/a.ts"
+    ]
+  );
+  assert_eq!(all.projects.len(), 1);
+  assert_eq!(all.projects[0].path, ["core", "ui", "core"]);
+  assert_eq!(
+    all.projects[0].via,
+    [
+      (
+        "libs/core/src/c.ts".to_string(),
+        "libs/ui/src/index.ts".to_string()
+      ),
+      (
+        "libs/ui/src/index.ts".to_string(),
+        "libs/core/src/d.ts".to_string()
+      ),
+    ]
+  );
+
+  let new =
+    find_new_cycles(&root, "main", None, projects, profiler).expect("find_new_cycles failed");
+  assert!(new.files.is_empty(), "a <-> b already existed at base");
+  assert_eq!(new.projects.len(), 1);
+  assert_eq!(new.projects[0].members, ["core", "ui"]);
+}
+
+/// Integration test: `domino unused-exports` follows re-export chains,
+/// namespace member access and dynamic imports, and skips entry files.
+#[test]
+fn test_unused_exports_follows_reexports_and_namespaces() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "libs/ui/src/components/button.ts",
+      "export const Button = () => 'button';\nexport const unusedHelper = () => 1;\n",
+    ),
+    (
+      "libs/ui/src/components/extra.ts",
+      "export const Badge = () => 'badge';\nexport const dead = 2;\n",
+    ),
+    (
+      "libs/ui/src/components/index.ts",
+      "export { Button } from './button';\nexport * from './extra';\n",
+    ),
+    (
+      "libs/ui/src/utils.ts",
+      "export const format = (v: number) => `${v}`;\nexport const legacy = 3;\n",
+    ),
+    (
+      "libs/ui/src/button.stories.ts",
+      "export default { title: 'Button' };\nexport const Primary = {};\n",
+    ),
+    (
+      "apps/web/src/lazy.ts",
+      "export const load = () => 'page';\nexport const other = 4;\n",
+    ),
+    (
+      "apps/web/src/index.ts",
+      "import { Button, Badge } from '../../../libs/ui/src/components';\nimport * as utils from '../../../libs/ui/src/utils';\n\nexport async function main() {\n  const page = await import('./lazy');\n  return [Button(), Badge(), utils.format(1), page.load()];\n}\n",
+    ),
+  ]);
+
+  let projects = vec![
+    barrel_project("ui", "libs/ui/src"),
+    barrel_project("web", "apps/web/src"),
+  ];
+  let patterns: Vec<String> = DEFAULT_ENTRY_PATTERNS
+    .iter()
+    .map(|pattern| pattern.to_string())
+    .collect();
+  let unused = find_unused_exports(&root, projects, &patterns, Arc::new(Profiler::new(false)))
+    .expect("find_unused_exports failed");
+
+  let found: Vec<(String, &str, usize)> = unused
+    .iter()
+    .map(|u| (u.file.display().to_string(), u.name.as_str(), u.line))
+    .collect();
+  assert_eq!(
+    found,
+    [
+      ("apps/web/src/lazy.ts".to_string(), "other", 2),
+      (
+        "libs/ui/src/components/button.ts".to_string(),
+        "unusedHelper",
+        2
+      ),
+      ("libs/ui/src/components/extra.ts".to_string(), "dead", 2),
+      ("libs/ui/src/utils.ts".to_string(), "legacy", 2),
+    ]
+  );
+  assert_eq!(unused[0].project, "web");
+}
+
+/// Integration test: `domino project-graph` folds file imports into typed
+/// project edges and flags package.json drift.
+#[test]
+fn test_project_graph_types_value_imports_used_only_as_types() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "package.json",
+      r#"{ "name": "root", "private": true, "workspaces": ["packages/*"] }"#,
+    ),
+    ("packages/app/package.json", r#"{ "name": "@org/app" }"#),
+    (
+      "packages/app/src/index.ts",
+      "import { Client } from '@org/client';\nimport { Logger } from '@org/logger';\n\nexport class Service {\n  constructor(private client: Client) {}\n  log(logger: Logger) {\n    return new Logger(logger);\n  }\n}\n",
+    ),
+    ("packages/client/package.json", r#"{ "name": "@org/client" }"#),
+    (
+      "packages/client/src/index.ts",
+      "export class Client {\n  get(url: string) {\n    return url;\n  }\n}\n",
+    ),
+    ("packages/logger/package.json", r#"{ "name": "@org/logger" }"#),
+    (
+      "packages/logger/src/index.ts",
+      "export class Logger {\n  constructor(readonly parent?: Logger) {}\n}\n",
+    ),
+  ]);
+
+  let projects = workspace::discover_projects(&root).expect("discover_projects failed");
+  let report = build_project_graph(&root, projects, Arc::new(Profiler::new(false)))
+    .expect("build_project_graph failed");
+
+  let app_edges: Vec<(String, String)> = report.graph.dependencies["@org/app"]
+    .iter()
+    .map(|d| (d.target.clone(), d.dependency_type.to_string()))
+    .collect();
+  assert_eq!(
+    app_edges,
+    [
+      ("@org/client".to_string(), "type-only".to_string()),
+      ("@org/logger".to_string(), "static".to_string()),
+    ]
+  );
+}
+
+#[test]
+fn test_project_graph_types_edges_and_flags_drift() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "package.json",
+      r#"{ "name": "root", "private": true, "workspaces": ["packages/*"] }"#,
+    ),
+    (
+      "packages/app/package.json",
+      r#"{ "name": "@org/app", "dependencies": { "@org/ui": "*", "@org/lazy": "*", "@org/legacy": "*" } }"#,
+    ),
+    (
+      "packages/app/src/index.ts",
+      "import { Button } from '@org/ui';\nimport type { Props } from '@org/types';\n\nexport const render = (props: Props) => Button(props);\nexport const page = () => import('@org/lazy').then((m) => m.load());\n",
+    ),
+    ("packages/ui/package.json", r#"{ "name": "@org/ui" }"#),
+    (
+      "packages/ui/src/index.ts",
+      "export const Button = (props: unknown) => props;\n",
+    ),
+    ("packages/types/package.json", r#"{ "name": "@org/types" }"#),
+    (
+      "packages/types/src/index.ts",
+      "export interface Props {\n  label: string;\n}\n",
+    ),
+    ("packages/lazy/package.json", r#"{ "name": "@org/lazy" }"#),
+    (
+      "packages/lazy/src/index.ts",
+      "export const load = () => 'page';\n",
+    ),
+    ("packages/legacy/package.json", r#"{ "name": "@org/legacy" }"#),
+    ("packages/legacy/src/index.ts", "export const old = 1;\n"),
+  ]);
+
+  let projects = workspace::discover_projects(&root).expect("discover_projects failed");
+  let report = build_project_graph(&root, projects, Arc::new(Profiler::new(false)))
+    .expect("build_project_graph failed");
+
+  let app_edges: Vec<(String, String)> = report.graph.dependencies["@org/app"]
+    .iter()
+    .map(|d| (d.target.clone(), d.dependency_type.to_string()))
+    .collect();
+  assert_eq!(
+    app_edges,
+    [
+      ("@org/lazy".to_string(), "dynamic".to_string()),
+      ("@org/types".to_string(), "type-only".to_string()),
+      ("@org/ui".to_string(), "static".to_string()),
+    ]
+  );
+  assert!(report.graph.dependencies["@org/ui"].is_empty());
+
+  let drift = |entries: &[DependencyDrift]| -> Vec<(String, String)> {
+    entries
+      .iter()
+      .map(|d| (d.project.clone(), d.dependency.clone()))
+      .collect()
+  };
+  assert_eq!(
+    drift(&report.undeclared_dependencies),
+    [("@org/app".to_string(), "@org/types".to_string())]
+  );
+  assert_eq!(
+    drift(&report.unused_dependencies),
+    [("@org/app".to_string(), "@org/legacy".to_string())]
+  );
+
+  let json = serde_json::to_value(&report).unwrap();
+  assert_eq!(json["graph"]["nodes"]["@org/ui"]["type"], "lib");
+  assert_eq!(
+    json["graph"]["dependencies"]["@org/app"][0]["type"],
+    "dynamic"
+  );
+}
+
+/// Integration test: `domino boundaries` checks project imports against
+/// `depConstraints` from `.eslintrc.json`, over the whole workspace or only
+/// the changed files.
+#[test]
+fn test_boundaries_reports_tag_violations() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "tsconfig.base.json",
+      r#"{ "compilerOptions": { "paths": {
+        "@org/shared": ["libs/shared/src/index.ts"],
+        "@org/web": ["apps/web/src/index.ts"]
+      } } }"#,
+    ),
+    (
+      ".eslintrc.json",
+      r#"{ "overrides": [{ "files": ["*.ts"], "rules": {
+        "@nx/enforce-module-boundaries": ["error", { "depConstraints": [
+          { "sourceTag": "scope:billing", "onlyDependOnLibsWithTags": ["scope:billing", "scope:shared"] }
+        ] }]
+      } }] }"#,
+    ),
+    (
+      "libs/billing/project.json",
+      r#"{ "name": "billing", "sourceRoot": "libs/billing/src", "tags": ["scope:billing"] }"#,
+    ),
+    (
+      "libs/billing/src/index.ts",
+      "import { format } from '@org/shared';\n\nexport const invoice = () => format(1);\nexport const page = () => import('@org/web');\n",
+    ),
+    (
+      "libs/shared/project.json",
+      r#"{ "name": "shared", "sourceRoot": "libs/shared/src", "tags": ["scope:shared"] }"#,
+    ),
+    (
+      "libs/shared/src/index.ts",
+      "export const format = (v: number) => `${v}`;\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src", "tags": ["scope:web"] }"#,
+    ),
+    ("apps/web/src/index.ts", "export const app = 1;\n"),
+  ]);
+
+  fs::write(
+    root.join("libs/billing/src/checkout.ts"),
+    "import { app } from '@org/web';\n\nexport const checkout = () => app;\n",
+  )
+  .unwrap();
+
+  let projects = workspace::discover_projects(&root).expect("discover_projects failed");
+  let profiler = Arc::new(Profiler::new(false));
+
+  let all = find_boundary_violations(&root, projects.clone(), None, profiler.clone())
+    .expect("find_boundary_violations failed");
+  let found: Vec<(String, usize, &str, &str)> = all
+    .iter()
+    .map(|v| {
+      (
+        v.file.display().to_string(),
+        v.line,
+        v.project.as_str(),
+        v.dependency.as_str(),
+      )
+    })
+    .collect();
+  assert_eq!(
+    found,
+    [
+      (
+        "libs/billing/src/checkout.ts".to_string(),
+        1,
+        "billing",
+        "web"
+      ),
+      ("libs/billing/src/index.ts".to_string(), 4, "billing", "web"),
+    ]
+  );
+  assert_eq!(
+    all[0].message,
+    "A project tagged with \"scope:billing\" can only depend on projects tagged with \"scope:billing\", \"scope:shared\""
+  );
+
+  let changed = [PathBuf::from("libs/billing/src/checkout.ts")];
+  let only_changed = find_boundary_violations(&root, projects, Some(&changed), profiler)
+    .expect("find_boundary_violations failed");
+  assert_eq!(only_changed.len(), 1);
+  assert_eq!(only_changed[0].specifier, "@org/web");
+}
+
+#[test]
+fn test_affected_filters_by_project_tags() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "tsconfig.base.json",
+      r#"{ "compilerOptions": { "paths": { "@org/ui": ["libs/ui/src/index.ts"] } } }"#,
+    ),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src", "tags": ["type:lib", "scope:shared"] }"#,
+    ),
+    (
+      "libs/ui/src/index.ts",
+      "export const button = () => 'button';\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src", "tags": ["type:app", "scope:web"] }"#,
+    ),
+    (
+      "apps/web/src/index.ts",
+      "import { button } from '@org/ui';\n\nexport const page = () => button();\n",
+    ),
+    (
+      "apps/web-e2e/project.json",
+      r#"{ "name": "web-e2e", "sourceRoot": "apps/web-e2e/src", "tags": ["type:e2e", "scope:web"], "implicitDependencies": ["web"] }"#,
+    ),
+    ("apps/web-e2e/src/app.spec.ts", "export const spec = 1;\n"),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/index.ts"),
+    "export const button = () => 'primary button';\n",
+  )
+  .unwrap();
+
+  let projects = workspace::discover_projects(&root).expect("discover_projects failed");
+  let web = projects.iter().find(|p| p.name == "web").unwrap();
+  assert_eq!(web.tags, ["type:app", "scope:web"]);
+
+  let affected = affected_in(&root, projects.clone());
+  assert_eq!(affected, ["ui", "web", "web-e2e"]);
+
+  let filter = |tags: &[&str], exclude: &[&str]| {
+    let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    filter_by_tags(
+      affected.clone(),
+      &projects,
+      &strings(tags),
+      &strings(exclude),
+    )
+  };
+  assert_eq!(filter(&["type:app", "type:lib"], &[]), ["ui", "web"]);
+  assert_eq!(filter(&["scope:*"], &["type:e2e"]), ["ui", "web"]);
+  assert_eq!(filter(&[], &["scope:web"]), ["ui"]);
+}
+
+#[test]
+fn test_affected_targets_from_project_json_and_scripts() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "package.json",
+      r#"{ "name": "root", "private": true, "workspaces": ["packages/*"] }"#,
+    ),
+    (
+      "packages/ui/package.json",
+      r#"{ "name": "@org/ui", "main": "src/index.ts", "scripts": { "build": "tsc", "lint": "eslint ." } }"#,
+    ),
+    (
+      "packages/ui/src/index.ts",
+      "export const button = () => 'button';\n",
+    ),
+    (
+      "packages/web/package.json",
+      r#"{ "name": "@org/web", "main": "src/index.ts", "dependencies": { "@org/ui": "*" }, "scripts": { "build": "next build", "test": "jest" } }"#,
+    ),
+    (
+      "packages/web/src/index.ts",
+      "import { button } from '@org/ui';\n\nexport const page = () => button();\n",
+    ),
+    (
+      "packages/docs/package.json",
+      r#"{ "name": "@org/docs", "main": "src/index.ts", "scripts": { "build": "vitepress build" } }"#,
+    ),
+    ("packages/docs/src/index.ts", "export const docs = 1;\n"),
+  ]);
+
+  fs::write(
+    root.join("packages/ui/src/index.ts"),
+    "export const button = () => 'primary button';\n",
+  )
+  .unwrap();
+
+  let projects = workspace::discover_projects(&root).expect("discover_projects failed");
+  let affected = affected_in(&root, projects.clone());
+  assert_eq!(affected, ["@org/ui", "@org/web"]);
+
+  let pairs: Vec<String> = select_targets(
+    &affected,
+    &projects,
+    &["build".to_string(), "test".to_string()],
+  )
+  .iter()
+  .map(|pair| pair.to_string())
+  .collect();
+  assert_eq!(pairs, ["@org/ui:build", "@org/web:build", "@org/web:test"]);
+}
+
+#[test]
+fn test_affected_targets_respect_nx_target_inputs() {
+  let (_tmp, root) = scaffold_repo(&[
+    (
+      "nx.json",
+      r#"{
+        "namedInputs": {
+          "default": ["{projectRoot}/**/*"],
+          "production": ["default", "!{projectRoot}/**/*.spec.ts"]
+        },
+        "targetDefaults": {
+          "build": { "inputs": ["production", "^production"] },
+          "test": { "inputs": ["default", "^production"] }
+        }
+      }"#,
+    ),
+    (
+      "tsconfig.base.json",
+      r#"{ "compilerOptions": { "paths": { "@org/ui": ["libs/ui/src/index.ts"] } } }"#,
+    ),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src", "targets": { "build": {}, "test": {} } }"#,
+    ),
+    (
+      "libs/ui/src/index.ts",
+      "export { button } from './button';\n",
+    ),
+    (
+      "libs/ui/src/button.ts",
+      "export const button = () => 'button';\n",
+    ),
+    (
+      "libs/ui/src/button.spec.ts",
+      "import { button } from './button';\n\nexport const check = () => button() === 'button';\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src", "targets": { "build": {}, "test": {} } }"#,
+    ),
+    (
+      "apps/web/src/index.ts",
+      "import { button } from '@org/ui';\n\nexport const page = () => button();\n",
+    ),
+  ]);
+  let projects = workspace::discover_projects(&root).expect("discover_projects failed");
+  let index = ProjectIndex::new(&projects, &root);
+  let inputs = NxInputs::load(&root, &projects).expect("nx workspace");
+  let targets = ["build".to_string(), "test".to_string()];
+  let affected_targets = || {
+    let affected = affected_in(&root, projects.clone());
+    let (changed_files, _) = domino::git::get_changed_files(&root, "main", None).unwrap();
+    let graph = build_project_graph(&root, projects.clone(), Arc::new(Profiler::new(false)))
+      .expect("build_project_graph failed")
+      .graph;
+    filter_targets_by_inputs(
+      &inputs,
+      select_targets(&affected, &projects, &targets),
+      &changed_files,
+      &index,
+      &graph,
+      false,
+    )
+    .iter()
+    .map(|pair| pair.to_string())
+    .collect::<Vec<_>>()
+  };
+
+  // A spec-only change only reruns the owning project's tests
+  fs::write(
+    root.join("libs/ui/src/button.spec.ts"),
+    "import { button } from './button';\n\nexport const check = () => button().length > 0;\n",
+  )
+  .unwrap();
+  assert_eq!(affected_targets(), ["ui:test"]);
+
+  fs::write(
+    root.join("libs/ui/src/button.ts"),
+    "export const button = () => 'primary button';\n",
+  )
+  .unwrap();
+  assert_eq!(
+    affected_targets(),
+    ["ui:build", "ui:test", "web:build", "web:test"]
+  );
+}
+
+#[test]
+fn test_affected_report_labels_test_only_projects() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "tsconfig.base.json",
+      r#"{ "compilerOptions": { "paths": {
+        "@org/ui": ["libs/ui/src/index.ts"],
+        "@org/utils": ["libs/utils/src/index.ts"]
+      } } }"#,
+    ),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    (
+      "libs/ui/src/index.ts",
+      "export const button = () => 'button';\n",
+    ),
+    (
+      "libs/utils/project.json",
+      r#"{ "name": "utils", "sourceRoot": "libs/utils/src" }"#,
+    ),
+    (
+      "libs/utils/src/index.ts",
+      "export const sum = (a: number, b: number) => a + b;\n",
+    ),
+    (
+      "libs/utils/src/sum.spec.ts",
+      "import { sum } from './index';\n\nexport const check = () => sum(1, 2) === 3;\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src" }"#,
+    ),
+    (
+      "apps/web/src/index.ts",
+      "import { sum } from '@org/utils';\n\nexport const total = () => sum(1, 2);\n",
+    ),
+    (
+      "apps/web/src/page.spec.ts",
+      "import { button } from '@org/ui';\n\nexport const check = () => button() === 'button';\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/index.ts"),
+    "export const button = () => 'primary button';\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("libs/utils/src/sum.spec.ts"),
+    "import { sum } from './index';\n\nexport const check = () => sum(2, 2) === 4;\n",
+  )
+  .unwrap();
+
+  let config = discovered_config(&root);
+  let scopes = || -> Vec<(String, AffectedScope)> {
+    let result = find_affected_with_report(config.clone(), Arc::new(Profiler::new(false)))
+      .expect("find_affected_with_report failed");
+    assert_eq!(result.affected_projects, ["ui", "utils", "web"]);
+    result
+      .report
+      .expect("expected a report")
+      .projects
+      .into_iter()
+      .map(|project| (project.name, project.scope))
+      .collect()
+  };
+  let expected = [
+    ("ui".to_string(), AffectedScope::Production),
+    ("utils".to_string(), AffectedScope::TestOnly),
+    ("web".to_string(), AffectedScope::TestOnly),
+  ];
+  assert_eq!(scopes(), expected);
+
+  // An invalid `domino.testPatterns` glob falls back to the default globs
+  // instead of failing the run
+  fs::write(
+    root.join("package.json"),
+    r#"{ "name": "root", "private": true, "domino": { "testPatterns": ["[invalid"] } }"#,
+  )
+  .unwrap();
+  assert_eq!(scopes(), expected);
+}
+
+#[test]
+fn test_affected_tests_follow_changed_symbols() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "tsconfig.base.json",
+      r#"{ "compilerOptions": { "paths": { "@org/ui": ["libs/ui/src/index.ts"] } } }"#,
+    ),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    (
+      "libs/ui/src/index.ts",
+      "export { button } from './button';\nexport { format } from './format';\n",
+    ),
+    ("libs/ui/src/button.ts", "export const button = () => 'button';\n"),
+    ("libs/ui/src/format.ts", "export const format = (v: number) => `${v}`;\n"),
+    (
+      "libs/ui/src/button.spec.ts",
+      "import { button } from './button';\n\ndescribe('button', () => {\n  it('renders', () => expect(button()).toBe('button'));\n});\n",
+    ),
+    (
+      "libs/ui/src/format.spec.ts",
+      "import { format } from './format';\n\ndescribe('format', () => {\n  it('formats', () => expect(format(1)).toBe('1'));\n});\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src" }"#,
+    ),
+    (
+      "apps/web/src/page.ts",
+      "import { button } from '@org/ui';\n\nexport const page = () => button();\n",
+    ),
+    (
+      "apps/web/src/__tests__/page.ts",
+      "import { page } from '../page';\n\ntest('page', () => expect(page()).toBeTruthy());\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/button.ts"),
+    "export const button = () => 'primary button';\n",
+  )
+  .unwrap();
+
+  let tests = find_affected_tests(
+    discovered_config(&root),
+    &[],
+    Arc::new(Profiler::new(false)),
+  )
+  .expect("find_affected_tests failed");
+
+  assert!(!tests.global);
+  assert_eq!(
+    tests.files,
+    [
+      PathBuf::from("apps/web/src/__tests__/page.ts"),
+      PathBuf::from("libs/ui/src/button.spec.ts"),
+    ]
+  );
+}
+
+#[test]
+fn test_affected_tests_honor_configured_test_patterns() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "package.json",
+      r#"{ "name": "root", "private": true, "domino": { "testPatterns": ["**/*.check.ts"] } }"#,
+    ),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    (
+      "libs/ui/src/button.ts",
+      "export const button = () => 'button';\n",
+    ),
+    (
+      "libs/ui/src/button.check.ts",
+      "import { button } from './button';\n\nexport const check = () => button() === 'button';\n",
+    ),
+    (
+      "libs/ui/src/other.check.ts",
+      "export const check = () => true;\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/button.ts"),
+    "export const button = () => 'primary button';\n",
+  )
+  .unwrap();
+
+  let tests = find_affected_tests(
+    discovered_config(&root),
+    &[],
+    Arc::new(Profiler::new(false)),
+  )
+  .expect("find_affected_tests failed");
+
+  assert_eq!(tests.files, [PathBuf::from("libs/ui/src/button.check.ts")]);
+}
+
+#[test]
+fn test_affected_stories_follow_changed_components() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    ("libs/ui/src/button.tsx", "export const Button = () => 'button';\n"),
+    ("libs/ui/src/card.tsx", "export const Card = () => 'card';\n"),
+    (
+      "libs/ui/src/button.stories.tsx",
+      "import { Button } from './button';\n\nexport default { title: 'UI/Button', component: Button };\n\nexport const Primary = {};\nexport const WithIcon = { name: 'With icon' };\n",
+    ),
+    (
+      "libs/ui/src/card.stories.tsx",
+      "import { Card } from './card';\n\nexport default { title: 'UI/Card', component: Card };\n\nexport const Default = {};\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/button.tsx"),
+    "export const Button = () => 'primary button';\n",
+  )
+  .unwrap();
+
+  let config = discovered_config(&root);
+  let patterns: Vec<String> = STORY_FILE_PATTERNS.iter().map(|p| p.to_string()).collect();
+  let affected = find_affected_stories(config, &patterns, Arc::new(Profiler::new(false)))
+    .expect("find_affected_stories failed");
+
+  let stories: Vec<(&str, &str)> = affected
+    .stories
+    .iter()
+    .map(|story| (story.id.as_str(), story.name.as_str()))
+    .collect();
+  assert_eq!(
+    stories,
+    [
+      ("ui-button--primary", "Primary"),
+      ("ui-button--with-icon", "With icon"),
+    ]
+  );
+}
+
+#[test]
+fn test_affected_stories_autotitle_from_storybook_config() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    (
+      "libs/ui/.storybook/main.ts",
+      "const config = {\n  stories: [{ directory: '../src', titlePrefix: 'UI' }],\n};\nexport default config;\n",
+    ),
+    ("libs/ui/src/button/button.tsx", "export const Button = () => 'button';\n"),
+    (
+      "libs/ui/src/button/button.stories.tsx",
+      "import { Button } from './button';\n\nexport default { component: Button };\n\nexport const Primary = {};\n",
+    ),
+    (
+      "libs/other/project.json",
+      r#"{ "name": "other", "sourceRoot": "libs/other" }"#,
+    ),
+    ("libs/other/card.tsx", "export const Card = () => 'card';\n"),
+    (
+      "libs/other/card.stories.tsx",
+      "import { Card } from './card';\n\nexport default { component: Card };\n\nexport const Default = {};\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/button/button.tsx"),
+    "export const Button = () => 'primary button';\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("libs/other/card.tsx"),
+    "export const Card = () => 'plain card';\n",
+  )
+  .unwrap();
+
+  let config = discovered_config(&root);
+  let patterns: Vec<String> = STORY_FILE_PATTERNS.iter().map(|p| p.to_string()).collect();
+  let affected = find_affected_stories(config, &patterns, Arc::new(Profiler::new(false)))
+    .expect("find_affected_stories failed");
+
+  let stories: Vec<(&str, &str)> = affected
+    .stories
+    .iter()
+    .map(|story| (story.id.as_str(), story.title.as_str()))
+    .collect();
+  assert_eq!(stories, [("ui-button--primary", "UI/button")]);
+  assert_eq!(
+    affected.untitled,
+    [PathBuf::from("libs/other/card.stories.tsx")]
+  );
+}
+
+#[test]
+fn test_affected_routes_map_reached_modules_to_url_paths() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web" }"#,
+    ),
+    ("apps/web/next.config.js", "module.exports = {};\n"),
+    ("apps/web/lib/money.ts", "export const money = (v: number) => `$${v}`;\n"),
+    (
+      "apps/web/app/layout.tsx",
+      "export default function RootLayout({ children }) { return children; }\n",
+    ),
+    ("apps/web/app/page.tsx", "export default function Home() { return 'home'; }\n"),
+    (
+      "apps/web/app/(shop)/billing/[id]/page.tsx",
+      "import { money } from '../../../../lib/money';\n\nexport default function Invoice() { return money(1); }\n",
+    ),
+    (
+      "apps/web/app/settings/layout.tsx",
+      "export default function SettingsLayout({ children }) { return children; }\n",
+    ),
+    (
+      "apps/web/app/settings/profile/page.tsx",
+      "export default function Profile() { return 'profile'; }\n",
+    ),
+    (
+      "apps/shop/project.json",
+      r#"{ "name": "shop", "sourceRoot": "apps/shop/app" }"#,
+    ),
+    (
+      "apps/shop/app/root.tsx",
+      "export default function App() { return 'app'; }\n",
+    ),
+    (
+      "apps/shop/app/routes/_index.tsx",
+      "export default function Index() { return 'index'; }\n",
+    ),
+    (
+      "apps/shop/app/routes/cart.tsx",
+      "export default function Cart() { return 'cart'; }\n",
+    ),
+    (
+      "apps/shop/app/routes/cart.$item.tsx",
+      "export default function CartItem() { return 'item'; }\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("apps/web/lib/money.ts"),
+    "export const money = (v: number) => `USD ${v}`;\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("apps/web/app/settings/layout.tsx"),
+    "export default function SettingsLayout({ children }) { return ['settings', children]; }\n",
+  )
+  .unwrap();
+  fs::write(
+    root.join("apps/shop/app/routes/cart.tsx"),
+    "export default function Cart() { return 'basket'; }\n",
+  )
+  .unwrap();
+
+  let affected = find_affected_routes(discovered_config(&root), Arc::new(Profiler::new(false)))
+    .expect("find_affected_routes failed");
+
+  assert!(!affected.global);
+  let routes: Vec<(&str, &str)> = affected
+    .routes
+    .iter()
+    .map(|route| (route.project.as_str(), route.route.as_str()))
+    .collect();
+  assert_eq!(
+    routes,
+    [
+      ("shop", "/cart"),
+      ("shop", "/cart/$item"),
+      ("web", "/billing/[id]"),
+      ("web", "/settings/profile"),
+    ]
+  );
+}
+
+#[test]
+fn test_affected_chunks_separate_initial_bundle_from_lazy_chunks() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src", "targets": { "build": { "options": { "main": "apps/web/src/main.ts" } } } }"#,
+    ),
+    (
+      "apps/web/src/main.ts",
+      "import { start } from './app';\n\nstart();\n",
+    ),
+    (
+      "apps/web/src/app.ts",
+      "import { title } from './title';\n\nexport const start = () => title;\nexport const loadBilling = () => import('./billing').then((m) => m.billing);\n",
+    ),
+    ("apps/web/src/title.ts", "export const title = 'web';\n"),
+    (
+      "apps/web/src/billing.ts",
+      "import { chart } from './chart';\n\nexport const billing = () => chart();\n",
+    ),
+    ("apps/web/src/chart.ts", "export const chart = () => 'bars';\n"),
+  ]);
+
+  let affected_chunks = || {
+    find_affected_chunks(
+      discovered_config(&root),
+      &[],
+      Arc::new(Profiler::new(false)),
+    )
+    .expect("find_affected_chunks failed")
+  };
+
+  fs::write(
+    root.join("apps/web/src/chart.ts"),
+    "export const chart = () => 'lines';\n",
+  )
+  .unwrap();
+  let affected = affected_chunks();
+  assert!(!affected.initial);
+  assert_eq!(affected.chunks.len(), 1);
+  assert_eq!(affected.chunks[0].kind, ChunkKind::Lazy);
+  assert_eq!(
+    affected.chunks[0].root,
+    PathBuf::from("apps/web/src/billing.ts")
+  );
+  assert_eq!(
+    affected.chunks[0].files,
+    [
+      PathBuf::from("apps/web/src/billing.ts"),
+      PathBuf::from("apps/web/src/chart.ts"),
+    ]
+  );
+
+  fs::write(
+    root.join("apps/web/src/title.ts"),
+    "export const title = 'app';\n",
+  )
+  .unwrap();
+  let affected = affected_chunks();
+  assert!(affected.initial);
+  let roots: Vec<(ChunkKind, &Path)> = affected
+    .chunks
+    .iter()
+    .map(|chunk| (chunk.kind, chunk.root.as_path()))
+    .collect();
+  assert_eq!(
+    roots,
+    [
+      (ChunkKind::Entry, Path::new("apps/web/src/main.ts")),
+      (ChunkKind::Lazy, Path::new("apps/web/src/billing.ts")),
+    ]
+  );
+}
+
+#[test]
+fn test_module_federation_hosts_affected_by_exposed_modules() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "apps/shop/project.json",
+      r#"{ "name": "shop", "sourceRoot": "apps/shop/src" }"#,
+    ),
+    (
+      "apps/shop/module-federation.config.ts",
+      "const config = {\n  name: 'shop',\n  exposes: {\n    './Button': 'apps/shop/src/button.tsx',\n    './Price': './src/price.ts',\n  },\n};\nexport default config;\n",
+    ),
+    ("apps/shop/src/button.tsx", "export const Button = () => 'buy';\n"),
+    ("apps/shop/src/price.ts", "export const price = () => 10;\n"),
+    (
+      "apps/shell/project.json",
+      r#"{ "name": "shell", "sourceRoot": "apps/shell/src" }"#,
+    ),
+    (
+      "apps/shell/module-federation.config.ts",
+      "module.exports = { name: 'shell', remotes: ['shop'] };\n",
+    ),
+    (
+      "apps/shell/src/app.tsx",
+      "import { Button } from 'shop/Button';\n\nexport const App = () => Button();\n",
+    ),
+    (
+      "apps/checkout/project.json",
+      r#"{ "name": "checkout", "sourceRoot": "apps/checkout/src" }"#,
+    ),
+    (
+      "apps/checkout/module-federation.config.ts",
+      "module.exports = { name: 'checkout', remotes: [['store', 'http://localhost:4201']] };\n",
+    ),
+    (
+      "apps/checkout/src/app.ts",
+      "export const total = () => import('shop/Price').then((m) => m.price());\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("apps/shop/src/button.tsx"),
+    "export const Button = () => 'add to cart';\n",
+  )
+  .unwrap();
+
+  // checkout does not declare the `shop` remote, so `shop/Price` stays external
+  assert_eq!(affected_with(discovered_config(&root)), ["shell", "shop"]);
+}
+
+#[test]
+fn test_angular_templates_link_selectors_and_pipes() {
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    (
+      "libs/ui/src/user-card.component.ts",
+      "import { Component, Input } from '@angular/core';\n\n@Component({\n  selector: 'app-user-card',\n  template: '<span>{{ name }}</span>',\n})\nexport class UserCardComponent {\n  @Input() name = '';\n}\n",
+    ),
+    (
+      "libs/ui/src/money.pipe.ts",
+      "import { Pipe, PipeTransform } from '@angular/core';\n\n@Pipe({ name: 'money' })\nexport class MoneyPipe implements PipeTransform {\n  transform(value: number) {\n    return value.toFixed(2);\n  }\n}\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src" }"#,
+    ),
+    (
+      "apps/web/src/page.component.ts",
+      "import { Component } from '@angular/core';\n\n@Component({\n  selector: 'app-page',\n  templateUrl: './page.component.html',\n})\nexport class PageComponent {\n  user = 'Ada';\n}\n",
+    ),
+    (
+      "apps/web/src/page.component.html",
+      "<app-user-card [name]=\"user\"></app-user-card>\n",
+    ),
+    (
+      "apps/admin/project.json",
+      r#"{ "name": "admin", "sourceRoot": "apps/admin/src" }"#,
+    ),
+    (
+      "apps/admin/src/total.component.ts",
+      "import { Component } from '@angular/core';\n\n@Component({\n  selector: 'app-total',\n  template: `<b>{{ total | money }}</b>`,\n})\nexport class TotalComponent {\n  total = 42;\n}\n",
+    ),
+  ]);
+
+  fs::write(
+    root.join("libs/ui/src/user-card.component.ts"),
+    "import { Component, Input } from '@angular/core';\n\n@Component({\n  selector: 'app-user-card',\n  template: '<span>{{ name }}</span>',\n})\nexport class UserCardComponent {\n  @Input() name = 'guest';\n}\n",
+  )
+  .unwrap();
+
+  // Nothing imports UserCardComponent; web uses it through its selector only.
+  // admin only uses the unchanged pipe.
+  assert_eq!(affected_with(discovered_config(&root)), ["ui", "web"]);
+
+  fs::write(
+    root.join("libs/ui/src/money.pipe.ts"),
+    "import { Pipe, PipeTransform } from '@angular/core';\n\n@Pipe({ name: 'money' })\nexport class MoneyPipe implements PipeTransform {\n  transform(value: number) {\n    return value.toFixed(0);\n  }\n}\n",
+  )
+  .unwrap();
+
+  assert_eq!(
+    affected_with(discovered_config(&root)),
+    ["admin", "ui", "web"]
+  );
+}
+
+#[test]
+fn test_single_file_components_trace_scripts_and_markup() {
+  let user_card = "<template>\n  <span class=\"card\">{{ formatName(name) }}</span>\n</template>\n\n<script setup lang=\"ts\">\nimport { formatName } from './format';\n\nconst name = 'Ada';\n</script>\n";
+  let (_tmp, root) = scaffold_repo(&[
+    ("nx.json", "{}"),
+    (
+      "tsconfig.base.json",
+      r#"{ "compilerOptions": { "paths": { "@org/ui": ["libs/ui/src/index.ts"] } } }"#,
+    ),
+    (
+      "libs/ui/project.json",
+      r#"{ "name": "ui", "sourceRoot": "libs/ui/src" }"#,
+    ),
+    (
+      "libs/ui/src/format.ts",
+      "export const formatName = (name: string) => name.toUpperCase();\n\nexport const formatDate = (date: Date) => date.toISOString();\n",
+    ),
+    ("libs/ui/src/UserCard.vue", user_card),
+    (
+      "libs/ui/src/index.ts",
+      "export { default as UserCard } from './UserCard.vue';\nexport * from './format';\n",
+    ),
+    (
+      "apps/web/project.json",
+      r#"{ "name": "web", "sourceRoot": "apps/web/src" }"#,
+    ),
+    (
+      "apps/web/src/App.vue",
+      "<template>\n  <user-card />\n</template>\n\n<script setup lang=\"ts\">\nimport { UserCard } from '@org/ui';\n</script>\n",
+    ),
+    (
+      "apps/kiosk/project.json",
+      r#"{ "name": "kiosk", "sourceRoot": "apps/kiosk/src" }"#,
+    ),
+    (
+      "apps/kiosk/src/Clock.svelte",
+      "<script>\n  import { formatDate } from '@org/ui';\n  let now = new Date();\n</script>\n\n<p>{formatDate(now)}</p>\n",
+    ),
+    (
+      "apps/site/project.json",
+      r#"{ "name": "site", "sourceRoot": "apps/site/src" }"#,
+    ),
+    (
+      "apps/site/src/pages/index.astro",
+      "---\nimport { formatDate } from '@org/ui';\nconst updated = formatDate(new Date());\n---\n<footer>{updated}</footer>\n",
+    ),
+  ]);
+
+  let affected = || affected_with(discovered_config(&root));
+
+  // A markup-only change is a change to the component's default export
+  fs::write(
+    root.join("libs/ui/src/UserCard.vue"),
+    user_card.replace("class=\"card\"", "class=\"card card--large\""),
+  )
+  .unwrap();
+  assert_eq!(affected(), ["ui", "web"]);
+
+  // Script imports are traced to their uses in Svelte markup and Astro
+  // frontmatter
+  fs::write(root.join("libs/ui/src/UserCard.vue"), user_card).unwrap();
+  fs::write(
+    root.join("libs/ui/src/format.ts"),
+    "export const formatName = (name: string) => name.toUpperCase();\n\nexport const formatDate = (date: Date) => date.toLocaleDateString();\n",
+  )
+  .unwrap();
+  assert_eq!(affected(), ["kiosk", "site", "ui"]);
+}